@@ -187,6 +187,8 @@ import('../target/wasm32-unknown-unknown/release/cb_browser_ui').then(mod => mod
                                     <ToWindowPortal>
                                         <Time.Windows state={this.state} setState={this.boundSetState} />
                                         <Debug.Windows state={this.state} setState={this.boundSetState} />
+                                        <Transport.RouteExplanationWindow state={this.state} setState={this.boundSetState} />
+                                        <Planning.PlanWarningsWindow state={this.state} setState={this.boundSetState} />
                                     </ToWindowPortal>
 
                                     <Utils.Interactive3DContext.Provider value={interactive3Dshapes} >
@@ -196,6 +198,7 @@ import('../target/wasm32-unknown-unknown/release/cb_browser_ui').then(mod => mod
                                             <LandUse.Layers state={this.state} />
                                             <Vegetation.Layers state={this.state} />
                                             <Transport.Layers state={this.state} />
+                                            <Households.Layers state={this.state} />
 
                                         </Utils.RenderContext.Provider>
                                     </Utils.Interactive3DContext.Provider>
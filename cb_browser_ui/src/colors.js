@@ -54,6 +54,10 @@ export default {
     canopies: [0.3, 0.5, 0.2],
     asphalt: [0.6, 0.6, 0.6],
     roadMarker: [1.0, 1.0, 1.0],
+    incident: [0.9, 0.3, 0.1],
+    routeExplanation: [0.2, 0.5, 0.9],
+    unsatisfied: [0.9, 0.2, 0.1],
+    satisfied: [0.2, 0.8, 0.3],
 
     WhiteWall: [0.95, 0.95, 0.95],
     FlatRoof: [0.5, 0.5, 0.5],
@@ -152,5 +156,12 @@ export default {
         [98.0, 127.0, 95.0],   // forest green
         [219.0, 182.0, 108.0], // soft orange
         [146.0, 52.0, 64.0],   // dark red
-    ].map(toLinFloat))
+    ].map(toLinFloat)),
+
+    vehicleTypes: {
+        DeliveryVan: toLinFloat([240.0, 240.0, 235.0]),
+        Truck: toLinFloat([70.0, 90.0, 130.0]),
+        Bus: toLinFloat([230.0, 190.0, 40.0]),
+        Bicycle: toLinFloat([60.0, 170.0, 90.0]),
+    }
 }
\ No newline at end of file
@@ -1,6 +1,8 @@
 import React from 'react';
 import { Button, InputNumber } from 'antd';
 import { fmtId } from '../browser_utils/Utils';
+import { ModeShare } from '../transport_browser/Transport';
+import { PriceIndices, Employment, Treasury, Ledger, tradedResources } from '../households_browser/Households';
 
 export const initialState = {
     show: false,
@@ -12,6 +14,17 @@ export const initialState = {
     spawnCarsSettings: {
         triesPerLane: 50
     },
+    trajectoryRecordingSettings: {
+        durationSeconds: 60
+    },
+    hierarchicalRouting: false,
+    trafficDemandConfig: JSON.stringify({
+        zones: [{ center: [-500, 0], radius: 300 }, { center: [500, 0], radius: 300 }],
+        flows: [{ origin: 0, destination: 1, trips_per_hour: 600 }],
+        generation_rates: [100, 100],
+        time_of_day_profile: []
+    }, null, 2),
+    trafficDemandError: null,
     logLastEntry: 0,
     logTextStart: 0,
     logFirstEntry: 0,
@@ -30,7 +43,18 @@ export function Windows(props) {
 
     if (state.debug.show) {
         if (!refreshInterval) {
-            refreshInterval = setInterval(() => cbRustBrowser.get_newest_log_messages(), 300);
+            refreshInterval = setInterval(() => {
+                cbRustBrowser.get_newest_log_messages();
+                cbRustBrowser.get_trip_statistics();
+                cbRustBrowser.get_employment_statistics();
+                cbRustBrowser.get_treasury_info();
+                if (window.cbReactApp.state.households.ledger.enabled) {
+                    cbRustBrowser.get_money_flows();
+                }
+                for (let resource of tradedResources) {
+                    cbRustBrowser.get_price_index(resource);
+                }
+            }, 300);
         }
     } else {
         if (refreshInterval) {
@@ -102,6 +126,55 @@ export function Windows(props) {
                                 state.debug.spawnCarsSettings.triesPerLane
                             )}>Spawn cars</Button>
                 </div>
+                <div key="trajectoryRecording">
+                    Record trajectories for (sim seconds)
+                <InputNumber
+                        value={state.debug.trajectoryRecordingSettings.durationSeconds}
+                        onChange={(durationSeconds) => setState(oldState => update(oldState, {
+                            debug: { trajectoryRecordingSettings: { durationSeconds: { $set: durationSeconds } } }
+                        }))}
+                        min={1} /> <Button
+                            onClick={() => cbRustBrowser.start_trajectory_recording(
+                                state.time.ticks,
+                                state.debug.trajectoryRecordingSettings.durationSeconds
+                            )}>Start recording</Button> <Button
+                            onClick={() => cbRustBrowser.stop_trajectory_recording()}>Stop &amp; export</Button>
+                </div>
+                <div key="routing">
+                    <Button
+                        onClick={() => {
+                            cbRustBrowser.set_hierarchical_routing(!state.debug.hierarchicalRouting);
+                            setState(oldState => update(oldState, {
+                                debug: { hierarchicalRouting: { $apply: e => !e } }
+                            }));
+                        }}>{state.debug.hierarchicalRouting ? "Use gossip routing" : "Use hierarchical routing"}</Button> <Button
+                            onClick={() => cbRustBrowser.check_routing_consistency()}>Check routing consistency</Button>
+                </div>
+                <div key="trafficDemand">
+                    Traffic demand (zones, OD flows, trips per zone and hourly factors)
+                    <textarea
+                        rows={8}
+                        style={{ width: "100%", fontFamily: "monospace" }}
+                        value={state.debug.trafficDemandConfig}
+                        onChange={(e) => setState(oldState => update(oldState, {
+                            debug: { trafficDemandConfig: { $set: e.target.value } }
+                        }))} />
+                    {state.debug.trafficDemandError && <div>{state.debug.trafficDemandError}</div>}
+                    <Button
+                        onClick={() => {
+                            try {
+                                cbRustBrowser.set_traffic_demand(JSON.parse(state.debug.trafficDemandConfig));
+                                setState(oldState => update(oldState, {
+                                    debug: { trafficDemandError: { $set: null } }
+                                }));
+                            } catch (e) {
+                                setState(oldState => update(oldState, {
+                                    debug: { trafficDemandError: { $set: e.toString() } }
+                                }));
+                            }
+                        }}>Generate demand</Button> <Button
+                            onClick={() => cbRustBrowser.stop_traffic_demand()}>Stop demand</Button>
+                </div>
                 <div key="rendering">
                     <Button
                         onClick={() => setState(
@@ -121,6 +194,26 @@ export function Windows(props) {
                     <div>{message}: {state.system.messageStats[message]}</div>
                 )}</div>
             </details>
+            <details>
+                <summary>Trip Statistics</summary>
+                <ModeShare state={state} />
+            </details>
+            <details>
+                <summary>Employment</summary>
+                <Employment state={state} />
+            </details>
+            <details>
+                <summary>City Treasury</summary>
+                <Treasury state={state} />
+            </details>
+            <details>
+                <summary>Ledger</summary>
+                <Ledger state={state} setState={setState} />
+            </details>
+            <details>
+                <summary>Prices</summary>
+                <PriceIndices state={state} />
+            </details>
             <details>
                 <summary>Simulation Log</summary>
                 <div className="scrollableLog">{state.debug.logEntries.map((entry, i) => {
@@ -9,6 +9,10 @@ use cb_planning::plan_manager::ProjectID;
 use planning::{CBPlanManagerID, CBGestureIntent};
 use transport::transport_planning::RoadLaneConfig;
 use descartes::{Corner};
+use transport::microtraffic::trajectories::TrajectoryRecorderID;
+use transport::pathfinding::hierarchical_routing::HierarchicalRouterID;
+use transport::demand::{DemandConfig, TrafficDemandGeneratorID};
+use cb_time::units::{Instant, Duration};
 
 #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
 pub fn plan_grid(project_id: Serde<ProjectID>, n: Serde<isize>, n_lanes: Serde<u8>, spacing: Serde<f32>) {
@@ -32,7 +36,8 @@ pub fn plan_grid(project_id: Serde<ProjectID>, n: Serde<isize>, n_lanes: Serde<u
                 vec![Corner::new(p1, Some(d), Some(d)), Corner::new(p2, Some(d), Some(d))],
                 RoadLaneConfig {
                     n_lanes_forward: n_lanes.0,
-                    n_lanes_backward: n_lanes.0
+                    n_lanes_backward: n_lanes.0,
+                    bike_lanes: false
                 }
             )),
             world,
@@ -51,7 +56,8 @@ pub fn plan_grid(project_id: Serde<ProjectID>, n: Serde<isize>, n_lanes: Serde<u
                 vec![Corner::new(p1, Some(d), Some(d)), Corner::new(p2, Some(d), Some(d))],
                 RoadLaneConfig {
                     n_lanes_forward: n_lanes.0,
-                    n_lanes_backward: n_lanes.0
+                    n_lanes_backward: n_lanes.0,
+                    bike_lanes: false
                 }
             )),
             world,
@@ -68,6 +74,52 @@ pub fn spawn_cars(tries_per_lane: usize) {
     }
 }
 
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn start_trajectory_recording(from_ticks: u32, duration_seconds: u32) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    TrajectoryRecorderID::global_first(world).start_recording(
+        Instant::new(from_ticks as usize),
+        Duration::from_seconds(duration_seconds as usize),
+        world,
+    );
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn stop_trajectory_recording() {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    TrajectoryRecorderID::global_first(world).stop_recording(world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn set_hierarchical_routing(enabled: bool) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    HierarchicalRouterID::global_first(world).set_enabled(enabled, world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn check_routing_consistency() {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    HierarchicalRouterID::global_first(world).check_consistency(world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn set_traffic_demand(config: Serde<DemandConfig>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    TrafficDemandGeneratorID::global_first(world).set_demand(config.0, world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn stop_traffic_demand() {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    TrafficDemandGeneratorID::global_first(world).stop(world);
+}
+
 use kay::{World, ActorSystem};
 use compact::{CVec, CString};
 use cb_util::log::{LogID, LogRecipient, LogRecipientID, Entry};
@@ -1,7 +1,10 @@
 import React from 'react';
+import { Button } from 'antd';
 import update from 'immutability-helper';
 import { fmtId } from '../browser_utils/Utils';
-import { Interactive3DShape } from '../browser_utils/Utils';
+import { Interactive3DShape, RenderLayer } from '../browser_utils/Utils';
+import colors from '../colors';
+import renderOrder from '../renderOrder';
 
 export const initialState = {
     buildingPositions: {},
@@ -10,8 +13,23 @@ export const initialState = {
     inspectedBuildingPinned: false,
     inspectedBuildingState: null,
     householdInfo: {},
+    priceIndices: {},
+    employment: null,
+    treasury: null,
+    satisfactionMap: null,
+    ledger: {
+        enabled: false,
+        moneyFlows: [],
+        nEntries: 0,
+        supplyChain: null,
+    },
 };
 
+export const tradedResources = [
+    "Groceries", "Produce", "Grain", "Flour", "BakedGoods", "Meat", "DairyGoods",
+    "Wood", "Furniture", "TextileGoods", "Clothes", "Devices", "Entertainment", "Services"
+];
+
 export function render(state, setState) {
     return {};
 }
@@ -73,6 +91,8 @@ export function Windows(props) {
         return <BuildingInfo
             buildingPosition2d={props.project3dTo2d(buildingPosition3d)}
             pinned={inspectedBuildingPinned}
+            now={props.state.time.ticks}
+            ledgerEnabled={props.state.households.ledger.enabled}
             {...{ inspectedBuilding, inspectedBuildingState, householdInfo, closeWindow }}
         />
     } else {
@@ -115,10 +135,11 @@ class BuildingInfo extends React.Component {
             {this.props.pinned && <a className="close-window" onClick={this.props.closeWindow}>×</a>}
             {this.props.inspectedBuildingState && [
                 <h1>{this.props.inspectedBuildingState.style}</h1>,
+                <p>Parking: {this.props.inspectedBuildingState.parking.occupied}/{this.props.inspectedBuildingState.parking.capacity} on lot, {this.props.inspectedBuildingState.parkedOnStreet} on street</p>,
                 <div className="household-list">
                     {this.props.inspectedBuildingState.households.map(id => <div className="household">{[
                         <h3>{fmtId(id)}</h3>,
-                        this.props.householdInfo[id] && <HouseholdInfo core={this.props.householdInfo[id].core} id={id} here={this.props.inspectedBuilding} />
+                        this.props.householdInfo[id] && <HouseholdInfo core={this.props.householdInfo[id].core} id={id} here={this.props.inspectedBuilding} now={this.props.now} ledgerEnabled={this.props.ledgerEnabled} />
                     ]}</div>)}
                 </div>
             ]}
@@ -127,16 +148,24 @@ class BuildingInfo extends React.Component {
 }
 
 function HouseholdInfo(props) {
-    const { resources, member_resources, member_tasks } = props.core;
+    const { resources, member_resources, member_tasks, member_jobs, rent, operating_costs, insolvent_days, storage_capacities, satisfaction, member_satisfaction, member_demographics } = props.core;
+    const capacityOf = resource => (storage_capacities.entries.find(([stored]) => stored == resource) || [])[1];
 
     return [
+        <p>Satisfaction: {fmtSatisfaction(satisfaction)}</p>,
+        <p>Rent: {rent.toFixed(2)}/day{operating_costs > 0 && `, operating costs: ${operating_costs.toFixed(2)}/day`}</p>,
+        insolvent_days > 0 && <p>Insolvent for {insolvent_days} days!</p>,
         resources.entries.map(([resource, amount]) =>
-            <p>{resource}: {amount.toFixed(2)}</p>
+            <p>{resource}: {amount.toFixed(2)}{capacityOf(resource) !== undefined && ` / ${capacityOf(resource).toFixed(0)}`}{props.ledgerEnabled && tradedResources.includes(resource) &&
+                <a onClick={() => cbRustBrowser.trace_supply_chain(props.id, resource)}> (trace)</a>}</p>
         ),
         member_resources.map((memberResources, memberI) =>
-            [
+            member_demographics[memberI].role == "Departed" ? [] : [
                 <h4>Member {memberI}</h4>,
-                <p><StateAndGoal here={props.here} state={member_tasks[memberI].state} goal={member_tasks[memberI].goal} /></p>,
+                <p>{member_demographics[memberI].role}, {Math.floor(member_demographics[memberI].age)} years old</p>,
+                <p>Satisfaction: {fmtSatisfaction(member_satisfaction[memberI])}</p>,
+                <p><StateAndGoal here={props.here} now={props.now} state={member_tasks[memberI].state} goal={member_tasks[memberI].goal} /></p>,
+                <p><Job job={member_jobs[memberI]} /></p>,
                 memberResources.entries.map(([resource, amount]) =>
                     <p>{resource}: {amount.toFixed(2)}</p>
                 ),
@@ -145,9 +174,103 @@ function HouseholdInfo(props) {
     ]
 }
 
+function fmtSatisfaction(satisfaction) {
+    return `${(satisfaction * 100).toFixed(0)}%`;
+}
+
+function Job(props) {
+    const { job } = props;
+
+    if (job) {
+        const fmtTime = ({ minutes_of_day }) =>
+            `${Math.floor(minutes_of_day / 60)}:${("0" + minutes_of_day % 60).slice(-2)}`;
+        return `Works at ${fmtId(job.offer.household)} from ${fmtTime(job.shift.start)} to ${fmtTime(job.shift.end)} for ${job.wage.toFixed(2)}.`;
+    } else {
+        return "Unemployed.";
+    }
+}
+
+export function Employment(props) {
+    const { employment } = props.state.households;
+
+    if (employment) {
+        return <div>
+            <div>Labor force: {employment.labor_force}</div>
+            <div>Employed: {employment.employed}</div>
+            <div>Unemployment: {(employment.unemployment_rate * 100).toFixed(1)}%</div>
+            <div>Open positions: {employment.vacancies}</div>
+        </div>;
+    } else {
+        return <div>unknown</div>;
+    }
+}
+
+export function Treasury(props) {
+    const { treasury } = props.state.households;
+
+    if (treasury) {
+        return <div>
+            <div>Balance: {treasury.balance.toFixed(2)}</div>
+            <div>Rent collected: {treasury.rent_collected.toFixed(2)}</div>
+        </div>;
+    } else {
+        return <div>unknown</div>;
+    }
+}
+
+export function PriceIndices(props) {
+    const { priceIndices } = props.state.households;
+
+    return <div>
+        {tradedResources.map(resource => {
+            const priceIndex = priceIndices[resource];
+            return <div key={resource}>
+                {resource}: {priceIndex
+                    ? (priceIndex.n_offers > 0
+                        ? `${(priceIndex.index * 100).toFixed(1)}% of initial price, ${priceIndex.mean_unit_price.toFixed(2)} per unit from ${priceIndex.n_offers} offers`
+                        : "no offers")
+                    : "unknown"}
+            </div>
+        })}
+    </div>;
+}
+
+export function Ledger(props) {
+    const { state, setState } = props;
+    const { ledger } = state.households;
+
+    return <div>
+        <Button
+            onClick={() => {
+                cbRustBrowser.set_ledger_enabled(!ledger.enabled);
+                setState(oldState => update(oldState, {
+                    households: { ledger: { enabled: { $apply: e => !e } } }
+                }));
+            }}>{ledger.enabled ? "Stop recording deals" : "Record deals"}</Button>
+        {ledger.enabled && [
+            <div>{ledger.nEntries} deals recorded</div>,
+            <h4>Money flows</h4>,
+            ledger.moneyFlows.map(({ from, to, amount }) =>
+                <div>{from} → {to}: {amount.toFixed(2)}</div>
+            ),
+            ledger.supplyChain && [
+                <h4>Supply chain of {ledger.supplyChain.resource} at {fmtId(ledger.supplyChain.household)}</h4>,
+                ledger.supplyChain.links.length > 0
+                    ? ledger.supplyChain.links.map(link =>
+                        <div style={{ paddingLeft: `${link.depth}em` }}>
+                            {link.amount.toFixed(2)} {link.resource} from {link.provider_kind} {fmtId(link.provider)} to {fmtId(link.receiver)}
+                        </div>
+                    )
+                    : <div>No recorded deliveries</div>
+            ]
+        ]}
+    </div>;
+}
+
 function StateAndGoal(props) {
     let statePart;
     let goalGerund = false;
+    let explainRoute = null;
 
     if (props.state) {
         if (props.state.IdleAt) {
@@ -162,6 +285,7 @@ function StateAndGoal(props) {
             goalGerund = true;
         } else if (props.state.InTrip) {
             statePart = "On the way to"
+            explainRoute = <a onClick={() => cbRustBrowser.explain_trip_route(props.state.InTrip, props.now)}> (explain route)</a>
         } else {
             statePart = JSON.stringify(props)
         }
@@ -169,18 +293,89 @@ function StateAndGoal(props) {
         let goalPart;
 
         if (props.goal) {
-            if (props.goal[0] == "Money") {
-                goalPart = (goalGerund ? "working at " : "work at ") + fmtId(props.goal[1].household)
-            } else if (props.goal[0] == "Wakefulness") {
-                goalPart = (goalGerund ? "sleeping at " : "sleep at ") + fmtId(props.goal[1].household)
+            if (props.goal.resource == "Money") {
+                goalPart = (goalGerund ? "working at " : "work at ") + fmtId(props.goal.offer.household)
+            } else if (props.goal.resource == "Wakefulness") {
+                goalPart = (goalGerund ? "sleeping at " : "sleep at ") + fmtId(props.goal.offer.household)
             } else {
-                goalPart = (goalGerund ? "getting " : "get ") + props.goal[0].toLowerCase() + " at " + fmtId(props.goal[1].household)
+                goalPart = (goalGerund ? "getting " : "get ") + props.goal.resource.toLowerCase() + " at " + fmtId(props.goal.offer.household)
             }
-            return statePart + " " + goalPart + ".";
+            return [statePart + " " + goalPart + ".", explainRoute];
         } else {
-            return statePart + ".";
+            return [statePart + ".", explainRoute];
         }
     } else {
         return "Gone missing?"
     }
-}
\ No newline at end of file
+}
+function satisfactionColor(satisfaction) {
+    return colors.unsatisfied.map((unsatisfied, i) =>
+        unsatisfied * (1 - satisfaction) + colors.satisfied[i] * satisfaction
+    );
+}
+
+// happiness heatmap: one square per area that residents live in
+export function Layers(props) {
+    const { state } = props;
+    const { satisfactionMap } = state.households;
+
+    if (state.uiMode != "happiness" || !satisfactionMap) {
+        return null;
+    }
+
+    const halfSize = satisfactionMap.areaSize / 2;
+    const areaMesh = {
+        vertices: new Float32Array([
+            -halfSize, -halfSize, 0.0,
+            halfSize, -halfSize, 0.0,
+            halfSize, halfSize, 0.0,
+            -halfSize, halfSize, 0.0,
+        ]),
+        indices: new Uint16Array([
+            0, 1, 2,
+            0, 2, 3
+        ])
+    };
+
+    const instances = satisfactionMap.areas.reduce((allInstances, { area, satisfaction }) =>
+        allInstances.concat([
+            (area.x + 0.5) * satisfactionMap.areaSize,
+            (area.y + 0.5) * satisfactionMap.areaSize,
+            0.0, 1.0, 0.0,
+            ...satisfactionColor(satisfaction)
+        ]), []);
+
+    return [
+        <RenderLayer
+            key="happiness"
+            renderOrder={renderOrder.happiness}
+            decal={true}
+            batches={[{
+                mesh: areaMesh,
+                instances: new Float32Array(instances)
+            }]} />
+    ];
+}
+
+export class HappinessMap extends React.Component {
+    componentWillMount() {
+        cbRustBrowser.get_satisfaction_map();
+        this.refreshInterval = setInterval(() => cbRustBrowser.get_satisfaction_map(), 1000);
+    }
+
+    componentWillUnmount() {
+        clearInterval(this.refreshInterval);
+    }
+
+    render() {
+        const { satisfactionMap } = this.props.state.households;
+
+        return <div key="happinessMap" className="window happiness">
+            <h1>Happiness</h1>
+            {satisfactionMap && satisfactionMap.summary.n_households > 0
+                ? <p>{satisfactionMap.summary.n_households} households, {fmtSatisfaction(satisfactionMap.summary.average)} satisfied on average</p>
+                : <p>No residents yet</p>}
+            <p>From red (unsatisfied) to green (satisfied), by area</p>
+        </div>;
+    }
+}
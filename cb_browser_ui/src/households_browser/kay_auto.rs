@@ -72,11 +72,46 @@ impl Into<HouseholdUIID> for BrowserHouseholdUIID {
     }
 }
 
+impl Into<PriceIndexRequesterID> for BrowserHouseholdUIID {
+    fn into(self) -> PriceIndexRequesterID {
+        PriceIndexRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<EmploymentStatisticsRequesterID> for BrowserHouseholdUIID {
+    fn into(self) -> EmploymentStatisticsRequesterID {
+        EmploymentStatisticsRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<TreasuryInfoRequesterID> for BrowserHouseholdUIID {
+    fn into(self) -> TreasuryInfoRequesterID {
+        TreasuryInfoRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<SatisfactionMapRequesterID> for BrowserHouseholdUIID {
+    fn into(self) -> SatisfactionMapRequesterID {
+        SatisfactionMapRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<LedgerRequesterID> for BrowserHouseholdUIID {
+    fn into(self) -> LedgerRequesterID {
+        LedgerRequesterID::from_raw(self.as_raw())
+    }
+}
+
 #[allow(unused_variables)]
 #[allow(unused_mut)]
 pub fn auto_setup(system: &mut ActorSystem) {
     
     HouseholdUIID::register_implementor::<BrowserHouseholdUI>(system);
+    PriceIndexRequesterID::register_implementor::<BrowserHouseholdUI>(system);
+    EmploymentStatisticsRequesterID::register_implementor::<BrowserHouseholdUI>(system);
+    TreasuryInfoRequesterID::register_implementor::<BrowserHouseholdUI>(system);
+    SatisfactionMapRequesterID::register_implementor::<BrowserHouseholdUI>(system);
+    LedgerRequesterID::register_implementor::<BrowserHouseholdUI>(system);
     system.add_spawner::<BrowserHouseholdUI, _, _>(
         |&MSG_BrowserHouseholdUI_spawn(id, ), world| {
             BrowserHouseholdUI::spawn(id, world)
@@ -1,4 +1,5 @@
 use kay::{World, ActorSystem, TypedID};
+use compact::CVec;
 use stdweb::serde::Serde;
 #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
 use stdweb::js_export;
@@ -13,6 +14,71 @@ pub fn get_household_info(household_id: Serde<::economy::households::HouseholdID
         .get_ui_info(BrowserHouseholdUIID::local_first(world).into(), world);
 }
 
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn get_price_index(resource: Serde<::economy::resources::Resource>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::economy::market::MarketID::global_first(world).get_price_index(
+        resource.0,
+        BrowserHouseholdUIID::local_first(world).into(),
+        world,
+    );
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn get_employment_statistics() {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::economy::households::jobs::EmploymentStatisticsID::global_first(world)
+        .get_employment_statistics(BrowserHouseholdUIID::local_first(world).into(), world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn get_treasury_info() {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::economy::treasury::CityTreasuryID::global_first(world)
+        .get_treasury_info(BrowserHouseholdUIID::local_first(world).into(), world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn get_satisfaction_map() {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::economy::households::satisfaction::SatisfactionMapID::global_first(world)
+        .get_satisfaction_map(BrowserHouseholdUIID::local_first(world).into(), world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn set_ledger_enabled(enabled: bool) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::economy::ledger::LedgerID::global_first(world).set_enabled(enabled, world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn get_money_flows() {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::economy::ledger::LedgerID::global_first(world)
+        .get_money_flows(BrowserHouseholdUIID::local_first(world).into(), world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn trace_supply_chain(
+    household_id: Serde<::economy::households::HouseholdID>,
+    resource: Serde<::economy::resources::Resource>,
+) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::economy::ledger::LedgerID::global_first(world).trace_supply_chain(
+        household_id.0,
+        resource.0,
+        BrowserHouseholdUIID::local_first(world).into(),
+        world,
+    );
+}
+
 #[derive(Compact, Clone)]
 pub struct BrowserHouseholdUI {
     id: BrowserHouseholdUIID,
@@ -72,6 +138,14 @@ impl HouseholdUI for BrowserHouseholdUI {
                                 used_offers: @{Serde(&core.used_offers)},
                                 member_used_offers: @{Serde(&core.member_used_offers)},
                                 provided_offers: @{Serde(&core.provided_offers)},
+                                member_jobs: @{Serde(&core.member_jobs)},
+                                rent: @{Serde(core.rent)},
+                                operating_costs: @{Serde(core.operating_costs)},
+                                insolvent_days: @{Serde(core.insolvent_days)},
+                                storage_capacities: @{Serde(&core.storage_capacities)},
+                                satisfaction: @{Serde(core.satisfaction)},
+                                member_satisfaction: @{Serde(&core.member_satisfaction)},
+                                member_demographics: @{Serde(&core.member_demographics)},
                             }
                         }}
                     }
@@ -81,6 +155,116 @@ impl HouseholdUI for BrowserHouseholdUI {
     }
 }
 
+use economy::market::{PriceIndex, PriceIndexRequester, PriceIndexRequesterID};
+
+impl PriceIndexRequester for BrowserHouseholdUI {
+    fn on_price_index(&mut self, price_index: PriceIndex, _world: &mut World) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                households: {
+                    priceIndices: {
+                        [@{Serde(price_index.resource)}]: {"$set": @{Serde(price_index)}}
+                    }
+                }
+            }));
+        }
+    }
+}
+
+use economy::households::jobs::{EmploymentSummary, EmploymentStatisticsRequester,
+EmploymentStatisticsRequesterID};
+
+impl EmploymentStatisticsRequester for BrowserHouseholdUI {
+    fn on_employment_statistics(&mut self, summary: EmploymentSummary, _world: &mut World) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                households: {
+                    employment: {"$set": @{Serde(summary)}}
+                }
+            }));
+        }
+    }
+}
+
+use economy::treasury::{TreasuryInfo, TreasuryInfoRequester, TreasuryInfoRequesterID};
+
+impl TreasuryInfoRequester for BrowserHouseholdUI {
+    fn on_treasury_info(&mut self, info: TreasuryInfo, _world: &mut World) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                households: {
+                    treasury: {"$set": @{Serde(info)}}
+                }
+            }));
+        }
+    }
+}
+
+use economy::households::satisfaction::{AreaSatisfaction, SatisfactionSummary,
+SatisfactionMapRequester, SatisfactionMapRequesterID, AREA_SIZE};
+
+impl SatisfactionMapRequester for BrowserHouseholdUI {
+    fn on_satisfaction_map(
+        &mut self,
+        areas: &CVec<AreaSatisfaction>,
+        summary: SatisfactionSummary,
+        _world: &mut World,
+    ) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                households: {
+                    satisfactionMap: {"$set": {
+                        areaSize: @{AREA_SIZE},
+                        areas: @{Serde(areas)},
+                        summary: @{Serde(summary)},
+                    }}
+                }
+            }));
+        }
+    }
+}
+
+use economy::households::HouseholdID;
+use economy::resources::Resource;
+use economy::ledger::{MoneyFlow, SupplyLink, LedgerRequester, LedgerRequesterID};
+
+impl LedgerRequester for BrowserHouseholdUI {
+    fn on_money_flows(&mut self, flows: &CVec<MoneyFlow>, n_entries: u32, _world: &mut World) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                households: {
+                    ledger: {
+                        moneyFlows: {"$set": @{Serde(flows)}},
+                        nEntries: {"$set": @{n_entries}}
+                    }
+                }
+            }));
+        }
+    }
+
+    fn on_supply_chain(
+        &mut self,
+        household: HouseholdID,
+        resource: Resource,
+        links: &CVec<SupplyLink>,
+        _world: &mut World,
+    ) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                households: {
+                    ledger: {
+                        supplyChain: {"$set": {
+                            household: @{Serde(household)},
+                            resource: @{Serde(resource)},
+                            links: @{Serde(links)},
+                        }}
+                    }
+                }
+            }));
+        }
+    }
+}
+
 mod kay_auto;
 pub use self::kay_auto::*;
 
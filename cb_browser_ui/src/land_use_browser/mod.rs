@@ -13,6 +13,7 @@ use ::land_use::buildings::architecture::language::ArchitectureRule;
 use ::land_use::buildings::architecture::materials_and_props::{ALL_MATERIALS, ALL_PROP_TYPES};
 use ::land_use::zone_planning::Lot;
 use ::economy::households::HouseholdID;
+use ::transport::parking::ParkingSpaces;
 
 #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
 pub fn get_building_info(building_id: Serde<BuildingID>) {
@@ -167,6 +168,8 @@ impl LandUseUI for BrowserLandUseUI {
         _id: BuildingID,
         style: BuildingStyle,
         households: &CVec<HouseholdID>,
+        parking: ParkingSpaces,
+        parked_on_street: u32,
         _world: &mut World,
     ) {
         js! {
@@ -175,6 +178,8 @@ impl LandUseUI for BrowserLandUseUI {
                     inspectedBuildingState: {"$set": {
                         households: @{Serde(households)},
                         style: @{Serde(style)},
+                        parking: @{Serde(parking)},
+                        parkedOnStreet: @{parked_on_street},
                     }}
                 }
             }));
@@ -39,7 +39,7 @@ function moveControlPoint(projectId, gestureId, pointIdx, newPosition, doneMovin
     }
 }
 
-function getGestureAsOf(state, projectId, gestureId) {
+export function getGestureAsOf(state, projectId, gestureId) {
     if (projectId && state.planning.projects[projectId]) {
         let project = state.planning.projects[projectId];
         for (let i = project.undoable_history.length - 1; i >= 0; i--) {
@@ -20,6 +20,13 @@ type Project = {
 
 type Mesh = {};
 
+type NetworkIssueKind = "DeadEnd" | { DisconnectedComponent: { n_lanes: number } } | "UnattachedBuilding" | "UnreachableIntersection";
+
+type NetworkIssue = {
+    kind: NetworkIssueKind,
+    position: [number, number]
+}
+
 export type PlanningSharedState = {
     planningMode: null | "roads" | "zoning",
     rendering: {
@@ -47,7 +54,11 @@ export type PlanningSharedState = {
             ongoing: Project
         }
     },
-    currentProject: string | null
+    currentProject: string | null,
+    networkWarnings: null | {
+        projects: string[],
+        issues: NetworkIssue[]
+    }
 }
 
 export const initialState: PlanningSharedState = {
@@ -68,7 +79,8 @@ export const initialState: PlanningSharedState = {
         gestures: {}
     },
     projects: {
-    }
+    },
+    networkWarnings: null
 };
 
 export const settingsSpec = {
@@ -132,4 +144,38 @@ export function PlanningUI(props: { state: SharedState, setState: SetSharedState
             intent={intent}
             setIntent={setIntent} />
     </>
-}
\ No newline at end of file
+}
+function describeNetworkIssue(kind: NetworkIssueKind) {
+    if (kind === "DeadEnd") {
+        return "Dead-end lane";
+    } else if (kind === "UnattachedBuilding") {
+        return "Building without lane attachment";
+    } else if (kind === "UnreachableIntersection") {
+        return "Unreachable intersection";
+    } else {
+        return `Disconnected part of the network (${kind.DisconnectedComponent.n_lanes} lanes)`;
+    }
+}
+
+export function PlanWarningsWindow(props: { state: SharedState, setState: SetSharedState }) {
+    const warnings = props.state.planning.networkWarnings;
+
+    if (!warnings || warnings.issues.length === 0) {
+        return null;
+    }
+
+    const dismiss = () => props.setState(oldState => update(oldState, {
+        planning: { networkWarnings: { $set: null } }
+    }));
+
+    return <div key="planWarnings" className="window plan-warnings">
+        <h1>Plan Warnings</h1>
+        <a className="close-window" onClick={dismiss}>×</a>
+        <p>{warnings.issues.length} network issues after implementing {warnings.projects.length} project(s)</p>
+        <div className="scrollableLog">{warnings.issues.map((issue, i) =>
+            <div key={i}>
+                {describeNetworkIssue(issue.kind)} at ({issue.position[0].toFixed(0)}, {issue.position[1].toFixed(0)})
+            </div>
+        )}</div>
+    </div>;
+}
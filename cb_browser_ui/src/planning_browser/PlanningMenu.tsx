@@ -10,6 +10,77 @@ import { SharedState, ToToolPortal } from '../citybound';
 import { Intent } from '../wasm32-unknown-unknown/release/cb_browser_ui';
 import { useInputBinding } from '../browser_utils/Utils';
 
+const ROAD_LANE_PRESETS = {
+    "1+1": { description: "Two-way, 1 lane each", nLanesForward: 1, nLanesBackward: 1 },
+    "2+2": { description: "Two-way, 2 lanes each", nLanesForward: 2, nLanesBackward: 2 },
+    "3+3": { description: "Two-way, 3 lanes each", nLanesForward: 3, nLanesBackward: 3 },
+    "2+1": { description: "Two-way, 2 lanes forward, 1 backward", nLanesForward: 2, nLanesBackward: 1 },
+    "1+0": { description: "One-way, 1 lane", nLanesForward: 1, nLanesBackward: 0 },
+    "2+0": { description: "One-way, 2 lanes", nLanesForward: 2, nLanesBackward: 0 },
+    "3+0": { description: "One-way, 3 lanes", nLanesForward: 3, nLanesBackward: 0 },
+};
+
+const ROAD_MARKING_PRESETS = {
+    automatic: { description: "Automatic lane use" },
+    innerTurnLane: { description: "Dedicated inner turn lane" },
+    outerTurnPocket: { description: "Outer turn pocket" },
+};
+
+const INNER_TURN_ONLY = { straight: false, u_turn: true, inner_turn: true, outer_turn: false };
+const OUTER_TURN_ONLY = { straight: false, u_turn: false, inner_turn: false, outer_turn: true };
+const TURN_POCKET_LENGTH = 30.0;
+
+function withRoadMarkingPreset(intent, preset) {
+    const cleared = [true, false].reduce(
+        (current, forward) => cbRustBrowser.with_turn_pocket(current, forward, null),
+        cbRustBrowser.with_lane_use(cbRustBrowser.with_lane_use(intent, 1, null), -1, null)
+    );
+
+    if (preset == "innerTurnLane") {
+        return cbRustBrowser.with_lane_use(cbRustBrowser.with_lane_use(cleared, 1, INNER_TURN_ONLY), -1, INNER_TURN_ONLY);
+    } else if (preset == "outerTurnPocket") {
+        const turnPocket = { length: TURN_POCKET_LENGTH, lane_use: OUTER_TURN_ONLY };
+        return cbRustBrowser.with_turn_pocket(cbRustBrowser.with_turn_pocket(cleared, true, turnPocket), false, turnPocket);
+    } else {
+        return cleared;
+    }
+}
+
+function roadMarkingKey({ lane_uses, turn_pocket_forward, turn_pocket_backward }) {
+    if (turn_pocket_forward || turn_pocket_backward) {
+        return "outerTurnPocket";
+    } else if (lane_uses.length) {
+        return "innerTurnLane";
+    } else {
+        return "automatic";
+    }
+}
+
+// levels: 0 is at grade, positive levels are bridges, negative ones tunnels
+const ROAD_ELEVATION_PRESETS = {
+    atGrade: { description: "At grade", elevation: { level: 0, start: { Intersection: 0 }, end: { Intersection: 0 } } },
+    bridge: { description: "Bridge", elevation: { level: 1, start: { Intersection: 0 }, end: { Intersection: 0 } } },
+    tunnel: { description: "Tunnel", elevation: { level: -1, start: { Intersection: 0 }, end: { Intersection: 0 } } },
+    highway: { description: "Elevated highway", elevation: { level: 1, start: { Intersection: 1 }, end: { Intersection: 1 } } },
+    entryRamp: { description: "Entry ramp", elevation: { level: 0, start: { Intersection: 0 }, end: { Merge: 1 } } },
+    exitRamp: { description: "Exit ramp", elevation: { level: 0, start: { Merge: 1 }, end: { Intersection: 0 } } },
+};
+
+function roadElevationKey(elevation) {
+    return Object.keys(ROAD_ELEVATION_PRESETS).find(preset =>
+        JSON.stringify(ROAD_ELEVATION_PRESETS[preset].elevation) == JSON.stringify(elevation)
+    );
+}
+
+const ROAD_BIKE_LANE_PRESETS = {
+    noBikeLanes: { description: "No bike lanes" },
+    bikeLanes: { description: "Bike lanes" },
+};
+
+function laneConfigKey({ n_lanes_forward, n_lanes_backward }) {
+    return n_lanes_forward + "+" + n_lanes_backward;
+}
+
 export function PlanningMenu(
     { state, currentProject, setCurrentProject, planningMode, setPlanningMode, intent, setIntent }:
         { state: SharedState, currentProject: string | null, setCurrentProject: (project: string) => void, intent: Intent | null, setIntent: (intent: Intent | null) => void, planningMode: 'roads' | 'zoning' | null, setPlanningMode: (mode: 'roads' | 'zoning' | null) => void }) {
@@ -105,6 +176,36 @@ export function PlanningMenu(
                         }
                     }
                 } />}
+        {currentProject && planningMode == "roads" &&
+            <Toolbar id="road-lanes-toolbar"
+                options={ROAD_LANE_PRESETS}
+                value={intent && intent.Road && laneConfigKey(intent.Road.lane_config)}
+                onChange={preset => {
+                    const { nLanesForward, nLanesBackward } = ROAD_LANE_PRESETS[preset];
+                    // keeps elevation, bike lanes and lane markings of the current intent
+                    setIntent(intent && intent.Road
+                        ? cbRustBrowser.with_lane_config({ Road: intent.Road }, nLanesForward, nLanesBackward)
+                        : { Road: cbRustBrowser.new_road_intent(nLanesForward, nLanesBackward) });
+                }}
+            />}
+        {currentProject && planningMode == "roads" && intent && intent.Road &&
+            <Toolbar id="road-elevation-toolbar"
+                options={ROAD_ELEVATION_PRESETS}
+                value={roadElevationKey(intent.Road.elevation)}
+                onChange={preset => setIntent(cbRustBrowser.with_elevation({ Road: intent.Road }, ROAD_ELEVATION_PRESETS[preset].elevation))}
+            />}
+        {currentProject && planningMode == "roads" && intent && intent.Road &&
+            <Toolbar id="road-bike-lanes-toolbar"
+                options={ROAD_BIKE_LANE_PRESETS}
+                value={intent.Road.lane_config.bike_lanes ? "bikeLanes" : "noBikeLanes"}
+                onChange={preset => setIntent(cbRustBrowser.with_bike_lanes({ Road: intent.Road }, preset == "bikeLanes"))}
+            />}
+        {currentProject && planningMode == "roads" && intent && intent.Road &&
+            <Toolbar id="road-markings-toolbar"
+                options={ROAD_MARKING_PRESETS}
+                value={roadMarkingKey(intent.Road.lane_markings)}
+                onChange={preset => setIntent(withRoadMarkingPreset({ Road: intent.Road }, preset))}
+            />}
         {currentProject && planningMode == "zoning" &&
             <Toolbar id="zoning-toolbar"
                 options={{
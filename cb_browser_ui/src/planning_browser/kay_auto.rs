@@ -78,12 +78,19 @@ impl Into<PlanningUIID<CBPlanningLogic>> for BrowserPlanningUIID {
     }
 }
 
+impl Into<NetworkValidationListenerID> for BrowserPlanningUIID {
+    fn into(self) -> NetworkValidationListenerID {
+        NetworkValidationListenerID::from_raw(self.as_raw())
+    }
+}
+
 #[allow(unused_variables)]
 #[allow(unused_mut)]
 pub fn auto_setup(system: &mut ActorSystem) {
     
     FrameListenerID::register_implementor::<BrowserPlanningUI>(system);
     PlanningUIID::<CBPlanningLogic>::register_implementor::<BrowserPlanningUI>(system);
+    NetworkValidationListenerID::register_implementor::<BrowserPlanningUI>(system);
     system.add_spawner::<BrowserPlanningUI, _, _>(
         |&MSG_BrowserPlanningUI_spawn(id, ), world| {
             BrowserPlanningUI::spawn(id, world)
@@ -1,6 +1,6 @@
 use stdweb::serde::Serde;
 use kay::{World, Actor, External, ActorSystem, TypedID};
-use compact::{CHashMap};
+use compact::{CHashMap, CVec};
 use std::collections::HashMap;
 use descartes::{LinePath, P2, EditArcLinePath, ResolutionStrategy, Closedness};
 use michelangelo::{MeshGrouper};
@@ -12,6 +12,7 @@ use cb_planning::plan_manager::ProjectID;
 use cb_planning::plan_manager::ui::{PlanningUI, PlanningUIID};
 use planning::{CBPlanningLogic, CBPlanManagerID, CBGestureIntent, CBPrototypeKind};
 use land_use::zone_planning::{LandUse, LAND_USES, ZoneIntent, ZoneConfig};
+use transport::validation::{NetworkValidationListener, NetworkValidationListenerID, NetworkIssue};
 use browser_utils::{updated_groups_to_js, to_js_mesh, FrameListener, FrameListenerID};
 
 #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
@@ -105,7 +106,8 @@ pub fn start_new_project(project_id: Serde<ProjectID>) {
     CBPlanManagerID::global_first(world).start_new_project(project_id.0, world);
 }
 
-use transport::transport_planning::{RoadIntent, RoadLaneConfig};
+use transport::transport_planning::{RoadIntent, RoadLaneConfig, ConnectionRole, TurnPocket};
+use transport::transport_planning::RoadElevation;
 
 #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
 pub fn new_road_intent(n_lanes_forward: usize, n_lanes_backward: usize) -> Serde<RoadIntent> {
@@ -114,10 +116,93 @@ pub fn new_road_intent(n_lanes_forward: usize, n_lanes_backward: usize) -> Serde
         RoadLaneConfig {
             n_lanes_forward: n_lanes_forward as u8,
             n_lanes_backward: n_lanes_backward as u8,
+            bike_lanes: false,
         },
     ))
 }
 
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn with_lane_config(
+    intent: Serde<CBGestureIntent>,
+    n_lanes_forward: usize,
+    n_lanes_backward: usize,
+) -> Serde<CBGestureIntent> {
+    Serde(match intent.0 {
+        CBGestureIntent::Road(road_intent) => {
+            match RoadLaneConfig::new(n_lanes_forward as u8, n_lanes_backward as u8) {
+                Some(lane_config) => {
+                    let bike_lanes = road_intent.lane_config.bike_lanes;
+                    CBGestureIntent::Road(
+                        road_intent.with_lane_config(lane_config.with_bike_lanes(bike_lanes)),
+                    )
+                }
+                None => CBGestureIntent::Road(road_intent),
+            }
+        }
+        other => other,
+    })
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn with_bike_lanes(intent: Serde<CBGestureIntent>, bike_lanes: bool) -> Serde<CBGestureIntent> {
+    Serde(match intent.0 {
+        CBGestureIntent::Road(road_intent) => {
+            let lane_config = road_intent.lane_config.with_bike_lanes(bike_lanes);
+            CBGestureIntent::Road(road_intent.with_lane_config(lane_config))
+        }
+        other => other,
+    })
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn with_lane_use(
+    intent: Serde<CBGestureIntent>,
+    lane: i32,
+    lane_use: Serde<Option<ConnectionRole>>,
+) -> Serde<CBGestureIntent> {
+    Serde(match intent.0 {
+        CBGestureIntent::Road(road_intent) => {
+            let mut lane_markings = road_intent.lane_markings.clone();
+            lane_markings.set_lane_use(lane as i8, lane_use.0);
+            CBGestureIntent::Road(road_intent.with_lane_markings(lane_markings))
+        }
+        other => other,
+    })
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn with_turn_pocket(
+    intent: Serde<CBGestureIntent>,
+    forward: bool,
+    turn_pocket: Serde<Option<TurnPocket>>,
+) -> Serde<CBGestureIntent> {
+    Serde(match intent.0 {
+        CBGestureIntent::Road(road_intent) => {
+            let mut lane_markings = road_intent.lane_markings.clone();
+            if forward {
+                lane_markings.turn_pocket_forward = turn_pocket.0;
+            } else {
+                lane_markings.turn_pocket_backward = turn_pocket.0;
+            }
+            CBGestureIntent::Road(road_intent.with_lane_markings(lane_markings))
+        }
+        other => other,
+    })
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn with_elevation(
+    intent: Serde<CBGestureIntent>,
+    elevation: Serde<RoadElevation>,
+) -> Serde<CBGestureIntent> {
+    Serde(match intent.0 {
+        CBGestureIntent::Road(road_intent) => {
+            CBGestureIntent::Road(road_intent.with_elevation(elevation.0))
+        }
+        other => other,
+    })
+}
+
 #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
 pub fn new_zone_intent(new_land_use: Serde<LandUse>) -> Serde<ZoneIntent> {
     Serde(ZoneIntent {
@@ -441,7 +526,7 @@ impl PlanningUI<CBPlanningLogic> for BrowserPlanningUI {
         for new_prototype in &result_update.new_prototypes {
             let corresponding_action = new_actions.corresponding_action(new_prototype.id);
             match new_prototype.kind {
-                CBPrototypeKind::Road(RoadPrototype::Lane(LanePrototype(ref lane_path, _))) => {
+                CBPrototypeKind::Road(RoadPrototype::Lane(LanePrototype(ref lane_path, ..))) => {
                     match corresponding_action {
                         Some(ref action) if action.is_construct() => {
                             lanes_to_construct_add.push((new_prototype.id, lane_mesh(lane_path)));
@@ -467,7 +552,7 @@ impl PlanningUI<CBPlanningLogic> for BrowserPlanningUI {
                 })) => match corresponding_action {
                     Some(ref action) if action.is_construct() => {
                         let mut intersection_mesh = Mesh::empty();
-                        for &LanePrototype(ref lane_path, _) in
+                        for &LanePrototype(ref lane_path, ..) in
                             connecting_lanes.values().flat_map(|lanes| lanes)
                         {
                             intersection_mesh += lane_mesh(lane_path);
@@ -641,6 +726,26 @@ impl PlanningUI<CBPlanningLogic> for BrowserPlanningUI {
     }
 }
 
+impl NetworkValidationListener for BrowserPlanningUI {
+    fn on_network_validated(
+        &mut self,
+        projects: &CVec<ProjectID>,
+        issues: &CVec<NetworkIssue>,
+        _world: &mut World,
+    ) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                planning: {
+                    networkWarnings: {"$set": {
+                        projects: @{Serde(projects)},
+                        issues: @{Serde(issues)},
+                    }}
+                }
+            }));
+        }
+    }
+}
+
 mod kay_auto;
 pub use self::kay_auto::*;
 
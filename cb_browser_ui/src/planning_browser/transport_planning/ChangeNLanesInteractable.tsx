@@ -6,9 +6,14 @@ import renderOrder from '../../renderOrder';
 import colors from '../../colors';
 import { useState } from 'react';
 import { LANE_DISTANCE } from './RoadPlanningLayers';
+import { getGestureAsOf } from '../ControlPoints';
 
-function setNLanes(projectId, gestureId, nLanesForward, nLanesBackward, doneChanging) {
-    window.cbRustBrowser.set_n_lanes(projectId, gestureId, nLanesForward, nLanesBackward, doneChanging);
+function setNLanes(state, projectId, gestureId, nLanesForward, nLanesBackward, doneChanging) {
+    const gesture = getGestureAsOf(state, projectId, gestureId);
+    if (gesture) {
+        const newIntent = window.cbRustBrowser.with_lane_config(gesture.intent, nLanesForward, nLanesBackward);
+        window.cbRustBrowser.set_intent(projectId, gestureId, newIntent, doneChanging);
+    }
 }
 
 export function ChangeNLanesInteractable({ gestureId, centerLine, nLanesBackward, nLanesForward, state, currentProject }: {
@@ -52,14 +57,16 @@ export function ChangeNLanesInteractable({ gestureId, centerLine, nLanesBackward
                     let newNLanesForward = nLanesForward;
                     let newNLanesBackward = nLanesBackward;
 
+                    // one side can be dragged down to zero lanes to get a one-way road,
+                    // but at least one lane has to remain
                     if (orthogonalDistance > 0.0) {
-                        newNLanesForward = Math.max(0.0, Math.round(orthogonalDistance / 3.0));
+                        newNLanesForward = Math.max(nLanesBackward > 0 ? 0.0 : 1.0, Math.round(orthogonalDistance / 3.0));
                     }
                     else {
-                        newNLanesBackward = Math.max(0.0, Math.round(-orthogonalDistance / 3.0));
+                        newNLanesBackward = Math.max(nLanesForward > 0 ? 0.0 : 1.0, Math.round(-orthogonalDistance / 3.0));
                     }
 
-                    setNLanes(currentProject, gestureId, newNLanesForward, newNLanesBackward, e.drag.end ? true : false);
+                    setNLanes(state, currentProject, gestureId, newNLanesForward, newNLanesBackward, e.drag.end ? true : false);
                 }
             }
             if (e.hover) {
@@ -9,6 +9,9 @@ export default {
     asphalt: i++,
     asphaltMarker: i++,
     asphaltMarkerGap: i++,
+    incidents: i++,
+    routeExplanation: i++,
+    happiness: i++,
     addedGesturesAsphalt: i++,
     addedGesturesMarker: i++,
     addedGesturesMarkerGap: i++,
@@ -1,8 +1,11 @@
 import colors from '../colors';
 import renderOrder from '../renderOrder';
 import carMesh from './carMesh';
-import { RenderLayer } from '../browser_utils/Utils';
+import { RenderLayer, Interactive3DShape, fmtId } from '../browser_utils/Utils';
 import React from 'react';
+import update from 'immutability-helper';
+import { ToToolPortal } from '../citybound';
+import { Toolbar } from '../toolbar';
 
 export const initialState = {
     rendering: {
@@ -12,10 +15,184 @@ export const initialState = {
         laneAsphaltGroups: new Map(),
         laneMarkerGroups: new Map(),
         laneMarkerGapGroups: new Map(),
+        incidentGroups: new Map(),
+        routeExplanationMesh: null,
         carInstances: []
-    }
+    },
+    incidentTool: "StalledCar",
+    detectors: {},
+    detectorTool: "Place",
+    routeExplanation: null,
+    tripStatistics: [],
+};
+
+const TICKS_PER_SIM_MINUTE = 180;
+
+const INCIDENT_TOOLS = {
+    StalledCar: { description: "Stalled car", kind: "StalledCar", length: 0.0, durationMinutes: 120 },
+    LaneClosure: { description: "Lane closure", kind: "LaneClosure", length: 50.0, durationMinutes: 240 },
+    ReducedSpeed: { description: "Reduced speed zone", kind: { ReducedSpeed: 5.0 }, length: 100.0, durationMinutes: 240 },
+    Clear: { description: "Clear incidents" },
+};
+
+export function IncidentTools(props) {
+    const { state, setState } = props;
+    const tool = state.transport.incidentTool;
+
+    return <>
+        <ToToolPortal>
+            <Toolbar id="incidents-toolbar"
+                options={INCIDENT_TOOLS}
+                value={tool}
+                onChange={newTool => setState(oldState => update(oldState, {
+                    transport: { incidentTool: { $set: newTool } }
+                }))} />
+        </ToToolPortal>
+        <Interactive3DShape
+            id="incidentCanvas"
+            key="incidentCanvas"
+            shape={{ type: "everywhere" }}
+            zIndex={1}
+            cursorHover="crosshair"
+            cursorActive="pointer"
+            onEvent={e => {
+                if (e.drag && e.drag.end) {
+                    const position = [e.drag.end[0], e.drag.end[1]];
+                    if (tool == "Clear") {
+                        cbRustBrowser.clear_incidents(position);
+                    } else {
+                        const { kind, length, durationMinutes } = INCIDENT_TOOLS[tool];
+                        const now = state.time.ticks;
+                        cbRustBrowser.add_incident(position, kind, length, now, now + durationMinutes * TICKS_PER_SIM_MINUTE);
+                    }
+                }
+            }} />
+    </>;
+}
+
+const DETECTOR_TOOLS = {
+    Place: { description: "Place loop detector" },
+    Clear: { description: "Remove loop detectors" },
 };
 
+export function DetectorTools(props) {
+    const { state, setState } = props;
+    const tool = state.transport.detectorTool;
+
+    return <>
+        <ToToolPortal>
+            <Toolbar id="detectors-toolbar"
+                options={DETECTOR_TOOLS}
+                value={tool}
+                onChange={newTool => setState(oldState => update(oldState, {
+                    transport: { detectorTool: { $set: newTool } }
+                }))} />
+        </ToToolPortal>
+        <Interactive3DShape
+            id="detectorCanvas"
+            key="detectorCanvas"
+            shape={{ type: "everywhere" }}
+            zIndex={1}
+            cursorHover="crosshair"
+            cursorActive="pointer"
+            onEvent={e => {
+                if (e.drag && e.drag.end) {
+                    const position = [e.drag.end[0], e.drag.end[1]];
+                    if (tool == "Clear") {
+                        cbRustBrowser.clear_detectors(position);
+                    } else {
+                        cbRustBrowser.add_detector(position);
+                    }
+                }
+            }} />
+    </>;
+}
+
+const DIAGRAM_WIDTH = 300;
+const DIAGRAM_HEIGHT = 200;
+
+// flow over density for every measurement interval of every detector
+export function FundamentalDiagram(props) {
+    const detectors = Object.values(props.state.transport.detectors)
+        .reduce((all, laneDetectors) => all.concat(laneDetectors), []);
+    const measurements = detectors
+        .reduce((all, detector) => all.concat(detector.measurements), []);
+
+    const maxDensity = Math.max(10, ...measurements.map(m => m.density));
+    const maxFlow = Math.max(100, ...measurements.map(m => m.flow));
+
+    return <div key="fundamentalDiagram" className="window detectors">
+        <h1>Loop Detectors</h1>
+        <p>{detectors.length} detectors, {measurements.length} intervals measured</p>
+        <svg width={DIAGRAM_WIDTH} height={DIAGRAM_HEIGHT}>
+            <line x1={0} y1={DIAGRAM_HEIGHT} x2={DIAGRAM_WIDTH} y2={DIAGRAM_HEIGHT} stroke="black" />
+            <line x1={0} y1={0} x2={0} y2={DIAGRAM_HEIGHT} stroke="black" />
+            {detectors.map((detector, d) => detector.measurements.map((m, i) =>
+                <circle key={d + "_" + i}
+                    cx={m.density / maxDensity * DIAGRAM_WIDTH}
+                    cy={DIAGRAM_HEIGHT - m.flow / maxFlow * DIAGRAM_HEIGHT}
+                    r={2}
+                    fill={`hsl(${(d * 67) % 360}, 70%, 45%)`}>
+                    <title>{`${m.flow.toFixed(0)} veh/h, ${m.density.toFixed(1)} veh/km, ${m.mean_speed ? m.mean_speed.toFixed(1) + " m/s" : "no vehicles"}, ${(m.occupancy * 100).toFixed(0)}% occupied`}</title>
+                </circle>
+            ))}
+        </svg>
+        <p>Density (0 - {maxDensity.toFixed(0)} veh/km) against flow (0 - {maxFlow.toFixed(0)} veh/h)</p>
+    </div>;
+}
+
+// the hop sequence of one trip, as the routing tables of each lane on the way see it
+export function RouteExplanationWindow(props) {
+    const { state, setState } = props;
+    const explanation = state.transport.routeExplanation;
+
+    if (!explanation) {
+        return null;
+    }
+
+    const closeWindow = () => setState(oldState => update(oldState, {
+        transport: {
+            routeExplanation: { $set: null },
+            rendering: { routeExplanationMesh: { $set: null } }
+        }
+    }));
+
+    return <div key="routeExplanation" className="window route-explanation">
+        <h1>Route Explanation</h1>
+        <a className="close-window" onClick={closeWindow}>×</a>
+        <p>{explanation.outcome} after {explanation.steps.length} lanes</p>
+        <div className="scrollableLog">{explanation.steps.map((step, i) =>
+            <div key={i}>
+                {i}. {fmtId(step.lane)}
+                {step.landmark && <span> (landmark {fmtId(step.landmark)})</span>}
+                {step.distance !== null
+                    ? <span>: {step.distance.toFixed(1)} to go{step.via_landmark && " via the destination's landmark"}</span>
+                    : <span>: no route known</span>}
+                {step.next && <span>, next {step.via_switch ? "switch to" : ""} {fmtId(step.next)} for {step.step_cost.toFixed(1)}</span>}
+            </div>
+        )}</div>
+    </div>;
+}
+
+// share of all started trips per vehicle type
+export function ModeShare(props) {
+    const modes = props.state.transport.tripStatistics;
+    const totalStarted = modes.reduce((sum, mode) => sum + mode.started, 0);
+
+    return <div>
+        {modes.length == 0 && <p>No trips yet</p>}
+        {modes.map(mode =>
+            <div key={mode.vehicle_type}>
+                {mode.vehicle_type}: {(mode.started / totalStarted * 100).toFixed(1)}% of {totalStarted} trips, {mode.succeeded} arrived, {mode.failed} failed
+            </div>
+        )}
+    </div>;
+}
+
+const routeExplanationInstance = new Float32Array([0.0, 0.0, 0.0, 1.0, 0.0, ...colors.routeExplanation]);
+
+const incidentInstance = new Float32Array([0.0, 0.0, 0.0, 1.0, 0.0, ...colors.incident]);
+
 const asphaltInstance = new Float32Array([0.0, 0.0, 0.0, 1.0, 0.0, ...colors.asphalt]);
 const roadMarkerInstance = new Float32Array([0.0, 0.0, 0.0, 1.0, 0.0, ...colors.roadMarker]);
 
@@ -44,6 +221,20 @@ export function Layers(props) {
                 mesh: groupMesh,
                 instances: asphaltInstance
             }))} />,
+        <RenderLayer
+            renderOrder={renderOrder.incidents}
+            decal={true}
+            batches={[...state.transport.rendering.incidentGroups.values()].map(groupMesh => ({
+                mesh: groupMesh,
+                instances: incidentInstance
+            }))} />,
+        state.transport.rendering.routeExplanationMesh && <RenderLayer
+            renderOrder={renderOrder.routeExplanation}
+            decal={true}
+            batches={[{
+                mesh: state.transport.rendering.routeExplanationMesh,
+                instances: routeExplanationInstance
+            }]} />,
         <RenderLayer
             renderOrder={renderOrder.cars}
             decal={false}
@@ -78,12 +78,26 @@ impl Into<TransportUIID> for BrowserTransportUIID {
     }
 }
 
+impl Into<RouteExplanationRequesterID> for BrowserTransportUIID {
+    fn into(self) -> RouteExplanationRequesterID {
+        RouteExplanationRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<TripStatisticsRequesterID> for BrowserTransportUIID {
+    fn into(self) -> TripStatisticsRequesterID {
+        TripStatisticsRequesterID::from_raw(self.as_raw())
+    }
+}
+
 #[allow(unused_variables)]
 #[allow(unused_mut)]
 pub fn auto_setup(system: &mut ActorSystem) {
     
     FrameListenerID::register_implementor::<BrowserTransportUI>(system);
     TransportUIID::register_implementor::<BrowserTransportUI>(system);
+    RouteExplanationRequesterID::register_implementor::<BrowserTransportUI>(system);
+    TripStatisticsRequesterID::register_implementor::<BrowserTransportUI>(system);
     system.add_spawner::<BrowserTransportUI, _, _>(
         |&MSG_BrowserTransportUI_spawn(id, ), world| {
             BrowserTransportUI::spawn(id, world)
@@ -1,9 +1,14 @@
 use kay::{World, ActorSystem, Actor, RawID, External, TypedID};
 use compact::CVec;
-use std::collections::HashMap;
-use descartes::LinePath;
+use std::collections::{HashMap, HashSet};
+use descartes::{LinePath, P2};
+use stdweb::serde::Serde;
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+use stdweb::js_export;
+use SYSTEM;
 use michelangelo::{MeshGrouper, Instance};
-use browser_utils::{FrameListener, FrameListenerID, flatten_instances, updated_groups_to_js};
+use browser_utils::{FrameListener, FrameListenerID, flatten_instances, updated_groups_to_js,
+to_js_mesh};
 
 #[derive(Compact, Clone)]
 pub struct BrowserTransportUI {
@@ -28,11 +33,14 @@ impl ::std::ops::DerefMut for BrowserTransportUI {
 pub struct BrowserTransportUINonPersistedState {
     car_instance_buffers: HashMap<RawID, Vec<::michelangelo::Instance>>,
     car_colors: Vec<[f32; 3]>,
+    vehicle_type_colors: HashMap<String, [f32; 3]>,
 
     // transport geometry
     asphalt_grouper: MeshGrouper<RawID>,
     lane_marker_grouper: MeshGrouper<RawID>,
     lane_marker_gaps_grouper: MeshGrouper<RawID>,
+    incident_grouper: MeshGrouper<RawID>,
+    lanes_with_incidents: HashSet<RawID>,
 }
 
 impl BrowserTransportUI {
@@ -48,9 +56,12 @@ impl BrowserTransportUI {
             state: External::new(BrowserTransportUINonPersistedState {
                 car_instance_buffers: HashMap::new(),
                 car_colors: vec![[0.0, 0.0, 0.0]],
+                vehicle_type_colors: HashMap::new(),
                 asphalt_grouper: MeshGrouper::new(2000),
                 lane_marker_grouper: MeshGrouper::new(2000),
                 lane_marker_gaps_grouper: MeshGrouper::new(2000),
+                incident_grouper: MeshGrouper::new(2000),
+                lanes_with_incidents: HashSet::new(),
             }),
         }
     }
@@ -93,10 +104,143 @@ impl FrameListener for BrowserTransportUI {
                 [color[0] as f32, color[1] as f32, color[2] as f32]
             })
             .collect();
+
+        let vehicle_type_color_vals: HashMap<String, Vec<f64>> = js! {
+            return require("../../../src/colors").default.vehicleTypes;
+        }
+        .try_into()
+        .unwrap();
+
+        self.vehicle_type_colors = vehicle_type_color_vals
+            .into_iter()
+            .map(|(vehicle_type, color)| {
+                (
+                    vehicle_type,
+                    [color[0] as f32, color[1] as f32, color[2] as f32],
+                )
+            })
+            .collect();
     }
 }
 
 use transport::ui::{TransportUI, TransportUIID, CarRenderInfo};
+use transport::microtraffic::VehicleType;
+
+// see carMesh.js
+const CAR_MESH_LENGTH: f32 = 4.5;
+use transport::incidents::{IncidentKind, IncidentRenderInfo};
+use transport::detectors::DetectorInfo;
+use transport::pathfinding::RoughLocationID;
+use transport::pathfinding::trip::{TripID, TripStatisticsID, TripStatisticsRequester,
+TripStatisticsRequesterID, ModeStatistics};
+use transport::pathfinding::route_explanation::{RouteExplainerID, RouteExplanation,
+RouteExplanationRequester, RouteExplanationRequesterID};
+use cb_time::units::Instant;
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn add_incident(
+    position: Serde<P2>,
+    kind: Serde<IncidentKind>,
+    length: f32,
+    from_ticks: u32,
+    until_ticks: u32,
+) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::transport::lane::LaneID::global_broadcast(world).add_incident_near(
+        position.0,
+        kind.0,
+        length,
+        Instant::new(from_ticks as usize),
+        Instant::new(until_ticks as usize),
+        world,
+    );
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn clear_incidents(position: Serde<P2>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::transport::lane::LaneID::global_broadcast(world).clear_incidents_near(position.0, world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn add_detector(position: Serde<P2>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::transport::lane::LaneID::global_broadcast(world).add_detector_near(position.0, world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn clear_detectors(position: Serde<P2>) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    ::transport::lane::LaneID::global_broadcast(world).clear_detectors_near(position.0, world);
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn explain_trip_route(trip: Serde<TripID>, now_ticks: u32) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    trip.0.explain_route(
+        BrowserTransportUIID::local_first(world).into(),
+        Instant::new(now_ticks as usize),
+        world,
+    );
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn explain_route(
+    source: Serde<RoughLocationID>,
+    destination: Serde<RoughLocationID>,
+    now_ticks: u32,
+) {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    RouteExplainerID::spawn(
+        source.0,
+        destination.0,
+        BrowserTransportUIID::local_first(world).into(),
+        Instant::new(now_ticks as usize),
+        world,
+    );
+}
+
+#[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), js_export)]
+pub fn get_trip_statistics() {
+    let system = unsafe { &mut *SYSTEM };
+    let world = &mut system.world();
+    TripStatisticsID::global_first(world)
+        .get_trip_statistics(BrowserTransportUIID::local_first(world).into(), world);
+}
+
+impl BrowserTransportUI {
+    fn update_incident_mesh(&mut self, lane: RawID, maybe_mesh: Option<::michelangelo::Mesh>) {
+        let had_incidents = self.lanes_with_incidents.remove(&lane);
+        if had_incidents || maybe_mesh.is_some() {
+            if maybe_mesh.is_some() {
+                self.lanes_with_incidents.insert(lane);
+            }
+
+            let updated_incident_groups = self.incident_grouper.update(
+                if had_incidents { Some(lane) } else { None },
+                maybe_mesh.map(|mesh| (lane, mesh)),
+            );
+
+            js! {
+                window.cbReactApp.boundSetState(oldState => update(oldState, {
+                    transport: {rendering: {
+                        incidentGroups: {
+                            "$add": @{updated_groups_to_js(
+                                updated_incident_groups
+                            )}
+                        }
+                    }}
+                }));
+            }
+        }
+    }
+}
 
 impl TransportUI for BrowserTransportUI {
     fn on_lane_constructed(
@@ -170,8 +314,11 @@ impl TransportUI for BrowserTransportUI {
         id: RawID,
         is_switch: bool,
         on_intersection: bool,
-        _world: &mut World,
+        world: &mut World,
     ) {
+        self.update_incident_mesh(id, None);
+        self.on_detectors_changed(id, &CVec::new(), world);
+
         if is_switch {
             let updated_lane_marker_gaps_groups =
                 self.lane_marker_gaps_grouper.update(Some(id), None);
@@ -229,13 +376,96 @@ impl TransportUI for BrowserTransportUI {
             .iter()
             .map(|render_info| Instance {
                 instance_position: [render_info.position[0], render_info.position[1], 0.0],
-                instance_direction: render_info.direction,
-                instance_color: self.car_colors
-                    [render_info.trip.as_raw().instance_id as usize % self.car_colors.len()],
+                // the car mesh is stretched to the length of each vehicle type
+                instance_direction: {
+                    let scale = render_info.vehicle_type.parameters().length / CAR_MESH_LENGTH;
+                    [
+                        render_info.direction[0] * scale,
+                        render_info.direction[1] * scale,
+                    ]
+                },
+                instance_color: match render_info.vehicle_type {
+                    VehicleType::Car => {
+                        self.car_colors
+                            [render_info.trip.as_raw().instance_id as usize % self.car_colors.len()]
+                    }
+                    other_type => self
+                        .vehicle_type_colors
+                        .get(&format!("{:?}", other_type))
+                        .cloned()
+                        .unwrap_or([0.0, 0.0, 0.0]),
+                },
             })
             .collect();
         self.car_instance_buffers.insert(from_lane, colored);
     }
+
+    fn on_incidents_changed(
+        &mut self,
+        lane: RawID,
+        incidents: &CVec<IncidentRenderInfo>,
+        _: &mut World,
+    ) {
+        use ::transport::ui::lane_mesh;
+        let maybe_mesh = if incidents.is_empty() {
+            None
+        } else {
+            Some(
+                incidents
+                    .iter()
+                    .map(|incident| lane_mesh(&incident.path))
+                    .sum(),
+            )
+        };
+        self.update_incident_mesh(lane, maybe_mesh);
+    }
+
+    fn on_detectors_changed(&mut self, lane: RawID, detectors: &CVec<DetectorInfo>, _: &mut World) {
+        if detectors.is_empty() {
+            js! {
+                window.cbReactApp.boundSetState(oldState => update(oldState, {
+                    transport: {detectors: {"$unset": [@{Serde(lane)}]}}
+                }));
+            }
+        } else {
+            js! {
+                window.cbReactApp.boundSetState(oldState => update(oldState, {
+                    transport: {detectors: {[@{Serde(lane)}]: {"$set": @{Serde(detectors)}}}}
+                }));
+            }
+        }
+    }
+}
+
+impl RouteExplanationRequester for BrowserTransportUI {
+    fn on_route_explanation(&mut self, explanation: &RouteExplanation, _: &mut World) {
+        use ::transport::ui::lane_mesh;
+        let mesh: ::michelangelo::Mesh = explanation.step_paths.iter().map(lane_mesh).sum();
+
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                transport: {
+                    routeExplanation: {"$set": {
+                        outcome: @{Serde(explanation.outcome)},
+                        steps: @{Serde(explanation.steps.clone())}
+                    }},
+                    rendering: {
+                        routeExplanationMesh: {"$set": @{to_js_mesh(&mesh)}}
+                    }
+                }
+            }));
+        }
+    }
+}
+
+impl TripStatisticsRequester for BrowserTransportUI {
+    fn on_trip_statistics(&mut self, modes: &CVec<ModeStatistics>, _: &mut World) {
+        js! {
+            window.cbReactApp.boundSetState(oldState => update(oldState, {
+                transport: {tripStatistics: {"$set": @{Serde(modes.clone())}}}
+            }));
+        }
+    }
 }
 
 mod kay_auto;
@@ -3,6 +3,7 @@ import { ToToolPortal, ToWindowPortal } from './citybound';
 import { Toolbar } from './toolbar';
 import { PlanningUI } from './planning_browser/Planning';
 import * as Households from './households_browser/Households';
+import * as Transport from './transport_browser/Transport';
 import update from 'immutability-helper';
 
 export default function MainUIModes(props: { state, setState, project2dTo3d, project3dTo2d }) {
@@ -14,7 +15,7 @@ export default function MainUIModes(props: { state, setState, project2dTo3d, pro
     return <>
         <ToToolPortal>
             <Toolbar id="main-toolbar"
-                options={{ inspection: { description: "Inspection" }, planning: { description: "Planning" } }}
+                options={{ inspection: { description: "Inspection" }, planning: { description: "Planning" }, incidents: { description: "Incidents" }, detectors: { description: "Detectors" }, happiness: { description: "Happiness" } }}
                 value={uiMode}
                 onChange={setUIMode} />
         </ToToolPortal>
@@ -22,7 +23,20 @@ export default function MainUIModes(props: { state, setState, project2dTo3d, pro
             ? <InspectionUI state={props.state} setState={props.setState} project2dTo3d={props.project2dTo3d} project3dTo2d={props.project3dTo2d} />
             : uiMode === 'planning'
                 ? <PlanningUI state={props.state} setState={props.setState} />
-                : null
+                : uiMode === 'incidents'
+                    ? <Transport.IncidentTools state={props.state} setState={props.setState} />
+                    : uiMode === 'detectors'
+                        ? <>
+                            <Transport.DetectorTools state={props.state} setState={props.setState} />
+                            <ToWindowPortal>
+                                <Transport.FundamentalDiagram state={props.state} />
+                            </ToWindowPortal>
+                        </>
+                        : uiMode === 'happiness'
+                            ? <ToWindowPortal>
+                                <Households.HappinessMap state={props.state} />
+                            </ToWindowPortal>
+                            : null
         }
     </>
 }
@@ -774,4 +774,7 @@ pub trait PlanningLogic: Compact + 'static {
 
         Ok(result)
     }
+
+    // called once the construction of an implemented project has been started
+    fn on_implemented(_project_id: plan_manager::ProjectID, _world: &mut ::kay::World) {}
 }
@@ -93,6 +93,8 @@ impl<Logic: PlanningLogic + 'static> PlanManager<Logic> {
                 self.master_result = result;
 
                 self.ui_state.invalidate_all();
+
+                Logic::on_implemented(project_id, world);
             }
             Err(err) => {
                 let err_str = match err {
@@ -0,0 +1,95 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+impl Actor for FreightDelivery {
+    type ID = FreightDeliveryID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct FreightDeliveryID {
+    _raw_id: RawID
+}
+
+impl Copy for FreightDeliveryID {}
+impl Clone for FreightDeliveryID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for FreightDeliveryID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "FreightDeliveryID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for FreightDeliveryID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for FreightDeliveryID {
+    fn eq(&self, other: &FreightDeliveryID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for FreightDeliveryID {}
+
+impl TypedID for FreightDeliveryID {
+    type Target = FreightDelivery;
+
+    fn from_raw(id: RawID) -> Self {
+        FreightDeliveryID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl FreightDeliveryID {
+    pub fn spawn(offer: OfferID, source: RoughLocationID, receiver: HouseholdID, receiver_member: MemberIdx, cargo: Deal, paid: Deal, instant: Instant, world: &mut World) -> Self {
+        let id = FreightDeliveryID::from_raw(world.allocate_instance_id::<FreightDelivery>());
+        let swarm = world.local_broadcast::<FreightDelivery>();
+        world.send(swarm, MSG_FreightDelivery_spawn(id, offer, source, receiver, receiver_member, cargo, paid, instant));
+        id
+    }
+    
+    pub fn done(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_FreightDelivery_done());
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_FreightDelivery_spawn(pub FreightDeliveryID, pub OfferID, pub RoughLocationID, pub HouseholdID, pub MemberIdx, pub Deal, pub Deal, pub Instant);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_FreightDelivery_done();
+
+impl Into<TripListenerID> for FreightDeliveryID {
+    fn into(self) -> TripListenerID {
+        TripListenerID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    
+    TripListenerID::register_implementor::<FreightDelivery>(system);
+    system.add_spawner::<FreightDelivery, _, _>(
+        |&MSG_FreightDelivery_spawn(id, offer, source, receiver, receiver_member, ref cargo, ref paid, instant), world| {
+            FreightDelivery::spawn(id, offer, source, receiver, receiver_member, cargo, paid, instant, world)
+        }, false
+    );
+    
+    system.add_handler::<FreightDelivery, _, _>(
+        |&MSG_FreightDelivery_done(), instance, world| {
+            instance.done(world)
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,203 @@
+use kay::{ActorSystem, World, Fate};
+use cb_time::units::Instant;
+use economy::market::Deal;
+use economy::resources::Entry;
+use economy::households::{HouseholdID, MemberIdx, OfferID};
+use economy::ledger::LedgerID;
+use transport::pathfinding::RoughLocationID;
+use transport::pathfinding::trip::{TripListener, TripListenerID, TripID, TripResult, TripFate};
+use transport::microtraffic::VehicleType;
+
+use cb_util::log::info;
+const LOG_T: &str = "Freight";
+
+const TRUCK_CARGO_THRESHOLD: f32 = 50.0;
+
+#[derive(Compact, Clone)]
+pub struct FreightDelivery {
+    id: FreightDeliveryID,
+    offer: OfferID,
+    receiver: HouseholdID,
+    receiver_member: MemberIdx,
+    cargo: Deal,
+    // the rest of the deal, already exchanged when it was made
+    paid: Deal,
+}
+
+impl FreightDelivery {
+    pub fn spawn(
+        id: FreightDeliveryID,
+        offer: OfferID,
+        source: RoughLocationID,
+        receiver: HouseholdID,
+        receiver_member: MemberIdx,
+        cargo: &Deal,
+        paid: &Deal,
+        instant: Instant,
+        world: &mut World,
+    ) -> FreightDelivery {
+        let total_amount: f32 = cargo.delta.iter().map(|&Entry(_, amount)| amount).sum();
+        let vehicle_type = if total_amount > TRUCK_CARGO_THRESHOLD {
+            VehicleType::Truck
+        } else {
+            VehicleType::DeliveryVan
+        };
+
+        TripID::spawn(
+            source,
+            receiver.into(),
+            Some(id.into()),
+            vehicle_type,
+            instant,
+            world,
+        );
+
+        FreightDelivery {
+            id,
+            offer,
+            receiver,
+            receiver_member,
+            cargo: cargo.clone(),
+            paid: paid.clone(),
+        }
+    }
+
+    pub fn done(&mut self, _: &mut World) -> Fate {
+        Fate::Die
+    }
+}
+
+impl TripListener for FreightDelivery {
+    fn trip_created(&mut self, _trip: TripID, _: &mut World) {}
+
+    fn trip_result(
+        &mut self,
+        _trip: TripID,
+        result: TripResult,
+        _rough_source: RoughLocationID,
+        _rough_destination: RoughLocationID,
+        world: &mut World,
+    ) {
+        match result.fate {
+            TripFate::Success(_) => {
+                LedgerID::global_first(world).record(
+                    self.offer.household,
+                    self.receiver,
+                    self.cargo.delta.clone(),
+                    self.receiver.into(),
+                    world,
+                );
+                self.receiver
+                    .receive_deal(self.cargo.clone(), self.receiver_member, world);
+            }
+            fate => {
+                info(
+                    LOG_T,
+                    format!(
+                        "Delivery of {} to {:?} failed ({:?})",
+                        self.cargo.main_given(),
+                        self.receiver,
+                        fate
+                    ),
+                    self.id,
+                    world,
+                );
+                // refund the receiver here, the offering household might not exist anymore
+                LedgerID::global_first(world).record(
+                    self.offer.household,
+                    self.receiver,
+                    self.paid.reversed().delta,
+                    self.receiver.into(),
+                    world,
+                );
+                self.receiver
+                    .provide_deal(self.paid.clone(), self.receiver_member, world);
+                // the offering household takes back the cargo and gives back the payment
+                self.offer.household.freight_failed(
+                    self.offer.idx,
+                    self.cargo.clone(),
+                    self.paid.clone(),
+                    world,
+                );
+            }
+        }
+
+        // the van drives off again right after unloading
+        if let Some(ticket) = result.parked_at {
+            ticket.give_back(world);
+        }
+
+        self.id.done(world);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cb_time::units::Duration;
+    use economy::resources::{Inventory, Resource};
+
+    fn grain_for_money() -> Deal {
+        Deal::new(
+            vec![(Resource::Grain, 10.0), (Resource::Money, -50.0)],
+            Duration(0),
+        )
+    }
+
+    fn stocked(grain: f32, money: f32) -> Inventory {
+        vec![(Resource::Grain, grain), (Resource::Money, money)]
+            .into_iter()
+            .collect()
+    }
+
+    fn amount(inventory: &Inventory, resource: Resource) -> f32 {
+        inventory.get(resource).cloned().unwrap_or(0.0)
+    }
+
+    // what request_receive_deal and request_freight exchange before the delivery
+    fn make_deal(deal: &Deal, seller: &mut Inventory, buyer: &mut Inventory) -> (Deal, Deal) {
+        let paid = deal.without_freight();
+        paid.delta.take_from(seller);
+        paid.delta.give_to(buyer);
+        let cargo = deal.freight().unwrap();
+        cargo.delta.take_from(seller);
+        (cargo, paid)
+    }
+
+    #[test]
+    fn delivered_freight_completes_the_deal() {
+        let (mut seller, mut buyer) = (stocked(10.0, 0.0), stocked(0.0, 50.0));
+        let (cargo, _paid) = make_deal(&grain_for_money(), &mut seller, &mut buyer);
+
+        cargo.delta.give_to(&mut buyer);
+
+        assert_eq!(amount(&seller, Resource::Grain), 0.0);
+        assert_eq!(amount(&seller, Resource::Money), 50.0);
+        assert_eq!(amount(&buyer, Resource::Grain), 10.0);
+        assert_eq!(amount(&buyer, Resource::Money), 0.0);
+    }
+
+    #[test]
+    fn failed_freight_gives_everything_back() {
+        let (mut seller, mut buyer) = (stocked(10.0, 0.0), stocked(0.0, 50.0));
+        let (cargo, paid) = make_deal(&grain_for_money(), &mut seller, &mut buyer);
+
+        // the receiver refunds itself, then freight_failed on the seller's side
+        paid.delta.take_from(&mut buyer);
+        cargo.delta.give_to(&mut seller);
+        paid.delta.give_to(&mut seller);
+
+        assert_eq!(amount(&seller, Resource::Grain), 10.0);
+        assert_eq!(amount(&seller, Resource::Money), 0.0);
+        assert_eq!(amount(&buyer, Resource::Grain), 0.0);
+        assert_eq!(amount(&buyer, Resource::Money), 50.0);
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<FreightDelivery>();
+    auto_setup(system);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
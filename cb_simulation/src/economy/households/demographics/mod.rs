@@ -0,0 +1,146 @@
+use cb_util::random::Rng;
+
+// demographic time runs faster than the clock, so that generations pass within a game
+pub const DAYS_PER_YEAR: f32 = 4.0;
+pub const ADULT_AGE: f32 = 18.0;
+pub const RETIREMENT_AGE: f32 = 65.0;
+const DEFAULT_AGE: f32 = 35.0;
+const FERTILE_AGES: (f32, f32) = (20.0, 45.0);
+// the age difference at which a member counts as a child of the eldest
+const GENERATION_GAP: f32 = 16.0;
+const YEARLY_BIRTH_RATE: f32 = 0.2;
+const YEARLY_MOVE_OUT_RATE: f32 = 0.25;
+pub const MAX_FAMILY_SIZE: usize = 6;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Role {
+    // will go to school instead of doing nothing in particular once there is education
+    Child,
+    Worker,
+    Retiree,
+    // died or moved out, the slot is kept so that member indices stay valid
+    Departed,
+}
+
+impl Role {
+    pub fn for_age(age: f32) -> Role {
+        if age < ADULT_AGE {
+            Role::Child
+        } else if age < RETIREMENT_AGE {
+            Role::Worker
+        } else {
+            Role::Retiree
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct Demographics {
+    pub age: f32,
+    pub role: Role,
+}
+
+impl Demographics {
+    pub fn of_age(age: f32) -> Demographics {
+        Demographics {
+            age,
+            role: Role::for_age(age),
+        }
+    }
+
+    pub fn is_present(&self) -> bool {
+        self.role != Role::Departed
+    }
+
+    pub fn is_fertile(&self) -> bool {
+        self.is_present() && self.age >= FERTILE_AGES.0 && self.age < FERTILE_AGES.1
+    }
+}
+
+impl Default for Demographics {
+    fn default() -> Demographics {
+        Demographics::of_age(DEFAULT_AGE)
+    }
+}
+
+// a single young adult, or parents of a similar age with children
+pub fn initial_demographics<R: Rng>(n_members: usize, rng: &mut R) -> Vec<Demographics> {
+    if n_members == 1 {
+        return vec![Demographics::of_age(rng.gen_range(ADULT_AGE, 30.0))];
+    }
+
+    let parent_age = rng.gen_range(25.0, 60.0);
+    (0..n_members)
+        .map(|member| match member {
+            0 => Demographics::of_age(parent_age),
+            1 => Demographics::of_age(parent_age + rng.gen_range(-5.0, 5.0)),
+            _ => Demographics::of_age(rng.gen_range(0.0, (parent_age - 20.0).min(ADULT_AGE))),
+        })
+        .collect()
+}
+
+// grows with age, starting very low for young people
+pub fn daily_mortality(age: f32) -> f32 {
+    0.000_05 * (0.09 * age).exp() / DAYS_PER_YEAR
+}
+
+// needs a couple, at least one of them still young enough
+pub fn daily_birth_chance(members: &[Demographics]) -> f32 {
+    let n_present = members.iter().filter(|member| member.is_present()).count();
+    let adults = members
+        .iter()
+        .filter(|member| member.is_present() && member.age >= ADULT_AGE)
+        .collect::<Vec<_>>();
+    if n_present < MAX_FAMILY_SIZE
+        && adults.len() >= 2
+        && adults.iter().any(|adult| adult.is_fertile())
+    {
+        YEARLY_BIRTH_RATE / DAYS_PER_YEAR
+    } else {
+        0.0
+    }
+}
+
+// only grown-up children leave to form their own household
+pub fn daily_move_out_chance(member: &Demographics, members: &[Demographics]) -> f32 {
+    let eldest_age = members
+        .iter()
+        .filter(|other| other.is_present())
+        .map(|other| other.age)
+        .fold(0.0, f32::max);
+    if member.role == Role::Worker && member.age < eldest_age - GENERATION_GAP {
+        YEARLY_MOVE_OUT_RATE / DAYS_PER_YEAR
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_adults_have_no_children() {
+        assert_eq!(daily_birth_chance(&[Demographics::of_age(30.0)]), 0.0);
+        assert_eq!(
+            daily_birth_chance(&[Demographics::of_age(30.0), Demographics::of_age(5.0)]),
+            0.0
+        );
+    }
+
+    #[test]
+    fn couples_need_one_fertile_member() {
+        let couple = [Demographics::of_age(30.0), Demographics::of_age(50.0)];
+        assert!(daily_birth_chance(&couple) > 0.0);
+
+        let old_couple = [Demographics::of_age(60.0), Demographics::of_age(62.0)];
+        assert_eq!(daily_birth_chance(&old_couple), 0.0);
+    }
+
+    #[test]
+    fn departed_members_dont_count() {
+        let mut widowed = [Demographics::of_age(30.0), Demographics::of_age(32.0)];
+        widowed[1].role = Role::Departed;
+        assert_eq!(daily_birth_chance(&widowed), 0.0);
+    }
+}
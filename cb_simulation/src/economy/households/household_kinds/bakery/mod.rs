@@ -68,15 +68,15 @@ impl Household for Bakery {
         self.site.into()
     }
 
-    fn is_shared(_: Resource) -> bool {
+    fn is_shared(&self, _: Resource) -> bool {
         true
     }
 
-    fn supplier_shared(_: Resource) -> bool {
+    fn supplier_shared(&self, _: Resource) -> bool {
         true
     }
 
-    fn importance(resource: Resource, time: TimeOfDay) -> f32 {
+    fn importance(&self, resource: Resource, time: TimeOfDay) -> f32 {
         let hour = time.hours_minutes().0;
 
         let bihourly_importance = match resource {
@@ -89,7 +89,7 @@ impl Household for Bakery {
             .unwrap_or(0.0)
     }
 
-    fn interesting_resources() -> &'static [Resource] {
+    fn interesting_resources(&self) -> &[Resource] {
         &[
             Resource::Money,
             Resource::Flour,
@@ -128,18 +128,12 @@ impl Household for Bakery {
     }
 }
 
-use cb_time::units::{Instant, TICKS_PER_SIM_SECOND};
+use cb_time::units::Instant;
 use cb_time::actors::{Temporal, TemporalID, Sleeper, SleeperID};
-const UPDATE_EVERY_N_SECS: u32 = 4;
 
 impl Temporal for Bakery {
     fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
-        if (current_instant.ticks() + self.id.as_raw().instance_id as usize)
-            % (UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND) as usize
-            == 0
-        {
-            self.decay(Duration(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND), world);
-        }
+        self.on_tick(current_instant, world);
     }
 }
 
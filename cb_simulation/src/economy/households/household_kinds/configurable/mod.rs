@@ -0,0 +1,307 @@
+use kay::{ActorSystem, World, TypedID, Actor};
+use compact::{CVec, CHashMap};
+use cb_time::units::{TimeOfDay, TimeOfDayRange, Duration, Ticks};
+use cb_time::actors::TimeID;
+use cb_util::config_manager::{Config, Name};
+use economy::resources::{Resource, ResourceAmount, Entry};
+use economy::market::{Deal, EvaluationRequester, EvaluationRequesterID, EvaluatedSearchResult};
+use land_use::buildings::{BuildingID, BuildingStyle, UnitType};
+
+use economy::households::{Household, HouseholdID, HouseholdCore, MemberIdx, Offer};
+
+// a household kind as described in modding/household_kinds.yaml
+#[derive(Compact, Clone, Serialize, Deserialize)]
+pub struct HouseholdKindConfig {
+    pub household_name: Name,
+    pub member_name: Name,
+    pub n_members: u32,
+    pub unit_type: UnitType,
+    pub building_style: BuildingStyle,
+    // how likely this kind is to immigrate without unmet demand for what it sells,
+    // relative to a family at 1.0
+    pub immigration_share: f32,
+    pub offers: CVec<OfferConfig>,
+    #[serde(default)]
+    pub importance: CVec<ImportanceConfig>,
+    #[serde(default)]
+    pub daily_rates: CVec<Entry<ResourceAmount>>,
+}
+
+impl Config for HouseholdKindConfig {
+    fn sanitize(&mut self, name: Name) {
+        if self.n_members == 0 {
+            println!("Household kind {} needs at least one member", name);
+            self.n_members = 1;
+        }
+
+        let last_member = self.n_members - 1;
+        for offer in self.offers.iter_mut() {
+            if offer.member > last_member {
+                println!(
+                    "Household kind {} has no member {}, giving the offer to member {}",
+                    name, offer.member, last_member
+                );
+                offer.member = last_member;
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone, Serialize, Deserialize)]
+pub struct OpeningHours {
+    pub from: (usize, usize),
+    pub to: (usize, usize),
+}
+
+#[derive(Compact, Clone, Serialize, Deserialize)]
+pub struct OfferConfig {
+    pub member: u32,
+    pub opening_hours: OpeningHours,
+    pub deal: CVec<Entry<ResourceAmount>>,
+    pub duration_minutes: usize,
+    pub max_users: usize,
+    #[serde(default)]
+    pub internal: bool,
+}
+
+impl OfferConfig {
+    fn to_offer(&self) -> Offer {
+        Offer::new(
+            MemberIdx(self.member),
+            TimeOfDayRange::new(
+                self.opening_hours.from.0,
+                self.opening_hours.from.1,
+                self.opening_hours.to.0,
+                self.opening_hours.to.1,
+            ),
+            Deal::new(
+                self.deal
+                    .iter()
+                    .map(|&Entry(resource, amount)| (resource, amount)),
+                Duration::from_minutes(self.duration_minutes),
+            ),
+            self.max_users,
+            self.internal,
+        )
+    }
+}
+
+#[derive(Copy, Clone, Serialize, Deserialize)]
+pub struct ImportanceConfig {
+    pub resource: Resource,
+    pub bihourly: [f32; 12],
+}
+
+#[derive(Compact, Clone)]
+pub struct ConfigurableHousehold {
+    id: ConfigurableHouseholdID,
+    site: BuildingID,
+    kind: Name,
+    // a copy as of moving in, later config changes only affect new households
+    config: HouseholdKindConfig,
+    interesting_resources: CVec<Resource>,
+    core: HouseholdCore,
+}
+
+impl ConfigurableHousehold {
+    pub fn move_into(
+        id: ConfigurableHouseholdID,
+        kind: Name,
+        config: &HouseholdKindConfig,
+        site: BuildingID,
+        time: TimeID,
+        world: &mut World,
+    ) -> ConfigurableHousehold {
+        time.wake_up_in(Ticks(0), id.into(), world);
+
+        let mut interesting_resources = vec![Resource::Money];
+        for resource in config
+            .offers
+            .iter()
+            .flat_map(|offer| offer.deal.iter().map(|&Entry(resource, _)| resource))
+            .chain(
+                config
+                    .importance
+                    .iter()
+                    .map(|importance| importance.resource),
+            )
+            .chain(
+                config
+                    .daily_rates
+                    .iter()
+                    .map(|&Entry(resource, _)| resource),
+            )
+        {
+            if !interesting_resources.contains(&resource) {
+                interesting_resources.push(resource);
+            }
+        }
+
+        ConfigurableHousehold {
+            id,
+            site,
+            kind,
+            config: config.clone(),
+            interesting_resources: interesting_resources.into(),
+            core: HouseholdCore::new(
+                id.into(),
+                world,
+                config.n_members as usize,
+                site.into(),
+                config
+                    .offers
+                    .iter()
+                    .map(OfferConfig::to_offer)
+                    .collect::<Vec<_>>()
+                    .into(),
+            ),
+        }
+    }
+
+    pub fn kind(&self) -> Name {
+        self.kind
+    }
+}
+
+impl Household for ConfigurableHousehold {
+    fn core(&self) -> &HouseholdCore {
+        &self.core
+    }
+
+    fn core_mut(&mut self) -> &mut HouseholdCore {
+        &mut self.core
+    }
+
+    fn site(&self) -> RoughLocationID {
+        self.site.into()
+    }
+
+    fn is_shared(&self, _: Resource) -> bool {
+        true
+    }
+
+    fn supplier_shared(&self, _: Resource) -> bool {
+        true
+    }
+
+    fn importance(&self, resource: Resource, time: TimeOfDay) -> f32 {
+        let hour = time.hours_minutes().0;
+
+        self.config
+            .importance
+            .iter()
+            .find(|importance| importance.resource == resource)
+            .map(|importance| importance.bihourly[hour / 2])
+            .unwrap_or(0.0)
+    }
+
+    fn interesting_resources(&self) -> &[Resource] {
+        &self.interesting_resources
+    }
+
+    fn decay(&mut self, dt: Duration, _: &mut World) {
+        for &Entry(resource, rate) in self.config.daily_rates.iter() {
+            let amount = self.core.resources.mut_entry_or(resource, 0.0);
+            *amount += rate * dt.as_days();
+        }
+    }
+
+    fn household_name(&self) -> String {
+        self.config.household_name.to_string()
+    }
+
+    fn member_name(&self, member: MemberIdx) -> String {
+        format!("{} {}", self.config.member_name, member.0 + 1)
+    }
+
+    fn on_destroy(&mut self, world: &mut World) {
+        self.site.remove_household(self.id_as(), world);
+    }
+}
+
+use economy::households::ResultAspect;
+
+impl EvaluationRequester for ConfigurableHousehold {
+    fn expect_n_results(&mut self, resource: Resource, n: u32, world: &mut World) {
+        self.update_results(resource, &ResultAspect::SetTarget(n), world);
+    }
+
+    fn on_result(&mut self, result: &EvaluatedSearchResult, world: &mut World) {
+        let &EvaluatedSearchResult {
+            resource,
+            ref evaluated_deals,
+            ..
+        } = result;
+        self.update_results(
+            resource,
+            &ResultAspect::AddDeals(evaluated_deals.clone()),
+            world,
+        );
+    }
+}
+
+use cb_time::units::Instant;
+use cb_time::actors::{Temporal, TemporalID, Sleeper, SleeperID};
+
+impl Temporal for ConfigurableHousehold {
+    fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
+        self.on_tick(current_instant, world);
+    }
+}
+
+impl Sleeper for ConfigurableHousehold {
+    fn wake(&mut self, current_instant: Instant, world: &mut World) {
+        self.update_core(current_instant, world);
+    }
+}
+
+use transport::pathfinding::{RoughLocationID, RoughLocation, RoughLocationResolve};
+
+impl RoughLocation for ConfigurableHousehold {
+    fn resolve(&self) -> RoughLocationResolve {
+        RoughLocationResolve::SameAs(self.site())
+    }
+}
+
+use transport::pathfinding::trip::{TripListener, TripListenerID, TripID, TripResult};
+
+impl TripListener for ConfigurableHousehold {
+    fn trip_created(&mut self, trip: TripID, world: &mut World) {
+        self.on_trip_created(trip, world);
+    }
+
+    fn trip_result(
+        &mut self,
+        trip: TripID,
+        result: TripResult,
+        rough_source: RoughLocationID,
+        rough_destination: RoughLocationID,
+        world: &mut World,
+    ) {
+        self.on_trip_result(trip, result, rough_source, rough_destination, world);
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<ConfigurableHousehold>();
+    auto_setup(system);
+
+    system.register::<cb_util::config_manager::ConfigManager<HouseholdKindConfig>>();
+    system.register::<cb_util::config_manager::ConfigFileWatcher<HouseholdKindConfig>>();
+    cb_util::config_manager::auto_setup::<HouseholdKindConfig, HouseholdKindConfig>(system);
+}
+
+pub fn spawn(world: &mut World) {
+    let config_manager = cb_util::config_manager::ConfigManagerID::<HouseholdKindConfig>::spawn(
+        CHashMap::new(),
+        world,
+    );
+    cb_util::config_manager::ConfigFileWatcherID::spawn(
+        config_manager,
+        "modding/household_kinds.yaml".to_owned().into(),
+        world,
+    );
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -78,15 +78,15 @@ impl Household for CowFarm {
         self.site.into()
     }
 
-    fn is_shared(_: Resource) -> bool {
+    fn is_shared(&self, _: Resource) -> bool {
         true
     }
 
-    fn supplier_shared(_: Resource) -> bool {
+    fn supplier_shared(&self, _: Resource) -> bool {
         true
     }
 
-    fn importance(resource: Resource, time: TimeOfDay) -> f32 {
+    fn importance(&self, resource: Resource, time: TimeOfDay) -> f32 {
         let hour = time.hours_minutes().0;
 
         let bihourly_importance = match resource {
@@ -99,7 +99,7 @@ impl Household for CowFarm {
             .unwrap_or(0.0)
     }
 
-    fn interesting_resources() -> &'static [Resource] {
+    fn interesting_resources(&self) -> &[Resource] {
         &[
             Resource::Money,
             Resource::Grain,
@@ -138,18 +138,12 @@ impl Household for CowFarm {
     }
 }
 
-use cb_time::units::{Instant, TICKS_PER_SIM_SECOND};
+use cb_time::units::Instant;
 use cb_time::actors::{Temporal, TemporalID, Sleeper, SleeperID};
-const UPDATE_EVERY_N_SECS: u32 = 4;
 
 impl Temporal for CowFarm {
     fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
-        if (current_instant.ticks() + self.id.as_raw().instance_id as usize)
-            % (UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND) as usize
-            == 0
-        {
-            self.decay(Duration(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND), world);
-        }
+        self.on_tick(current_instant, world);
     }
 }
 
@@ -1,9 +1,11 @@
 use kay::{ActorSystem, World, Actor};
 use cb_util::random::{seed, Rng};
+use cb_util::log::info;
+const LOG_T: &str = "Family";
 
 use cb_time::actors::{Temporal, TemporalID, TimeID};
 use cb_time::units::{TimeOfDay, TimeOfDayRange, Instant, Duration, Ticks};
-use economy::resources::Resource;
+use economy::resources::{Resource, ResourceMap, Entry};
 use economy::resources::Resource::*;
 use economy::market::{Deal, EvaluationRequester, EvaluationRequesterID, EvaluatedSearchResult};
 use land_use::buildings::BuildingID;
@@ -15,6 +17,12 @@ use self::names::{family_name, member_name};
 
 use economy::households::{Household, HouseholdID, HouseholdCore,
 MemberIdx, Offer, OfferID, OfferIdx};
+use economy::households::DecisionState;
+use economy::households::tasks::TaskState;
+use economy::households::jobs::{switch_job, EmploymentStatisticsID};
+use economy::households::demographics::{Role, Demographics, DAYS_PER_YEAR, initial_demographics,
+daily_mortality, daily_birth_chance, daily_move_out_chance};
+use economy::immigration_and_development::ImmigrationManagerID;
 
 #[derive(Compact, Clone)]
 pub struct Family {
@@ -33,6 +41,13 @@ impl Family {
     ) -> Family {
         time.wake_up_in(Ticks(0), id.into(), world);
 
+        let demographics = initial_demographics(n_members as usize, &mut seed(id));
+        let n_workers = demographics
+            .iter()
+            .filter(|member| member.role == Role::Worker)
+            .count();
+        EmploymentStatisticsID::local_first(world).on_joined_labor_force(n_workers as u32, world);
+
         let mut core = HouseholdCore::new(
             id.into(),
             world,
@@ -48,6 +63,8 @@ impl Family {
             .into(),
         );
 
+        core.member_demographics = demographics.into();
+
         core.used_offers.insert(
             Wakefulness,
             OfferID {
@@ -58,6 +75,59 @@ impl Family {
 
         Family { id, home, core }
     }
+
+    // members can only leave while they are at home and not deciding what to do next
+    fn can_leave(&self, member: MemberIdx) -> bool {
+        let idle = match self.core.member_tasks[member.as_idx()].state {
+            TaskState::IdleAt(_) => true,
+            _ => false,
+        };
+        let deciding = match self.core.decision_state {
+            DecisionState::Choosing(deciding_member, ..)
+            | DecisionState::WaitingForTrip(deciding_member) => deciding_member == member,
+            DecisionState::None => false,
+        };
+        idle && !deciding
+    }
+
+    fn give_up_job(&mut self, member: MemberIdx, world: &mut World) {
+        if let Some(job_offer) = self.core.member_used_offers[member.as_idx()].remove(Money) {
+            job_offer
+                .household
+                .stopped_using(job_offer.idx, self.id_as(), Some(member), world);
+        }
+        switch_job(&mut self.core.member_jobs[member.as_idx()], None, world);
+        EmploymentStatisticsID::local_first(world).on_left_labor_force(1, world);
+    }
+
+    fn leave(&mut self, member: MemberIdx, world: &mut World) {
+        if self.core.member_demographics[member.as_idx()].role == Role::Worker {
+            self.give_up_job(member, world);
+        }
+
+        let id_as_household = self.id_as();
+
+        for &Entry(_, offer) in self.core.member_used_offers[member.as_idx()].iter() {
+            offer
+                .household
+                .stopped_using(offer.idx, id_as_household, Some(member), world);
+        }
+        self.core.member_used_offers[member.as_idx()] = ResourceMap::new();
+
+        if let Some(ticket) = self.core.member_tasks[member.as_idx()].parked_car.take() {
+            ticket.give_back(world);
+        }
+
+        self.core.member_demographics[member.as_idx()].role = Role::Departed;
+    }
+
+    fn n_workers(&self) -> u32 {
+        self.core
+            .member_demographics
+            .iter()
+            .filter(|member| member.role == Role::Worker)
+            .count() as u32
+    }
 }
 
 use cb_time::actors::{Sleeper, SleeperID};
@@ -121,23 +191,24 @@ impl Household for Family {
         self.home.into()
     }
 
-    fn is_shared(resource: Resource) -> bool {
+    fn is_shared(&self, resource: Resource) -> bool {
         match resource {
-            Wakefulness | Satiety => false,
-            Money | Groceries => true,
-            _ => unimplemented!(),
+            Wakefulness | Satiety | Entertainment => false,
+            Money | Groceries | Furniture | Clothes | Devices | Services => true,
+            // goods a family doesn't use itself would still belong to all of it
+            Produce | Grain | Flour | BakedGoods | Meat | DairyGoods | Wood | TextileGoods => true,
         }
     }
 
-    fn supplier_shared(resource: Resource) -> bool {
+    fn supplier_shared(&self, resource: Resource) -> bool {
         match resource {
-            Money => false,
-            Wakefulness | Satiety | Groceries => true,
-            _ => unimplemented!(),
+            Money | Entertainment => false,
+            Wakefulness | Satiety | Groceries | Furniture | Clothes | Devices | Services => true,
+            Produce | Grain | Flour | BakedGoods | Meat | DairyGoods | Wood | TextileGoods => true,
         }
     }
 
-    fn importance(resource: Resource, time: TimeOfDay) -> f32 {
+    fn importance(&self, resource: Resource, time: TimeOfDay) -> f32 {
         let hour = time.hours_minutes().0;
 
         let bihourly_importance = match resource {
@@ -145,6 +216,10 @@ impl Household for Family {
             Satiety => Some([0, 0, 5, 5, 1, 5, 5, 1, 5, 5, 1, 1]),
             Money => Some([0, 0, 3, 3, 5, 5, 5, 3, 3, 1, 1, 1]),
             Groceries => Some([0, 0, 4, 4, 1, 4, 4, 4, 4, 4, 0, 0]),
+            Entertainment => Some([0, 0, 0, 0, 0, 0, 1, 1, 3, 3, 2, 0]),
+            Furniture | Devices => Some([0, 0, 0, 0, 1, 2, 2, 2, 2, 1, 0, 0]),
+            Clothes => Some([0, 0, 0, 0, 2, 2, 2, 2, 2, 1, 0, 0]),
+            Services => Some([0, 0, 0, 0, 2, 2, 2, 2, 1, 0, 0, 0]),
             _ => None,
         };
 
@@ -153,21 +228,105 @@ impl Household for Family {
             .unwrap_or(0.0)
     }
 
-    fn interesting_resources() -> &'static [Resource] {
+    fn update_demographics(&mut self, instant: Instant, world: &mut World) {
+        let mut rng = seed((instant.ticks(), self.id));
+
+        for idx in 0..self.core.member_demographics.len() {
+            let member = MemberIdx::new(idx);
+            let demographics = self.core.member_demographics[idx];
+            if !demographics.is_present() {
+                continue;
+            }
+
+            let age = demographics.age + 1.0 / DAYS_PER_YEAR;
+            let role = Role::for_age(age);
+            self.core.member_demographics[idx].age = age;
+
+            if role != demographics.role {
+                match (demographics.role, role) {
+                    (Role::Child, Role::Worker) => {
+                        EmploymentStatisticsID::local_first(world).on_joined_labor_force(1, world);
+                    }
+                    (Role::Worker, Role::Retiree) => self.give_up_job(member, world),
+                    _ => {}
+                }
+                self.core.member_demographics[idx].role = role;
+            }
+
+            if !self.can_leave(member) {
+                continue;
+            }
+
+            if rng.gen::<f32>() < daily_mortality(age) {
+                info(
+                    LOG_T,
+                    format!("{} died at {:.0}", self.member_name(member), age),
+                    self.id(),
+                    world,
+                );
+                self.leave(member, world);
+            } else if rng.gen::<f32>()
+                < daily_move_out_chance(
+                    &self.core.member_demographics[idx],
+                    &self.core.member_demographics,
+                )
+            {
+                info(
+                    LOG_T,
+                    format!("{} moved out to form a household", self.member_name(member)),
+                    self.id(),
+                    world,
+                );
+                self.leave(member, world);
+                ImmigrationManagerID::local_first(world).on_household_forming(world);
+            }
+        }
+
+        if rng.gen::<f32>() < daily_birth_chance(&self.core.member_demographics) {
+            let home = self.site();
+            let child = self.core.add_member(Demographics::of_age(0.0), home, world);
+            info(
+                LOG_T,
+                format!("{} was born", self.member_name(child)),
+                self.id(),
+                world,
+            );
+        }
+
+        if !self
+            .core
+            .member_demographics
+            .iter()
+            .any(|member| member.is_present())
+        {
+            info(LOG_T, "No members left", self.id(), world);
+            self.destroy(world);
+        }
+    }
+
+    fn is_residential(&self) -> bool {
+        true
+    }
+
+    fn interesting_resources(&self) -> &[Resource] {
         &[
             Wakefulness,
-            Satiety, //Entertainment,
+            Satiety,
+            Entertainment,
             Money,
             Groceries,
-            /* Furniture,
-             *Clothes,
-             *Devices,
-             *Services, */
+            Furniture,
+            Clothes,
+            Devices,
+            Services,
         ]
     }
 
     fn decay(&mut self, dt: Duration, _: &mut World) {
         for (i, member_resources) in self.core.member_resources.iter_mut().enumerate() {
+            if !self.core.member_demographics[i].is_present() {
+                continue;
+            }
             {
                 let individuality = seed((self.id, i)).gen_range(0.8, 1.2);
                 let wakefulness = member_resources.mut_entry_or(Wakefulness, 0.0);
@@ -183,31 +342,37 @@ impl Household for Family {
                 }
                 *satiety -= 3.0 * individuality * dt.as_days();
             }
-            // {
-            //     let individuality = seed((self.id, i)).gen_range(0.8, 1.2);
-            //     let entertainment = member_resources.mut_entry_or(Entertainment, 0.0);
-            //     *entertainment -= 0.2 * individuality * dt.as_hours();
-            // }
+            {
+                let individuality = seed((self.id, i, 2u8)).gen_range(0.8, 1.2);
+                let entertainment = member_resources.mut_entry_or(Entertainment, 0.0);
+                *entertainment -= 0.2 * individuality * dt.as_hours();
+            }
+        }
+        {
+            let individuality = seed((self.id, 3u8)).gen_range(0.8, 1.2);
+            let furniture = self.core.resources.mut_entry_or(Furniture, 0.0);
+            *furniture -= 0.005 * individuality * dt.as_hours();
+        }
+        {
+            let individuality = seed((self.id, 4u8)).gen_range(0.8, 1.2);
+            let clothes = self.core.resources.mut_entry_or(Clothes, 0.0);
+            *clothes -= 0.01 * individuality * dt.as_hours();
+        }
+        {
+            let individuality = seed((self.id, 5u8)).gen_range(0.8, 1.2);
+            let devices = self.core.resources.mut_entry_or(Devices, 0.0);
+            *devices -= 0.005 * individuality * dt.as_hours();
+        }
+        {
+            let individuality = seed((self.id, 6u8)).gen_range(0.8, 1.2);
+            let services = self.core.resources.mut_entry_or(Services, 0.0);
+            *services -= 0.01 * individuality * dt.as_hours();
         }
-        // {
-        //     let individuality = seed(self.id).gen_range(0.8, 1.2);
-        //     let furniture = self.core.resources.mut_entry_or(Furniture, 0.0);
-        //     *furniture -= 0.005 * individuality * dt.as_hours();
-        // }
-        // {
-        //     let individuality = seed(self.id).gen_range(0.8, 1.2);
-        //     let devices = self.core.resources.mut_entry_or(Devices, 0.0);
-        //     *devices -= 0.005 * individuality * dt.as_hours();
-        // }
-        // {
-        //     let individuality = seed(self.id).gen_range(0.8, 1.2);
-        //     let services = self.core.resources.mut_entry_or(Services, 0.0);
-        //     *services -= 0.01 * individuality * dt.as_hours();
-        // }
     }
 
     fn on_destroy(&mut self, world: &mut World) {
         self.home.remove_household(self.id_as(), world);
+        EmploymentStatisticsID::local_first(world).on_left_labor_force(self.n_workers(), world);
     }
 
     fn household_name(&self) -> String {
@@ -70,19 +70,19 @@ impl Household for GrainFarm {
         self.site.into()
     }
 
-    fn is_shared(_: Resource) -> bool {
+    fn is_shared(&self, _: Resource) -> bool {
         true
     }
 
-    fn supplier_shared(_: Resource) -> bool {
+    fn supplier_shared(&self, _: Resource) -> bool {
         true
     }
 
-    fn importance(_: Resource, _: TimeOfDay) -> f32 {
+    fn importance(&self, _: Resource, _: TimeOfDay) -> f32 {
         0.0
     }
 
-    fn interesting_resources() -> &'static [Resource] {
+    fn interesting_resources(&self) -> &[Resource] {
         &[Resource::Money, Resource::Grain]
     }
 
@@ -106,18 +106,12 @@ impl Household for GrainFarm {
     }
 }
 
-use cb_time::units::{Instant, TICKS_PER_SIM_SECOND};
+use cb_time::units::Instant;
 use cb_time::actors::{Temporal, TemporalID, Sleeper, SleeperID};
-const UPDATE_EVERY_N_SECS: u32 = 4;
 
 impl Temporal for GrainFarm {
     fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
-        if (current_instant.ticks() + self.id.as_raw().instance_id as usize)
-            % (UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND) as usize
-            == 0
-        {
-            self.decay(Duration(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND), world);
-        }
+        self.on_tick(current_instant, world);
     }
 }
 
@@ -70,15 +70,15 @@ impl Household for GroceryShop {
         self.site.into()
     }
 
-    fn is_shared(_: Resource) -> bool {
+    fn is_shared(&self, _: Resource) -> bool {
         true
     }
 
-    fn supplier_shared(_: Resource) -> bool {
+    fn supplier_shared(&self, _: Resource) -> bool {
         true
     }
 
-    fn importance(resource: Resource, time: TimeOfDay) -> f32 {
+    fn importance(&self, resource: Resource, time: TimeOfDay) -> f32 {
         let hour = time.hours_minutes().0;
 
         let bihourly_importance = match resource {
@@ -93,7 +93,7 @@ impl Household for GroceryShop {
             .unwrap_or(0.0)
     }
 
-    fn interesting_resources() -> &'static [Resource] {
+    fn interesting_resources(&self) -> &[Resource] {
         &[
             Money, Groceries, Produce, Grain, Flour, BakedGoods, Meat, DairyGoods,
         ]
@@ -170,18 +170,12 @@ impl EvaluationRequester for GroceryShop {
     }
 }
 
-use cb_time::units::{Instant, TICKS_PER_SIM_SECOND};
+use cb_time::units::Instant;
 use cb_time::actors::{Temporal, TemporalID, Sleeper, SleeperID};
-const UPDATE_EVERY_N_SECS: u32 = 4;
 
 impl Temporal for GroceryShop {
     fn tick(&mut self, _dt: f32, current_instant: Instant, world: &mut World) {
-        if (current_instant.ticks() + self.id.as_raw().instance_id as usize)
-            % (UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND) as usize
-            == 0
-        {
-            self.decay(Duration(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND), world);
-        }
+        self.on_tick(current_instant, world);
     }
 }
 
@@ -6,3 +6,4 @@ pub mod vegetable_farm;
 pub mod mill;
 pub mod bakery;
 pub mod neighboring_town_trade;
+pub mod configurable;
@@ -34,26 +34,26 @@ impl NeighboringTownTrade {
                 300,
                 false,
             ),
-            // Offer::new(
-            //     MemberIdx(0),
-            //     TimeOfDayRange::new(7, 0, 20, 0),
-            //     Deal::new(
-            //         vec![(Entertainment, 5.0), (Money, -10.0)],
-            //         Duration::from_minutes(30),
-            //     ),
-            //     10,
-            //     false
-            // ),
-            // Offer::new(
-            //     MemberIdx(0),
-            //     TimeOfDayRange::new(7, 0, 20, 0),
-            //     Deal::new(
-            //         vec![(Services, 5.0), (Money, -10.0)],
-            //         Duration::from_minutes(30),
-            //     ),
-            //     10,
-            //     false
-            // ),
+            Offer::new(
+                MemberIdx(0),
+                TimeOfDayRange::new(7, 0, 20, 0),
+                Deal::new(
+                    vec![(Entertainment, 5.0), (Money, -10.0)],
+                    Duration::from_minutes(30),
+                ),
+                10,
+                false,
+            ),
+            Offer::new(
+                MemberIdx(0),
+                TimeOfDayRange::new(7, 0, 20, 0),
+                Deal::new(
+                    vec![(Services, 5.0), (Money, -10.0)],
+                    Duration::from_minutes(30),
+                ),
+                10,
+                false,
+            ),
             Offer::new(
                 MemberIdx(0),
                 TimeOfDayRange::new(7, 0, 20, 0),
@@ -130,56 +130,56 @@ impl NeighboringTownTrade {
                 8,
                 false,
             ),
-            /* Offer::new(
-             *     MemberIdx(0),
-             *     TimeOfDayRange::new(7, 0, 20, 0),
-             *     Deal::new(
-             *         vec![(Wood, 30.0), (Money, -10.0)],
-             *         Duration::from_minutes(10),
-             *     ),
-             *     10,
-             *     false
-             * ),
-             * Offer::new(
-             *     MemberIdx(0),
-             *     TimeOfDayRange::new(7, 0, 20, 0),
-             *     Deal::new(
-             *         vec![(Furniture, 5.0), (Money, -100.0)],
-             *         Duration::from_minutes(10),
-             *     ),
-             *     10,
-             *     false
-             * ),
-             * Offer::new(
-             *     MemberIdx(0),
-             *     TimeOfDayRange::new(7, 0, 20, 0),
-             *     Deal::new(
-             *         vec![(TextileGoods, 30.0), (Money, -30.0)],
-             *         Duration::from_minutes(10),
-             *     ),
-             *     10,
-             *     false
-             * ),
-             * Offer::new(
-             *     MemberIdx(0),
-             *     TimeOfDayRange::new(7, 0, 20, 0),
-             *     Deal::new(
-             *         vec![(Clothes, 5.0), (Money, -50.0)],
-             *         Duration::from_minutes(10),
-             *     ),
-             *     10,
-             *     false
-             * ),
-             * Offer::new(
-             *     MemberIdx(0),
-             *     TimeOfDayRange::new(7, 0, 20, 0),
-             *     Deal::new(
-             *         vec![(Devices, 5.0), (Money, -100.0)],
-             *         Duration::from_minutes(10),
-             *     ),
-             *     10,
-             *     false
-             * ), */
+            Offer::new(
+                MemberIdx(0),
+                TimeOfDayRange::new(7, 0, 20, 0),
+                Deal::new(
+                    vec![(Wood, 30.0), (Money, -10.0)],
+                    Duration::from_minutes(10),
+                ),
+                10,
+                false,
+            ),
+            Offer::new(
+                MemberIdx(0),
+                TimeOfDayRange::new(7, 0, 20, 0),
+                Deal::new(
+                    vec![(Furniture, 5.0), (Money, -100.0)],
+                    Duration::from_minutes(10),
+                ),
+                10,
+                false,
+            ),
+            Offer::new(
+                MemberIdx(0),
+                TimeOfDayRange::new(7, 0, 20, 0),
+                Deal::new(
+                    vec![(TextileGoods, 30.0), (Money, -30.0)],
+                    Duration::from_minutes(10),
+                ),
+                10,
+                false,
+            ),
+            Offer::new(
+                MemberIdx(0),
+                TimeOfDayRange::new(7, 0, 20, 0),
+                Deal::new(
+                    vec![(Clothes, 5.0), (Money, -50.0)],
+                    Duration::from_minutes(10),
+                ),
+                10,
+                false,
+            ),
+            Offer::new(
+                MemberIdx(0),
+                TimeOfDayRange::new(7, 0, 20, 0),
+                Deal::new(
+                    vec![(Devices, 5.0), (Money, -100.0)],
+                    Duration::from_minutes(10),
+                ),
+                10,
+                false,
+            ),
         ];
 
         NeighboringTownTrade {
@@ -203,34 +203,51 @@ impl Household for NeighboringTownTrade {
         self.town.into()
     }
 
-    fn is_shared(_: Resource) -> bool {
+    fn is_shared(&self, _: Resource) -> bool {
+        true
+    }
+
+    fn supplier_shared(&self, _: Resource) -> bool {
         true
     }
 
-    fn supplier_shared(_: Resource) -> bool {
+    // the neighboring town has its own workforce, its jobs are just extra work on offer
+    fn is_staffed(&self) -> bool {
         true
     }
 
-    fn importance(_: Resource, _: TimeOfDay) -> f32 {
+    fn open_positions(&self) -> u32 {
+        0
+    }
+
+    fn importance(&self, _: Resource, _: TimeOfDay) -> f32 {
         1.0
     }
 
-    fn interesting_resources() -> &'static [Resource] {
+    fn interesting_resources(&self) -> &[Resource] {
         &[
-            //Entertainment,
-            //Services,
-            Groceries, Produce, Grain, Flour, BakedGoods, Meat,
+            Entertainment,
+            Services,
+            Groceries,
+            Produce,
+            Grain,
+            Flour,
+            BakedGoods,
+            Meat,
             DairyGoods,
-            /* Wood,
-             *Furniture,
-             *TextileGoods,
-             *Clothes,
-             *Devices, */
+            Wood,
+            Furniture,
+            TextileGoods,
+            Clothes,
+            Devices,
         ]
     }
 
     fn decay(&mut self, _dt: Duration, _: &mut World) {}
 
+    // stands in for a whole town's warehouses, so it is never full
+    fn set_storage_capacities(&mut self, _lot_area: f32, _: &mut World) {}
+
     fn household_name(&self) -> String {
         "Neighboring Town".to_owned()
     }
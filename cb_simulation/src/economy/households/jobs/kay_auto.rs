@@ -0,0 +1,214 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct EmploymentStatisticsRequesterID {
+    _raw_id: RawID
+}
+
+impl Copy for EmploymentStatisticsRequesterID {}
+impl Clone for EmploymentStatisticsRequesterID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for EmploymentStatisticsRequesterID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "EmploymentStatisticsRequesterID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for EmploymentStatisticsRequesterID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for EmploymentStatisticsRequesterID {
+    fn eq(&self, other: &EmploymentStatisticsRequesterID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for EmploymentStatisticsRequesterID {}
+
+pub struct EmploymentStatisticsRequesterRepresentative;
+
+impl ActorOrActorTrait for EmploymentStatisticsRequesterRepresentative {
+    type ID = EmploymentStatisticsRequesterID;
+}
+
+impl TypedID for EmploymentStatisticsRequesterID {
+    type Target = EmploymentStatisticsRequesterRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        EmploymentStatisticsRequesterID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + EmploymentStatisticsRequester> TraitIDFrom<Act> for EmploymentStatisticsRequesterID {}
+
+impl EmploymentStatisticsRequesterID {
+    pub fn on_employment_statistics(self, summary: EmploymentSummary, world: &mut World) {
+        world.send(self.as_raw(), MSG_EmploymentStatisticsRequester_on_employment_statistics(summary));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<EmploymentStatisticsRequesterRepresentative>();
+        system.register_trait_message::<MSG_EmploymentStatisticsRequester_on_employment_statistics>();
+    }
+
+    pub fn register_implementor<Act: Actor + EmploymentStatisticsRequester>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, EmploymentStatisticsRequesterRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_EmploymentStatisticsRequester_on_employment_statistics(summary), instance, world| {
+                instance.on_employment_statistics(summary, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_EmploymentStatisticsRequester_on_employment_statistics(pub EmploymentSummary);
+impl Actor for EmploymentStatistics {
+    type ID = EmploymentStatisticsID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct EmploymentStatisticsID {
+    _raw_id: RawID
+}
+
+impl Copy for EmploymentStatisticsID {}
+impl Clone for EmploymentStatisticsID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for EmploymentStatisticsID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "EmploymentStatisticsID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for EmploymentStatisticsID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for EmploymentStatisticsID {
+    fn eq(&self, other: &EmploymentStatisticsID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for EmploymentStatisticsID {}
+
+impl TypedID for EmploymentStatisticsID {
+    type Target = EmploymentStatistics;
+
+    fn from_raw(id: RawID) -> Self {
+        EmploymentStatisticsID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl EmploymentStatisticsID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = EmploymentStatisticsID::from_raw(world.allocate_instance_id::<EmploymentStatistics>());
+        let swarm = world.local_broadcast::<EmploymentStatistics>();
+        world.send(swarm, MSG_EmploymentStatistics_spawn(id, ));
+        id
+    }
+    
+    pub fn on_joined_labor_force(self, n_members: u32, world: &mut World) {
+        world.send(self.as_raw(), MSG_EmploymentStatistics_on_joined_labor_force(n_members));
+    }
+    
+    pub fn on_left_labor_force(self, n_members: u32, world: &mut World) {
+        world.send(self.as_raw(), MSG_EmploymentStatistics_on_left_labor_force(n_members));
+    }
+    
+    pub fn on_job_taken(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_EmploymentStatistics_on_job_taken());
+    }
+    
+    pub fn on_job_lost(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_EmploymentStatistics_on_job_lost());
+    }
+    
+    pub fn on_open_positions(self, employer: HouseholdID, n_positions: u32, world: &mut World) {
+        world.send(self.as_raw(), MSG_EmploymentStatistics_on_open_positions(employer, n_positions));
+    }
+    
+    pub fn get_employment_statistics(self, requester: EmploymentStatisticsRequesterID, world: &mut World) {
+        world.send(self.as_raw(), MSG_EmploymentStatistics_get_employment_statistics(requester));
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_EmploymentStatistics_spawn(pub EmploymentStatisticsID, );
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_EmploymentStatistics_on_joined_labor_force(pub u32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_EmploymentStatistics_on_left_labor_force(pub u32);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_EmploymentStatistics_on_job_taken();
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_EmploymentStatistics_on_job_lost();
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_EmploymentStatistics_on_open_positions(pub HouseholdID, pub u32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_EmploymentStatistics_get_employment_statistics(pub EmploymentStatisticsRequesterID);
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    EmploymentStatisticsRequesterID::register_trait(system);
+    system.add_spawner::<EmploymentStatistics, _, _>(
+        |&MSG_EmploymentStatistics_spawn(id, ), world| {
+            EmploymentStatistics::spawn(id, world)
+        }, false
+    );
+    
+    system.add_handler::<EmploymentStatistics, _, _>(
+        |&MSG_EmploymentStatistics_on_joined_labor_force(n_members), instance, world| {
+            instance.on_joined_labor_force(n_members, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<EmploymentStatistics, _, _>(
+        |&MSG_EmploymentStatistics_on_left_labor_force(n_members), instance, world| {
+            instance.on_left_labor_force(n_members, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<EmploymentStatistics, _, _>(
+        |&MSG_EmploymentStatistics_on_job_taken(), instance, world| {
+            instance.on_job_taken(world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<EmploymentStatistics, _, _>(
+        |&MSG_EmploymentStatistics_on_job_lost(), instance, world| {
+            instance.on_job_lost(world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<EmploymentStatistics, _, _>(
+        |&MSG_EmploymentStatistics_on_open_positions(employer, n_positions), instance, world| {
+            instance.on_open_positions(employer, n_positions, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<EmploymentStatistics, _, _>(
+        |&MSG_EmploymentStatistics_get_employment_statistics(requester), instance, world| {
+            instance.get_employment_statistics(requester, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,146 @@
+use kay::{ActorSystem, World};
+use compact::CHashMap;
+use cb_time::units::{Instant, TimeOfDayRange, Duration, Ticks};
+use economy::market::EvaluatedDeal;
+use economy::resources::Resource;
+use super::{HouseholdID, OfferID};
+
+// a member's persistent employment at one of a business' work offers
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct Job {
+    pub offer: OfferID,
+    pub shift: TimeOfDayRange,
+    pub wage: f32,
+    pub last_shift_start: Option<Instant>,
+}
+
+impl Job {
+    // deals that only pay Money are wages for working a shift
+    pub fn from_evaluated_deal(evaluated: &EvaluatedDeal) -> Option<Job> {
+        let wage = evaluated
+            .deal
+            .delta
+            .get(Resource::Money)
+            .cloned()
+            .unwrap_or(0.0);
+        if evaluated.deal.delta.len() == 1 && wage > 0.0 {
+            Some(Job {
+                offer: evaluated.offer,
+                shift: evaluated.opening_hours,
+                wage,
+                last_shift_start: None,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn shift_due(&self, instant: Instant) -> bool {
+        self.shift.contains(instant)
+            && self.last_shift_start.map_or(true, |last_shift_start| {
+                instant.ticks() - last_shift_start.ticks()
+                    > Ticks::from(MIN_TIME_BETWEEN_SHIFTS).0 as usize
+            })
+    }
+}
+
+// keeps the employment statistics in sync with a member's job slot
+pub fn switch_job(job: &mut Option<Job>, new_job: Option<Job>, world: &mut World) {
+    match (job.is_some(), new_job.is_some()) {
+        (false, true) => EmploymentStatisticsID::local_first(world).on_job_taken(world),
+        (true, false) => EmploymentStatisticsID::local_first(world).on_job_lost(world),
+        _ => {}
+    }
+    *job = new_job;
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct EmploymentSummary {
+    pub labor_force: u32,
+    pub employed: u32,
+    pub unemployment_rate: f32,
+    pub vacancies: u32,
+}
+
+pub trait EmploymentStatisticsRequester {
+    fn on_employment_statistics(&mut self, summary: EmploymentSummary, world: &mut World);
+}
+
+// Counts members looking for or holding a job, which gives the unemployment rate
+#[derive(Compact, Clone)]
+pub struct EmploymentStatistics {
+    id: EmploymentStatisticsID,
+    labor_force: u32,
+    employed: u32,
+    open_positions: CHashMap<HouseholdID, u32>,
+}
+
+impl EmploymentStatistics {
+    pub fn spawn(id: EmploymentStatisticsID, _: &mut World) -> EmploymentStatistics {
+        EmploymentStatistics {
+            id,
+            labor_force: 0,
+            employed: 0,
+            open_positions: CHashMap::new(),
+        }
+    }
+
+    pub fn on_joined_labor_force(&mut self, n_members: u32, _: &mut World) {
+        self.labor_force += n_members;
+    }
+
+    pub fn on_left_labor_force(&mut self, n_members: u32, _: &mut World) {
+        self.labor_force = self.labor_force.saturating_sub(n_members);
+    }
+
+    pub fn on_job_taken(&mut self, _: &mut World) {
+        self.employed += 1;
+    }
+
+    pub fn on_job_lost(&mut self, _: &mut World) {
+        self.employed = self.employed.saturating_sub(1);
+    }
+
+    pub fn on_open_positions(&mut self, employer: HouseholdID, n_positions: u32, _: &mut World) {
+        if n_positions == 0 {
+            self.open_positions.remove(employer);
+        } else {
+            self.open_positions.insert(employer, n_positions);
+        }
+    }
+
+    pub fn get_employment_statistics(
+        &mut self,
+        requester: EmploymentStatisticsRequesterID,
+        world: &mut World,
+    ) {
+        let unemployed = self.labor_force.saturating_sub(self.employed);
+        requester.on_employment_statistics(
+            EmploymentSummary {
+                labor_force: self.labor_force,
+                employed: self.employed,
+                unemployment_rate: if self.labor_force > 0 {
+                    unemployed as f32 / self.labor_force as f32
+                } else {
+                    0.0
+                },
+                vacancies: self.open_positions.pairs().map(|(_, n)| *n).sum(),
+            },
+            world,
+        );
+    }
+}
+
+const MIN_TIME_BETWEEN_SHIFTS: Duration = Duration(12 * 60 * 60);
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<EmploymentStatistics>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) {
+    EmploymentStatisticsID::spawn(world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -126,16 +126,48 @@ impl HouseholdID {
         world.send(self.as_raw(), MSG_Household_on_tick(current_instant));
     }
     
+    pub fn adjust_prices(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_adjust_prices());
+    }
+    
+    pub fn set_upkeep(self, rent: f32, operating_costs: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_set_upkeep(rent, operating_costs));
+    }
+    
+    pub fn set_home_area(self, area: Area, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_set_home_area(area));
+    }
+    
+    pub fn set_storage_capacities(self, lot_area: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_set_storage_capacities(lot_area));
+    }
+    
+    pub fn pay_upkeep(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_pay_upkeep());
+    }
+    
+    pub fn update_demographics(self, instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_update_demographics(instant));
+    }
+    
     pub fn evaluate(self, offer_idx: OfferIdx, instant: Instant, location: RoughLocationID, requester: EvaluationRequesterID, world: &mut World) {
         world.send(self.as_raw(), MSG_Household_evaluate(offer_idx, instant, location, requester));
     }
     
-    pub fn request_receive_deal(self, offer_idx: OfferIdx, requester: HouseholdID, requester_member: MemberIdx, world: &mut World) {
-        world.send(self.as_raw(), MSG_Household_request_receive_deal(offer_idx, requester, requester_member));
+    pub fn request_receive_deal(self, offer_idx: OfferIdx, requester: HouseholdID, requester_member: MemberIdx, deal: Deal, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_request_receive_deal(offer_idx, requester, requester_member, deal));
+    }
+    
+    pub fn request_receive_undo_deal(self, offer_idx: OfferIdx, deal: Deal, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_request_receive_undo_deal(offer_idx, deal));
     }
     
-    pub fn request_receive_undo_deal(self, offer_idx: OfferIdx, requester: HouseholdID, requester_member: MemberIdx, world: &mut World) {
-        world.send(self.as_raw(), MSG_Household_request_receive_undo_deal(offer_idx, requester, requester_member));
+    pub fn request_freight(self, offer_idx: OfferIdx, requester: HouseholdID, requester_member: MemberIdx, deal: Deal, instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_request_freight(offer_idx, requester, requester_member, deal, instant));
+    }
+    
+    pub fn freight_failed(self, offer_idx: OfferIdx, cargo: Deal, paid: Deal, world: &mut World) {
+        world.send(self.as_raw(), MSG_Household_freight_failed(offer_idx, cargo, paid));
     }
     
     pub fn started_using(self, offer_idx: OfferIdx, user: HouseholdID, using_member: Option < MemberIdx >, world: &mut World) {
@@ -183,9 +215,17 @@ impl HouseholdID {
         system.register_trait_message::<MSG_Household_start_task>();
         system.register_trait_message::<MSG_Household_stop_task>();
         system.register_trait_message::<MSG_Household_on_tick>();
+        system.register_trait_message::<MSG_Household_adjust_prices>();
+        system.register_trait_message::<MSG_Household_set_upkeep>();
+        system.register_trait_message::<MSG_Household_set_home_area>();
+        system.register_trait_message::<MSG_Household_set_storage_capacities>();
+        system.register_trait_message::<MSG_Household_pay_upkeep>();
+        system.register_trait_message::<MSG_Household_update_demographics>();
         system.register_trait_message::<MSG_Household_evaluate>();
         system.register_trait_message::<MSG_Household_request_receive_deal>();
         system.register_trait_message::<MSG_Household_request_receive_undo_deal>();
+        system.register_trait_message::<MSG_Household_request_freight>();
+        system.register_trait_message::<MSG_Household_freight_failed>();
         system.register_trait_message::<MSG_Household_started_using>();
         system.register_trait_message::<MSG_Household_stopped_using>();
         system.register_trait_message::<MSG_Household_started_actively_using>();
@@ -310,6 +350,42 @@ impl HouseholdID {
             }, false
         );
         
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_adjust_prices(), instance, world| {
+                instance.adjust_prices(world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_set_upkeep(rent, operating_costs), instance, world| {
+                instance.set_upkeep(rent, operating_costs, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_set_home_area(area), instance, world| {
+                instance.set_home_area(area, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_set_storage_capacities(lot_area), instance, world| {
+                instance.set_storage_capacities(lot_area, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_pay_upkeep(), instance, world| {
+                instance.pay_upkeep(world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_update_demographics(instant), instance, world| {
+                instance.update_demographics(instant, world); Fate::Live
+            }, false
+        );
+        
         system.add_handler::<Act, _, _>(
             |&MSG_Household_evaluate(offer_idx, instant, location, requester), instance, world| {
                 instance.evaluate(offer_idx, instant, location, requester, world); Fate::Live
@@ -317,14 +393,26 @@ impl HouseholdID {
         );
         
         system.add_handler::<Act, _, _>(
-            |&MSG_Household_request_receive_deal(offer_idx, requester, requester_member), instance, world| {
-                instance.request_receive_deal(offer_idx, requester, requester_member, world); Fate::Live
+            |&MSG_Household_request_receive_deal(offer_idx, requester, requester_member, ref deal), instance, world| {
+                instance.request_receive_deal(offer_idx, requester, requester_member, deal, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_request_receive_undo_deal(offer_idx, ref deal), instance, world| {
+                instance.request_receive_undo_deal(offer_idx, deal, world); Fate::Live
             }, false
         );
         
         system.add_handler::<Act, _, _>(
-            |&MSG_Household_request_receive_undo_deal(offer_idx, requester, requester_member), instance, world| {
-                instance.request_receive_undo_deal(offer_idx, requester, requester_member, world); Fate::Live
+            |&MSG_Household_request_freight(offer_idx, requester, requester_member, ref deal, instant), instance, world| {
+                instance.request_freight(offer_idx, requester, requester_member, deal, instant, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_Household_freight_failed(offer_idx, ref cargo, ref paid), instance, world| {
+                instance.freight_failed(offer_idx, cargo, paid, world); Fate::Live
             }, false
         );
         
@@ -404,12 +492,28 @@ struct MSG_Household_start_task(pub MemberIdx, pub Instant, pub RoughLocationID)
 struct MSG_Household_stop_task(pub MemberIdx, pub Option < RoughLocationID >);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_on_tick(pub Instant);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_adjust_prices();
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_set_upkeep(pub f32, pub f32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_set_home_area(pub Area);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_set_storage_capacities(pub f32);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_pay_upkeep();
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_update_demographics(pub Instant);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_evaluate(pub OfferIdx, pub Instant, pub RoughLocationID, pub EvaluationRequesterID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Household_request_receive_deal(pub OfferIdx, pub HouseholdID, pub MemberIdx);
+struct MSG_Household_request_receive_deal(pub OfferIdx, pub HouseholdID, pub MemberIdx, pub Deal);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_request_receive_undo_deal(pub OfferIdx, pub Deal);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Household_request_freight(pub OfferIdx, pub HouseholdID, pub MemberIdx, pub Deal, pub Instant);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Household_request_receive_undo_deal(pub OfferIdx, pub HouseholdID, pub MemberIdx);
+struct MSG_Household_freight_failed(pub OfferIdx, pub Deal, pub Deal);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Household_started_using(pub OfferIdx, pub HouseholdID, pub Option < MemberIdx >);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
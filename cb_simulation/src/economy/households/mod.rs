@@ -1,7 +1,8 @@
 use kay::{ActorSystem, World, Actor, TypedID, Fate};
 use compact::{CVec, CDict, COption};
 use cb_time::actors::{TimeID, Sleeper, Temporal};
-use cb_time::units::{Duration, TimeOfDay, Instant, Ticks, TICKS_PER_SIM_SECOND};
+use cb_time::units::{Duration, TimeOfDay, Instant, Ticks, TICKS_PER_SIM_SECOND,
+TICKS_PER_SIM_MINUTE};
 use cb_util::async_counter::AsyncCounter;
 use cb_util::random::{seed, Rng};
 use ordered_float::OrderedFloat;
@@ -10,6 +11,10 @@ const LOG_T: &str = "Households";
 
 pub mod tasks;
 pub mod offers;
+pub mod jobs;
+pub mod satisfaction;
+pub mod demographics;
+pub mod storage;
 pub mod ui;
 
 pub mod household_kinds;
@@ -30,15 +35,40 @@ impl MemberIdx {
 
 use super::market::{MarketID, Deal, EvaluatedDeal, EvaluationRequester, EvaluationRequesterID,
 TripCostEstimatorID, EvaluatedSearchResult};
+use super::freight::FreightDeliveryID;
+use super::treasury::CityTreasuryID;
+use super::ledger::LedgerID;
 use super::resources::{Resource, ResourceAmount, ResourceMap, Entry, Inventory};
 use transport::pathfinding::{RoughLocationID, RoughLocation};
 use transport::pathfinding::trip::{TripListener, TripID, TripResult, TripFate};
-use self::tasks::{Task, TaskState, TaskEndSchedulerID};
+use transport::microtraffic::VehicleType;
+use self::tasks::{Task, TaskGoal, TaskState, TaskEndSchedulerID};
 pub use self::offers::{Offer, OfferIdx, OfferID};
+use self::jobs::{Job, switch_job, EmploymentStatisticsID};
+use self::satisfaction::{Area, SatisfactionMapID};
+use self::demographics::{Demographics, Role};
+use self::storage::{storage_capacities_for, spoil_and_discard_excess};
+use super::immigration_and_development::ImmigrationManagerID;
 
 const N_TOP_PROBLEMS: usize = 5;
 const DECISION_PAUSE: Ticks = Ticks(200);
 const UPDATE_EVERY_N_SECS: u32 = 4;
+const ADJUST_PRICES_EVERY_N_MINUTES: u32 = 60;
+const PAY_UPKEEP_EVERY_N_MINUTES: u32 = 24 * 60;
+const INITIAL_SAVINGS: f32 = 200.0;
+const MAX_DEBT: f32 = 500.0;
+const MAX_INSOLVENT_DAYS: u32 = 3;
+const UPDATE_SATISFACTION_EVERY_N_MINUTES: u32 = 60;
+// hourly samples, so this averages over about a day
+const SATISFACTION_SMOOTHING: f32 = 1.0 / 24.0;
+// total graveness of a member's top problems at which they are only half satisfied
+const HALF_SATISFIED_GRAVENESS: f32 = 20.0;
+const INITIAL_SATISFACTION: f32 = 0.75;
+const SATISFACTION_TO_EMIGRATE: f32 = 0.3;
+const MIN_CYCLING_DISTANCE: f32 = 500.0;
+const MAX_CYCLING_DISTANCE: f32 = 5000.0;
+const CYCLING_SHARE: f32 = 0.3;
+const COMMUTE_URGENCY: f32 = 10.0;
 
 // TODO: make kay_codegen figure this out on it's own
 impl Into<RoughLocationID> for HouseholdID {
@@ -54,28 +84,30 @@ pub trait Household:
     fn core_mut(&mut self) -> &mut HouseholdCore;
     fn site(&self) -> RoughLocationID;
 
-    fn is_shared(resource: Resource) -> bool;
-    fn supplier_shared(resource: Resource) -> bool;
-    fn importance(resource: Resource, time: TimeOfDay) -> f32;
-    fn graveness(resource: Resource, amount: ResourceAmount, time: TimeOfDay) -> f32 {
-        -amount * Self::importance(resource, time)
+    fn is_shared(&self, resource: Resource) -> bool;
+    fn supplier_shared(&self, resource: Resource) -> bool;
+    fn importance(&self, resource: Resource, time: TimeOfDay) -> f32;
+    fn graveness(&self, resource: Resource, amount: ResourceAmount, time: TimeOfDay) -> f32 {
+        -amount * self.importance(resource, time)
     }
-    fn interesting_resources() -> &'static [Resource];
+    fn interesting_resources(&self) -> &[Resource];
     fn decay(&mut self, dt: Duration, world: &mut World);
 
     fn household_name(&self) -> String;
     fn member_name(&self, member: MemberIdx) -> String;
 
     fn receive_deal(&mut self, deal: &Deal, member: MemberIdx, _: &mut World) {
+        let shared_resources = self.shared_resources_of(deal);
         let core = self.core_mut();
         deal.delta.give_to_shared_private(
             &mut core.resources,
             &mut core.member_resources[member.as_idx()],
-            Self::is_shared,
+            |resource| shared_resources.contains(&resource),
         );
     }
 
     fn provide_deal(&mut self, deal: &Deal, member: MemberIdx, _: &mut World) {
+        let shared_resources = self.shared_resources_of(deal);
         let core = self.core_mut();
         let provide_wakefulness =
             deal.delta.len() == 1 && deal.delta.get(Resource::Wakefulness).is_some();
@@ -83,11 +115,19 @@ pub trait Household:
             deal.delta.take_from_shared_private(
                 &mut core.resources,
                 &mut core.member_resources[member.as_idx()],
-                Self::is_shared,
+                |resource| shared_resources.contains(&resource),
             );
         }
     }
 
+    fn shared_resources_of(&self, deal: &Deal) -> Vec<Resource> {
+        deal.delta
+            .iter()
+            .map(|&Entry(resource, _)| resource)
+            .filter(|&resource| self.is_shared(resource))
+            .collect()
+    }
+
     fn task_succeeded(&mut self, member: MemberIdx, world: &mut World) {
         {
             debug(
@@ -154,16 +194,21 @@ pub trait Household:
             }
         }
 
+        for job in self.core_mut().member_jobs.iter_mut() {
+            if job.map(|job| job.offer == offer).unwrap_or(false) {
+                switch_job(job, None, world);
+            }
+        }
+
         let members_to_reset = self
             .core()
             .member_tasks
             .iter()
             .enumerate()
             .filter_map(|(i, task)| {
-                if let Task {
-                    goal: Some((_, task_offer)),
-                    ..
-                } = *task
+                if let Some(TaskGoal {
+                    offer: task_offer, ..
+                }) = task.goal
                 {
                     if task_offer == offer {
                         Some(MemberIdx::new(i))
@@ -201,6 +246,26 @@ pub trait Household:
             }
         }
 
+        for job in self.core_mut().member_jobs.iter_mut() {
+            switch_job(job, None, world);
+        }
+
+        for task in self.core().member_tasks.iter() {
+            if let Some(ticket) = task.parked_car {
+                ticket.give_back(world);
+            }
+        }
+
+        if self.core().provided_offers.iter().any(Offer::is_job) {
+            EmploymentStatisticsID::local_first(world).on_open_positions(self.id_as(), 0, world);
+        }
+
+        if self.core().home_area.is_some() {
+            SatisfactionMapID::local_first(world).on_household_gone(self.id_as(), world);
+        }
+
+        LedgerID::global_first(world).on_household_gone(self.id_as(), world);
+
         for (idx, offer) in self.core().provided_offers.iter().enumerate() {
             MarketID::local_first(world).withdraw(
                 offer.deal.main_given(),
@@ -223,6 +288,7 @@ pub trait Household:
                 .member_tasks
                 .iter()
                 .enumerate()
+                .filter(|&(idx, _)| self.core().member_demographics[idx].is_present())
                 .filter_map(|(idx, m)| match m.state {
                     TaskState::IdleAt(loc) => Some((idx, loc)),
                     _ => None,
@@ -242,13 +308,20 @@ pub trait Household:
     }
 
     fn top_problems(&self, member: MemberIdx, time: TimeOfDay) -> Vec<(Resource, f32)> {
+        let role = self.core().member_demographics[member.as_idx()].role;
+        if role == Role::Departed {
+            return vec![];
+        }
+
         let mut resource_graveness = self
             .core()
             .resources
             .iter()
             .chain(self.core().member_resources[member.as_idx()].iter())
+            // only workers earn money
+            .filter(|&&Entry(resource, _)| resource != Resource::Money || role == Role::Worker)
             .filter_map(|&Entry(resource, amount)| {
-                let graveness = Self::graveness(resource, amount, time);
+                let graveness = self.graveness(resource, amount, time);
                 if graveness > 0.1 {
                     Some((resource, graveness))
                 } else {
@@ -272,7 +345,11 @@ pub trait Household:
         debug(LOG_T, "Top N Problems", self.id(), world);
 
         let time = TimeOfDay::from(instant);
-        let top_problems = self.top_problems(member, time);
+        // going to work takes precedence once a shift is due
+        let top_problems = match self.core().member_jobs[member.as_idx()] {
+            Some(job) if job.shift_due(instant) => vec![(Resource::Money, COMMUTE_URGENCY)],
+            _ => self.top_problems(member, time),
+        };
 
         if top_problems.is_empty() {
             TimeID::local_first(world).wake_up_in(DECISION_PAUSE, self.id_as(), world);
@@ -280,16 +357,22 @@ pub trait Household:
             let mut decision_entries = CDict::<Resource, DecisionResourceEntry>::new();
             let id_as_eval_requester = self.id_as();
             let log_as = self.id();
+            let suppliers_shared = top_problems
+                .iter()
+                .map(|&(resource, _)| self.supplier_shared(resource))
+                .collect::<Vec<_>>();
             let core = self.core_mut();
 
-            for &(resource, graveness) in &top_problems {
+            for (&(resource, graveness), &supplier_shared) in
+                top_problems.iter().zip(suppliers_shared.iter())
+            {
                 debug(
                     LOG_T,
                     format!("Member #{}: {} = {}", member.as_idx(), resource, graveness),
                     log_as,
                     world,
                 );
-                let maybe_offer = if Self::supplier_shared(resource) {
+                let maybe_offer = if supplier_shared {
                     core.used_offers.get(resource)
                 } else {
                     core.member_used_offers[member.as_idx()].get(resource)
@@ -465,6 +548,13 @@ pub trait Household:
             let core = self.core_mut();
 
             if let DecisionState::Choosing(member, instant, _, ref entries) = core.decision_state {
+                // nothing at all on offer for these resources
+                for (resource, entry) in entries.pairs() {
+                    if entry.best_deal.is_none() {
+                        ImmigrationManagerID::local_first(world).on_unmet_demand(*resource, world);
+                    }
+                }
+
                 let maybe_best = most_useful_evaluated_deal(entries);
 
                 if let Some(best) = maybe_best {
@@ -472,9 +562,18 @@ pub trait Household:
 
                     *task = if let TaskState::IdleAt(location) = task.state {
                         Task {
-                            goal: Some((best.deal.main_given(), best.offer)),
+                            goal: Some(TaskGoal {
+                                resource: best.deal.main_given(),
+                                offer: best.offer,
+                                deal: best.deal.clone(),
+                            }),
                             duration: best.deal.duration,
                             state: TaskState::GettingReadyAt(location),
+                            vehicle_type: choose_vehicle_type(
+                                best.travel_distance,
+                                &mut seed((instant.ticks(), log_as, member.as_idx())),
+                            ),
+                            parked_car: task.parked_car,
                         }
                     } else {
                         panic!("Member who gets new task should be idle");
@@ -498,7 +597,7 @@ pub trait Household:
 
         if let Some((member, instant, best)) = maybe_best_info {
             {
-                let (used_offers, maybe_member) = if Self::supplier_shared(best.deal.main_given()) {
+                let (used_offers, maybe_member) = if self.supplier_shared(best.deal.main_given()) {
                     (&mut self.core_mut().used_offers, None)
                 } else {
                     (
@@ -525,11 +624,20 @@ pub trait Household:
                 );
             }
 
+            if !self.supplier_shared(best.deal.main_given()) {
+                let job = &mut self.core_mut().member_jobs[member.as_idx()];
+                let keeps_job = job.map(|job| job.offer == best.offer).unwrap_or(false);
+                if best.deal.main_given() == Resource::Money && !keeps_job {
+                    switch_job(job, Job::from_evaluated_deal(&best), world);
+                }
+            }
+
             self.core_mut().decision_state = DecisionState::WaitingForTrip(member);
             best.offer.household.request_receive_deal(
                 best.offer.idx,
                 id_as_household,
                 member,
+                best.deal.clone(),
                 world,
             );
             self.start_trip(member, instant, world);
@@ -556,15 +664,24 @@ pub trait Household:
 
     fn start_trip(&mut self, member: MemberIdx, instant: Instant, world: &mut World) {
         if let Task {
-            goal: Some((_, offer)),
+            goal: Some(TaskGoal { offer, .. }),
             state: TaskState::GettingReadyAt(source),
+            vehicle_type,
             ..
         } = self.core().member_tasks[member.as_idx()]
         {
+            if vehicle_type != VehicleType::Bicycle {
+                let task = &mut self.core_mut().member_tasks[member.as_idx()];
+                if let Some(ticket) = task.parked_car.take() {
+                    ticket.give_back(world);
+                }
+            }
+
             TripID::spawn(
                 source,
                 offer.household.into(),
                 Some(self.id_as()),
+                vehicle_type,
                 instant,
                 world,
             );
@@ -600,8 +717,8 @@ pub trait Household:
             .filter_map(|(idx, task)| {
                 if let TaskState::InTrip(task_trip_id) = task.state {
                     if task_trip_id == trip {
-                        if let Some((goal, offer)) = task.goal {
-                            Some((MemberIdx::new(idx), goal, offer))
+                        if let Some(ref goal) = task.goal {
+                            Some((MemberIdx::new(idx), goal.resource, goal.offer))
                         } else {
                             None
                         }
@@ -617,7 +734,7 @@ pub trait Household:
         {
             let id_as_household = self.id_as();
 
-            let (used_offers, maybe_member) = if Self::supplier_shared(matching_resource) {
+            let (used_offers, maybe_member) = if self.supplier_shared(matching_resource) {
                 (&mut self.core_mut().used_offers, None)
             } else {
                 (
@@ -640,9 +757,39 @@ pub trait Household:
             }
         }
 
+        match result.fate {
+            TripFate::Success(_) => {}
+            _ => {
+                // not showing up for a shift loses the job
+                let job = &mut self.core_mut().member_jobs[matching_task_member.as_idx()];
+                if job.map(|job| job.offer == matching_offer).unwrap_or(false) {
+                    switch_job(job, None, world);
+                }
+            }
+        }
+
+        if let Some(ticket) = result.parked_at {
+            let task = &mut self.core_mut().member_tasks[matching_task_member.as_idx()];
+            // a planned bike trip might have been driven, leaving the old car behind
+            if let Some(old_ticket) = ::std::mem::replace(&mut task.parked_car, Some(ticket)) {
+                old_ticket.give_back(world);
+            }
+        }
+
         match result.fate {
             TripFate::Success(instant) => {
                 self.start_task(matching_task_member, instant, rough_destination, world);
+                if let Some(ref goal) = self.core().member_tasks[matching_task_member.as_idx()].goal
+                {
+                    matching_offer.household.request_freight(
+                        matching_offer.idx,
+                        self.id_as(),
+                        matching_task_member,
+                        goal.deal.clone(),
+                        instant,
+                        world,
+                    );
+                }
             }
             fate => {
                 info(
@@ -658,15 +805,23 @@ pub trait Household:
                     world,
                 );
 
-                if let Some((_, offer)) =
-                    self.core().member_tasks[matching_task_member.as_idx()].goal
-                {
-                    offer.household.request_receive_undo_deal(
-                        offer.idx,
+                let maybe_goal = self.core().member_tasks[matching_task_member.as_idx()]
+                    .goal
+                    .clone();
+                if let Some(goal) = maybe_goal {
+                    // refund here, the offering household might not exist anymore
+                    let paid = goal.deal.without_freight();
+                    LedgerID::global_first(world).record(
+                        goal.offer.household,
                         self.id_as(),
-                        matching_task_member,
+                        paid.reversed().delta,
+                        goal.offer.household.into(),
                         world,
                     );
+                    self.provide_deal(&paid, matching_task_member, world);
+                    goal.offer
+                        .household
+                        .request_receive_undo_deal(goal.offer.idx, paid, world);
                 }
                 self.stop_task(matching_task_member, result.location_now, world);
             }
@@ -692,10 +847,16 @@ pub trait Household:
             member,
             world,
         );
-        if let Some((_, offer)) = self.core().member_tasks[member.as_idx()].goal {
+        if let Some(TaskGoal { offer, .. }) = self.core().member_tasks[member.as_idx()].goal {
             offer
                 .household
                 .started_actively_using(offer.idx, self.id_as(), member, world);
+
+            if let Some(ref mut job) = self.core_mut().member_jobs[member.as_idx()] {
+                if job.offer == offer {
+                    job.last_shift_start = Some(start);
+                }
+            }
         }
         self.core_mut().member_tasks[member.as_idx()].state = TaskState::StartedAt(start, location);
     }
@@ -713,6 +874,7 @@ pub trait Household:
                 TripResult {
                     location_now: None,
                     fate: TripFate::ForceStopped,
+                    parked_at: None,
                 },
                 world,
             )
@@ -732,7 +894,7 @@ pub trait Household:
             self.core_mut().member_tasks[member.as_idx()].state =
                 TaskState::IdleAt(location.unwrap_or_else(|| self.site()));
 
-            if let Some((_, offer)) = self.core().member_tasks[member.as_idx()].goal {
+            if let Some(TaskGoal { offer, .. }) = self.core().member_tasks[member.as_idx()].goal {
                 offer
                     .household
                     .stopped_actively_using(offer.idx, self.id_as(), member, world);
@@ -747,10 +909,204 @@ pub trait Household:
             % (UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND) as usize
             == 0
         {
-            self.decay(Duration(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND), world);
+            let dt = Duration(UPDATE_EVERY_N_SECS * TICKS_PER_SIM_SECOND);
+
+            if self.is_staffed() {
+                self.decay(dt, world);
+            }
+
+            // goods also go bad while nobody is there
+            let core = self.core_mut();
+            spoil_and_discard_excess(&mut core.resources, &core.storage_capacities, dt);
+        }
+
+        if (current_instant.ticks() + self.id().as_raw().instance_id as usize)
+            % (ADJUST_PRICES_EVERY_N_MINUTES * TICKS_PER_SIM_MINUTE) as usize
+            == 0
+        {
+            self.adjust_prices(world);
+        }
+
+        if (current_instant.ticks() + self.id().as_raw().instance_id as usize)
+            % (UPDATE_SATISFACTION_EVERY_N_MINUTES * TICKS_PER_SIM_MINUTE) as usize
+            == 0
+            && !self.core().being_destroyed
+        {
+            self.update_satisfaction(TimeOfDay::from(current_instant));
+
+            if let Some(area) = self.core().home_area {
+                SatisfactionMapID::local_first(world).on_satisfaction(
+                    self.id_as(),
+                    area,
+                    self.core().satisfaction,
+                    world,
+                );
+            }
+
+            if self.core().provided_offers.iter().any(Offer::is_job) {
+                EmploymentStatisticsID::local_first(world).on_open_positions(
+                    self.id_as(),
+                    self.open_positions(),
+                    world,
+                );
+            }
+        }
+
+        if (current_instant.ticks() + self.id().as_raw().instance_id as usize)
+            % (PAY_UPKEEP_EVERY_N_MINUTES * TICKS_PER_SIM_MINUTE) as usize
+            == 0
+            && !self.core().being_destroyed
+        {
+            self.pay_upkeep(world);
+
+            if !self.core().being_destroyed {
+                self.update_demographics(current_instant, world);
+            }
         }
     }
 
+    fn adjust_prices(&mut self, world: &mut World) {
+        let id_as_household = self.id_as();
+        let core = self.core_mut();
+
+        for (idx, offer) in core.provided_offers.iter_mut().enumerate() {
+            if offer.is_priced() {
+                let resource = offer.deal.main_given();
+                let stock = core.resources.get(resource).cloned().unwrap_or(0.0);
+                offer.adjust_price(stock);
+
+                if !offer.is_internal {
+                    MarketID::local_first(world).report_price(
+                        resource,
+                        OfferID {
+                            household: id_as_household,
+                            idx: OfferIdx(idx as u16),
+                        },
+                        offer.unit_price(),
+                        offer.base_unit_price(),
+                        world,
+                    );
+                }
+            }
+        }
+    }
+
+    fn set_upkeep(&mut self, rent: f32, operating_costs: f32, _: &mut World) {
+        let core = self.core_mut();
+        core.rent = rent;
+        core.operating_costs = operating_costs;
+    }
+
+    // only residents are part of the happiness map
+    fn set_home_area(&mut self, area: Area, _: &mut World) {
+        if self.is_residential() {
+            self.core_mut().home_area = Some(area);
+        }
+    }
+
+    fn set_storage_capacities(&mut self, lot_area: f32, _: &mut World) {
+        self.core_mut().storage_capacities = storage_capacities_for(lot_area);
+    }
+
+    // households deep in debt for too long can't afford to stay and move out
+    fn pay_upkeep(&mut self, world: &mut World) {
+        let (rent, insolvent_days) = {
+            let core = self.core_mut();
+            let upkeep = core.rent + core.operating_costs;
+            let money = {
+                let money = core.resources.mut_entry_or(Resource::Money, 0.0);
+                *money -= upkeep;
+                *money
+            };
+
+            if money < -MAX_DEBT {
+                core.insolvent_days += 1;
+            } else {
+                core.insolvent_days = 0;
+            }
+
+            (core.rent, core.insolvent_days)
+        };
+
+        if rent > 0.0 {
+            CityTreasuryID::local_first(world).receive_rent(rent, world);
+        }
+
+        if insolvent_days >= MAX_INSOLVENT_DAYS {
+            info(
+                LOG_T,
+                format!("Insolvent for {} days, moving out", insolvent_days),
+                self.id(),
+                world,
+            );
+            self.destroy(world);
+        } else if self.is_residential() && self.core().satisfaction < SATISFACTION_TO_EMIGRATE {
+            info(
+                LOG_T,
+                format!("Unsatisfied ({}), emigrating", self.core().satisfaction),
+                self.id(),
+                world,
+            );
+            ImmigrationManagerID::local_first(world).on_emigration(world);
+            self.destroy(world);
+        }
+    }
+
+    fn update_demographics(&mut self, _: Instant, _: &mut World) {}
+
+    fn is_residential(&self) -> bool {
+        false
+    }
+
+    // from 1.0 without any problems towards 0.0 the graver the members' top problems get
+    fn update_satisfaction(&mut self, time: TimeOfDay) {
+        let present_members = (0..self.core().member_tasks.len())
+            .filter(|&member| self.core().member_demographics[member].is_present())
+            .collect::<Vec<_>>();
+        if present_members.is_empty() {
+            return;
+        }
+
+        let samples = present_members
+            .iter()
+            .map(|&member| {
+                let total_graveness: f32 = self
+                    .top_problems(MemberIdx::new(member), time)
+                    .into_iter()
+                    .map(|(_, graveness)| graveness)
+                    .sum();
+                1.0 / (1.0 + total_graveness / HALF_SATISFIED_GRAVENESS)
+            })
+            .collect::<Vec<_>>();
+
+        let core = self.core_mut();
+        let mut total_satisfaction = 0.0;
+        for (&member, sample) in present_members.iter().zip(samples) {
+            let satisfaction = &mut core.member_satisfaction[member];
+            *satisfaction += SATISFACTION_SMOOTHING * (sample - *satisfaction);
+            total_satisfaction += *satisfaction;
+        }
+        core.satisfaction = total_satisfaction / present_members.len() as f32;
+    }
+
+    // businesses with jobs to fill can only operate with at least one employee
+    fn is_staffed(&self) -> bool {
+        let offers = &self.core().provided_offers;
+        !offers.iter().any(Offer::is_job)
+            || offers
+                .iter()
+                .any(|offer| offer.is_job() && !offer.users.is_empty())
+    }
+
+    fn open_positions(&self) -> u32 {
+        self.core()
+            .provided_offers
+            .iter()
+            .filter(|offer| offer.is_job())
+            .map(|offer| (offer.max_users as usize).saturating_sub(offer.users.len()) as u32)
+            .sum()
+    }
+
     fn get_offer(&self, idx: OfferIdx) -> &Offer {
         &self.core().provided_offers[idx.0 as usize]
     }
@@ -767,11 +1123,14 @@ pub trait Household:
         requester: EvaluationRequesterID,
         world: &mut World,
     ) {
+        // vacancies stay open even while nobody works here yet
+        let operating = self.get_offer(offer_idx).is_job() || self.is_staffed();
         let offer = self.get_offer(offer_idx);
 
-        if offer
-            .opening_hours
-            .end_after_on_same_day(TimeOfDay::from(instant))
+        if operating
+            && offer
+                .opening_hours
+                .end_after_on_same_day(TimeOfDay::from(instant))
         {
             let search_result = EvaluatedSearchResult {
                 resource: offer.deal.main_given(),
@@ -782,6 +1141,7 @@ pub trait Household:
                     },
                     deal: offer.deal.clone(),
                     opening_hours: offer.opening_hours,
+                    travel_distance: None,
                 }]
                 .into(),
             };
@@ -796,7 +1156,11 @@ pub trait Household:
         } else {
             debug(
                 LOG_T,
-                format!("Not in opening hours for {}", offer.deal.main_given()),
+                if operating {
+                    format!("Not in opening hours for {}", offer.deal.main_given())
+                } else {
+                    format!("No staff to provide {}", offer.deal.main_given())
+                },
                 self.id(),
                 world,
             );
@@ -815,23 +1179,70 @@ pub trait Household:
         offer_idx: OfferIdx,
         requester: HouseholdID,
         requester_member: MemberIdx,
+        deal: &Deal,
         world: &mut World,
     ) {
-        let offer = self.get_offer(offer_idx).clone(); // borrow checker too dumb
-        self.provide_deal(&offer.deal, offer.offering_member, world);
-        requester.receive_deal(offer.deal, requester_member, world);
+        self.get_offer_mut(offer_idx).sales_since_adjustment += 1;
+        let offering_member = self.get_offer(offer_idx).offering_member;
+        // the deal as agreed, prices might have been adjusted since
+        // bulk goods are only handed over once they are delivered, see request_freight
+        let immediate_part = deal.without_freight();
+        self.provide_deal(&immediate_part, offering_member, world);
+        LedgerID::global_first(world).record(
+            self.id_as(),
+            requester,
+            immediate_part.delta.clone(),
+            self.site(),
+            world,
+        );
+        requester.receive_deal(immediate_part, requester_member, world);
     }
 
-    fn request_receive_undo_deal(
+    // the requester already refunded itself, this only takes back what was handed over
+    fn request_receive_undo_deal(&mut self, offer_idx: OfferIdx, deal: &Deal, world: &mut World) {
+        let offering_member = self.get_offer(offer_idx).offering_member;
+        self.receive_deal(deal, offering_member, world);
+    }
+
+    fn request_freight(
         &mut self,
         offer_idx: OfferIdx,
         requester: HouseholdID,
         requester_member: MemberIdx,
+        deal: &Deal,
+        instant: Instant,
+        world: &mut World,
+    ) {
+        if let Some(cargo) = deal.freight() {
+            let offering_member = self.get_offer(offer_idx).offering_member;
+            self.provide_deal(&cargo, offering_member, world);
+            FreightDeliveryID::spawn(
+                OfferID {
+                    household: self.id_as(),
+                    idx: offer_idx,
+                },
+                self.site(),
+                requester,
+                requester_member,
+                cargo,
+                deal.without_freight(),
+                instant,
+                world,
+            );
+        }
+    }
+
+    // takes back the cargo and gives back the payment, the requester was already refunded
+    fn freight_failed(
+        &mut self,
+        offer_idx: OfferIdx,
+        cargo: &Deal,
+        paid: &Deal,
         world: &mut World,
     ) {
-        let offer = self.get_offer(offer_idx).clone(); // borrow checker too dumb
-        self.receive_deal(&offer.deal, offer.offering_member, world);
-        requester.provide_deal(offer.deal, requester_member, world);
+        let offering_member = self.get_offer(offer_idx).offering_member;
+        self.receive_deal(cargo, offering_member, world);
+        self.receive_deal(paid, offering_member, world);
     }
 
     fn started_using(
@@ -880,6 +1291,7 @@ pub trait Household:
     ) -> Fate {
         {
             let id_as_household = self.id_as();
+            let being_destroyed = self.core().being_destroyed;
             let offer = self.get_offer_mut(offer_idx);
             let users_before = offer.users.len();
 
@@ -887,7 +1299,9 @@ pub trait Household:
                 o_user != user || o_using_member != using_member
             });
 
-            if offer.is_internal
+            // reopen the vacancy or spot in the market
+            if !offer.is_internal
+                && !being_destroyed
                 && users_before >= offer.max_users as usize
                 && offer.users.len() < offer.max_users as usize
             {
@@ -1013,6 +1427,17 @@ pub struct HouseholdCore {
     pub used_offers: ResourceMap<OfferID>,
     pub member_used_offers: CVec<ResourceMap<OfferID>>,
     pub provided_offers: CVec<Offer>,
+    pub member_jobs: CVec<Option<Job>>,
+    // daily, set by the building the household moved into
+    pub rent: f32,
+    pub operating_costs: f32,
+    pub insolvent_days: u32,
+    pub home_area: Option<Area>,
+    // set by the building the household moved into, unbounded until then
+    pub storage_capacities: Inventory,
+    pub satisfaction: f32,
+    pub member_satisfaction: CVec<f32>,
+    pub member_demographics: CVec<Demographics>,
     pub being_destroyed: bool,
 }
 
@@ -1037,17 +1462,76 @@ impl HouseholdCore {
             )
         }
 
+        let mut resources = Inventory::new();
+        resources.insert(Resource::Money, INITIAL_SAVINGS);
+
         HouseholdCore {
-            resources: Inventory::new(),
+            resources,
             member_resources: vec![Inventory::new(); n_members].into(),
             member_tasks: vec![Task::idle_at(initial_location); n_members].into(),
             decision_state: DecisionState::None,
             used_offers: ResourceMap::new(),
             member_used_offers: vec![ResourceMap::new(); n_members].into(),
             provided_offers,
+            member_jobs: vec![None; n_members].into(),
+            rent: 0.0,
+            operating_costs: 0.0,
+            insolvent_days: 0,
+            home_area: None,
+            storage_capacities: Inventory::new(),
+            satisfaction: INITIAL_SATISFACTION,
+            member_satisfaction: vec![INITIAL_SATISFACTION; n_members].into(),
+            member_demographics: vec![Demographics::default(); n_members].into(),
             being_destroyed: false,
         }
     }
+
+    pub fn add_member(
+        &mut self,
+        demographics: Demographics,
+        location: RoughLocationID,
+        world: &mut World,
+    ) -> MemberIdx {
+        // reuse the slot of a departed member, if any
+        if let Some(idx) = self
+            .member_demographics
+            .iter()
+            .position(|member| !member.is_present())
+        {
+            if let Some(ticket) = self.member_tasks[idx].parked_car.take() {
+                ticket.give_back(world);
+            }
+            self.member_resources[idx] = Inventory::new();
+            self.member_tasks[idx] = Task::idle_at(location);
+            self.member_used_offers[idx] = ResourceMap::new();
+            self.member_jobs[idx] = None;
+            self.member_satisfaction[idx] = INITIAL_SATISFACTION;
+            self.member_demographics[idx] = demographics;
+            return MemberIdx::new(idx);
+        }
+
+        self.member_resources.push(Inventory::new());
+        self.member_tasks.push(Task::idle_at(location));
+        self.member_used_offers.push(ResourceMap::new());
+        self.member_jobs.push(None);
+        self.member_satisfaction.push(INITIAL_SATISFACTION);
+        self.member_demographics.push(demographics);
+        MemberIdx::new(self.member_tasks.len() - 1)
+    }
+}
+
+// households cycle some of their medium-distance trips and drive otherwise
+fn choose_vehicle_type<R: Rng>(maybe_distance: Option<f32>, rng: &mut R) -> VehicleType {
+    match maybe_distance {
+        Some(distance)
+            if distance >= MIN_CYCLING_DISTANCE
+                && distance <= MAX_CYCLING_DISTANCE
+                && rng.gen::<f32>() < CYCLING_SHARE =>
+        {
+            VehicleType::Bicycle
+        }
+        _ => VehicleType::Car,
+    }
 }
 
 pub fn setup(system: &mut ActorSystem) {
@@ -1061,11 +1545,17 @@ pub fn setup(system: &mut ActorSystem) {
     mill::setup(system);
     bakery::setup(system);
     neighboring_town_trade::setup(system);
+    configurable::setup(system);
+    jobs::setup(system);
+    satisfaction::setup(system);
     ui::auto_setup(system);
 }
 
 pub fn spawn(world: &mut World) {
     tasks::spawn(world);
+    configurable::spawn(world);
+    jobs::spawn(world);
+    satisfaction::spawn(world);
 }
 
 mod kay_auto;
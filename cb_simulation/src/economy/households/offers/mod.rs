@@ -1,5 +1,6 @@
 use compact::CVec;
 use economy::market::Deal;
+use economy::resources::{Resource, ResourceAmount};
 use super::{HouseholdID, MemberIdx};
 use cb_time::units::TimeOfDayRange;
 
@@ -22,6 +23,10 @@ pub struct Offer {
     pub users: CVec<(HouseholdID, Option<MemberIdx>)>,
     pub active_users: CVec<(HouseholdID, MemberIdx)>,
     pub being_withdrawn: bool,
+    // the Money part of the deal as first offered, the price moves around it
+    pub base_price: f32,
+    pub price_factor: f32,
+    pub sales_since_adjustment: u32,
 }
 
 impl Offer {
@@ -32,6 +37,7 @@ impl Offer {
         max_users: usize,
         is_internal: bool,
     ) -> Offer {
+        let base_price = deal.delta.get(Resource::Money).cloned().unwrap_or(0.0);
         Offer {
             offering_member,
             opening_hours,
@@ -41,10 +47,81 @@ impl Offer {
             is_internal,
             max_users: max_users as u32,
             being_withdrawn: false,
+            base_price,
+            price_factor: 1.0,
+            sales_since_adjustment: 0,
         }
     }
+
+    // only offers that are paid for have a price to adjust
+    pub fn is_priced(&self) -> bool {
+        self.base_price < 0.0
+    }
+
+    // a vacancy pays a wage for working a shift
+    pub fn is_job(&self) -> bool {
+        !self.is_internal && self.base_price > 0.0 && self.deal.delta.len() == 1
+    }
+
+    fn amount_per_deal(&self) -> ResourceAmount {
+        self.deal
+            .delta
+            .get(self.deal.main_given())
+            .cloned()
+            .unwrap_or(1.0)
+    }
+
+    pub fn unit_price(&self) -> f32 {
+        -self.base_price * self.price_factor / self.amount_per_deal()
+    }
+
+    pub fn base_unit_price(&self) -> f32 {
+        -self.base_price / self.amount_per_deal()
+    }
+
+    // prices rise with high utilization, scarce stock and brisk sales and fall otherwise
+    pub fn adjust_price(&mut self, stock: ResourceAmount) {
+        if !self.is_priced() {
+            return;
+        }
+
+        let utilization = self.users.len() as f32 / self.max_users.max(1) as f32;
+
+        let stock_in_deals = stock / self.amount_per_deal();
+        let stock_pressure = if stock_in_deals < LOW_STOCK_IN_DEALS {
+            1.0
+        } else if stock_in_deals > HIGH_STOCK_IN_DEALS {
+            -1.0
+        } else {
+            0.0
+        };
+
+        let sales_pressure = if self.sales_since_adjustment == 0 {
+            -1.0
+        } else if self.sales_since_adjustment > self.max_users {
+            1.0
+        } else {
+            0.0
+        };
+
+        let pressure =
+            (utilization - TARGET_UTILIZATION) + 0.5 * stock_pressure + 0.5 * sales_pressure;
+
+        self.price_factor = (self.price_factor * (1.0 + PRICE_ADJUSTMENT_RATE * pressure))
+            .max(MIN_PRICE_FACTOR)
+            .min(MAX_PRICE_FACTOR);
+        self.sales_since_adjustment = 0;
+        *self.deal.delta.mut_entry_or(Resource::Money, 0.0) = self.base_price * self.price_factor;
+    }
 }
 
+const TARGET_UTILIZATION: f32 = 0.75;
+const LOW_STOCK_IN_DEALS: f32 = 3.0;
+const HIGH_STOCK_IN_DEALS: f32 = 30.0;
+const PRICE_ADJUSTMENT_RATE: f32 = 0.05;
+const MIN_PRICE_FACTOR: f32 = 0.5;
+const MAX_PRICE_FACTOR: f32 = 2.0;
+
 //     // The offer stays alive until the withdrawal is confirmed
 //     // to prevent offers being used while they're being withdrawn
 //     pub fn withdraw(&mut self, world: &mut World) {
@@ -0,0 +1,178 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct SatisfactionMapRequesterID {
+    _raw_id: RawID
+}
+
+impl Copy for SatisfactionMapRequesterID {}
+impl Clone for SatisfactionMapRequesterID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for SatisfactionMapRequesterID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "SatisfactionMapRequesterID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for SatisfactionMapRequesterID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for SatisfactionMapRequesterID {
+    fn eq(&self, other: &SatisfactionMapRequesterID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for SatisfactionMapRequesterID {}
+
+pub struct SatisfactionMapRequesterRepresentative;
+
+impl ActorOrActorTrait for SatisfactionMapRequesterRepresentative {
+    type ID = SatisfactionMapRequesterID;
+}
+
+impl TypedID for SatisfactionMapRequesterID {
+    type Target = SatisfactionMapRequesterRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        SatisfactionMapRequesterID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + SatisfactionMapRequester> TraitIDFrom<Act> for SatisfactionMapRequesterID {}
+
+impl SatisfactionMapRequesterID {
+    pub fn on_satisfaction_map(self, areas: CVec < AreaSatisfaction >, summary: SatisfactionSummary, world: &mut World) {
+        world.send(self.as_raw(), MSG_SatisfactionMapRequester_on_satisfaction_map(areas, summary));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<SatisfactionMapRequesterRepresentative>();
+        system.register_trait_message::<MSG_SatisfactionMapRequester_on_satisfaction_map>();
+    }
+
+    pub fn register_implementor<Act: Actor + SatisfactionMapRequester>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, SatisfactionMapRequesterRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_SatisfactionMapRequester_on_satisfaction_map(ref areas, summary), instance, world| {
+                instance.on_satisfaction_map(areas, summary, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_SatisfactionMapRequester_on_satisfaction_map(pub CVec < AreaSatisfaction >, pub SatisfactionSummary);
+impl Actor for SatisfactionMap {
+    type ID = SatisfactionMapID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct SatisfactionMapID {
+    _raw_id: RawID
+}
+
+impl Copy for SatisfactionMapID {}
+impl Clone for SatisfactionMapID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for SatisfactionMapID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "SatisfactionMapID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for SatisfactionMapID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for SatisfactionMapID {
+    fn eq(&self, other: &SatisfactionMapID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for SatisfactionMapID {}
+
+impl TypedID for SatisfactionMapID {
+    type Target = SatisfactionMap;
+
+    fn from_raw(id: RawID) -> Self {
+        SatisfactionMapID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl SatisfactionMapID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = SatisfactionMapID::from_raw(world.allocate_instance_id::<SatisfactionMap>());
+        let swarm = world.local_broadcast::<SatisfactionMap>();
+        world.send(swarm, MSG_SatisfactionMap_spawn(id, ));
+        id
+    }
+    
+    pub fn on_satisfaction(self, household: HouseholdID, area: Area, satisfaction: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_SatisfactionMap_on_satisfaction(household, area, satisfaction));
+    }
+    
+    pub fn on_household_gone(self, household: HouseholdID, world: &mut World) {
+        world.send(self.as_raw(), MSG_SatisfactionMap_on_household_gone(household));
+    }
+    
+    pub fn get_satisfaction_map(self, requester: SatisfactionMapRequesterID, world: &mut World) {
+        world.send(self.as_raw(), MSG_SatisfactionMap_get_satisfaction_map(requester));
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_SatisfactionMap_spawn(pub SatisfactionMapID, );
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_SatisfactionMap_on_satisfaction(pub HouseholdID, pub Area, pub f32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_SatisfactionMap_on_household_gone(pub HouseholdID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_SatisfactionMap_get_satisfaction_map(pub SatisfactionMapRequesterID);
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    SatisfactionMapRequesterID::register_trait(system);
+    system.add_spawner::<SatisfactionMap, _, _>(
+        |&MSG_SatisfactionMap_spawn(id, ), world| {
+            SatisfactionMap::spawn(id, world)
+        }, false
+    );
+    
+    system.add_handler::<SatisfactionMap, _, _>(
+        |&MSG_SatisfactionMap_on_satisfaction(household, area, satisfaction), instance, world| {
+            instance.on_satisfaction(household, area, satisfaction, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<SatisfactionMap, _, _>(
+        |&MSG_SatisfactionMap_on_household_gone(household), instance, world| {
+            instance.on_household_gone(household, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<SatisfactionMap, _, _>(
+        |&MSG_SatisfactionMap_get_satisfaction_map(requester), instance, world| {
+            instance.get_satisfaction_map(requester, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
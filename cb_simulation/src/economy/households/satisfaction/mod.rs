@@ -0,0 +1,126 @@
+use kay::{ActorSystem, World};
+use compact::{CVec, CHashMap};
+use descartes::P2;
+use std::collections::HashMap;
+use super::HouseholdID;
+
+pub const AREA_SIZE: f32 = 200.0;
+
+// a square cell of the happiness heatmap
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub struct Area {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Area {
+    pub fn containing(position: P2) -> Area {
+        Area {
+            x: (position.x / AREA_SIZE).floor() as i32,
+            y: (position.y / AREA_SIZE).floor() as i32,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct AreaSatisfaction {
+    pub area: Area,
+    pub n_households: u32,
+    pub satisfaction: f32,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct SatisfactionSummary {
+    pub n_households: u32,
+    pub average: f32,
+}
+
+pub trait SatisfactionMapRequester {
+    fn on_satisfaction_map(
+        &mut self,
+        areas: &CVec<AreaSatisfaction>,
+        summary: SatisfactionSummary,
+        world: &mut World,
+    );
+}
+
+// Collects the latest satisfaction of all residents, by the area they live in
+#[derive(Compact, Clone)]
+pub struct SatisfactionMap {
+    id: SatisfactionMapID,
+    household_satisfaction: CHashMap<HouseholdID, (Area, f32)>,
+}
+
+impl SatisfactionMap {
+    pub fn spawn(id: SatisfactionMapID, _: &mut World) -> SatisfactionMap {
+        SatisfactionMap {
+            id,
+            household_satisfaction: CHashMap::new(),
+        }
+    }
+
+    pub fn on_satisfaction(
+        &mut self,
+        household: HouseholdID,
+        area: Area,
+        satisfaction: f32,
+        _: &mut World,
+    ) {
+        self.household_satisfaction
+            .insert(household, (area, satisfaction));
+    }
+
+    pub fn on_household_gone(&mut self, household: HouseholdID, _: &mut World) {
+        self.household_satisfaction.remove(household);
+    }
+
+    pub fn get_satisfaction_map(
+        &mut self,
+        requester: SatisfactionMapRequesterID,
+        world: &mut World,
+    ) {
+        let mut by_area = HashMap::<Area, (u32, f32)>::new();
+        let mut n_households = 0;
+        let mut total = 0.0;
+
+        for (_, &(area, satisfaction)) in self.household_satisfaction.pairs() {
+            let entry = by_area.entry(area).or_insert((0, 0.0));
+            entry.0 += 1;
+            entry.1 += satisfaction;
+            n_households += 1;
+            total += satisfaction;
+        }
+
+        requester.on_satisfaction_map(
+            by_area
+                .into_iter()
+                .map(|(area, (n_households, sum))| AreaSatisfaction {
+                    area,
+                    n_households,
+                    satisfaction: sum / n_households as f32,
+                })
+                .collect::<CVec<_>>(),
+            SatisfactionSummary {
+                n_households,
+                average: if n_households > 0 {
+                    total / n_households as f32
+                } else {
+                    0.0
+                },
+            },
+            world,
+        );
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<SatisfactionMap>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) {
+    SatisfactionMapID::spawn(world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
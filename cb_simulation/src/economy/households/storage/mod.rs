@@ -0,0 +1,72 @@
+use cb_time::units::Duration;
+use economy::resources::{Resource, ResourceAmount, Inventory, Entry};
+use economy::resources::Resource::*;
+
+// how much of a good fits on each square meter of a household's lot
+const BULK_UNITS_PER_SQUARE_METER: f32 = 1.0;
+const PACKAGED_UNITS_PER_SQUARE_METER: f32 = 0.25;
+
+const STORED_GOODS: [Resource; 12] = [
+    Groceries,
+    Produce,
+    Grain,
+    Flour,
+    BakedGoods,
+    Meat,
+    DairyGoods,
+    Wood,
+    Furniture,
+    TextileGoods,
+    Clothes,
+    Devices,
+];
+
+// everything else (money, needs and services) is not stored and thus unbounded
+pub fn storage_capacities_for(lot_area: f32) -> Inventory {
+    STORED_GOODS
+        .iter()
+        .map(|&resource| {
+            let units_per_square_meter = if resource.is_bulk() {
+                BULK_UNITS_PER_SQUARE_METER
+            } else {
+                PACKAGED_UNITS_PER_SQUARE_METER
+            };
+            (resource, lot_area * units_per_square_meter)
+        })
+        .collect()
+}
+
+// share of a stock that goes bad per day
+pub fn daily_spoilage_rate(resource: Resource) -> f32 {
+    match resource {
+        BakedGoods => 0.5,
+        DairyGoods => 0.2,
+        Meat => 0.2,
+        Produce => 0.1,
+        _ => 0.0,
+    }
+}
+
+// lets perishables go bad and discards whatever doesn't fit into storage,
+// only positive stocks are affected, negative ones are needs
+pub fn spoil_and_discard_excess(resources: &mut Inventory, capacities: &Inventory, dt: Duration) {
+    let stocks = resources
+        .iter()
+        .map(|&Entry(resource, amount)| (resource, amount))
+        .collect::<Vec<_>>();
+
+    for (resource, amount) in stocks {
+        if amount <= 0.0 {
+            continue;
+        }
+
+        let kept_share = (1.0 - daily_spoilage_rate(resource)).powf(dt.as_days());
+        let mut new_amount: ResourceAmount = amount * kept_share;
+
+        if let Some(&capacity) = capacities.get(resource) {
+            new_amount = new_amount.min(capacity);
+        }
+
+        resources.insert(resource, new_amount);
+    }
+}
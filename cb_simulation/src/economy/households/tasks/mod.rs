@@ -4,7 +4,10 @@ use cb_time::actors::{Temporal, TemporalID};
 use cb_time::units::{Instant, Duration};
 use transport::pathfinding::RoughLocationID;
 use transport::pathfinding::trip::TripID;
+use transport::microtraffic::VehicleType;
+use transport::parking::ParkingTicket;
 use super::super::resources::Resource;
+use super::super::market::Deal;
 use super::OfferID;
 
 use super::{HouseholdID, MemberIdx};
@@ -17,11 +20,22 @@ pub enum TaskState {
     IdleAt(RoughLocationID),
 }
 
-#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+#[derive(Compact, Clone, Debug, Serialize, Deserialize)]
+pub struct TaskGoal {
+    pub resource: Resource,
+    pub offer: OfferID,
+    // as agreed when the task was chosen, undone from this if anything fails
+    pub deal: Deal,
+}
+
+#[derive(Compact, Clone, Debug, Serialize, Deserialize)]
 pub struct Task {
-    pub goal: Option<(Resource, OfferID)>,
+    pub goal: Option<TaskGoal>,
     pub duration: Duration,
     pub state: TaskState,
+    pub vehicle_type: VehicleType,
+    // where the member's car was left after the last car trip
+    pub parked_car: Option<ParkingTicket>,
 }
 
 impl Task {
@@ -30,6 +44,8 @@ impl Task {
             goal: None,
             duration: Duration(0),
             state: TaskState::IdleAt(location),
+            vehicle_type: VehicleType::Car,
+            parked_car: None,
         }
     }
 }
@@ -62,6 +62,22 @@ impl ImmigrationManagerID {
         id
     }
     
+    pub fn on_unmet_demand(self, resource: Resource, world: &mut World) {
+        world.send(self.as_raw(), MSG_ImmigrationManager_on_unmet_demand(resource));
+    }
+    
+    pub fn on_vacant_dwellings_changed(self, delta: i32, world: &mut World) {
+        world.send(self.as_raw(), MSG_ImmigrationManager_on_vacant_dwellings_changed(delta));
+    }
+    
+    pub fn on_emigration(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_ImmigrationManager_on_emigration());
+    }
+    
+    pub fn on_household_forming(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_ImmigrationManager_on_household_forming());
+    }
+    
     pub fn on_unit_offer(self, building_id: BuildingID, unit_idx: UnitIdx, world: &mut World) {
         world.send(self.as_raw(), MSG_ImmigrationManager_on_unit_offer(building_id, unit_idx));
     }
@@ -70,6 +86,14 @@ impl ImmigrationManagerID {
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_ImmigrationManager_spawn(pub ImmigrationManagerID, pub TimeID, pub DevelopmentManagerID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ImmigrationManager_on_unmet_demand(pub Resource);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ImmigrationManager_on_vacant_dwellings_changed(pub i32);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ImmigrationManager_on_emigration();
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ImmigrationManager_on_household_forming();
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_ImmigrationManager_on_unit_offer(pub BuildingID, pub UnitIdx);
 
 impl Into<SleeperID> for ImmigrationManagerID {
@@ -77,6 +101,24 @@ impl Into<SleeperID> for ImmigrationManagerID {
         SleeperID::from_raw(self.as_raw())
     }
 }
+
+impl Into<ConfigUserID<HouseholdKindConfig>> for ImmigrationManagerID {
+    fn into(self) -> ConfigUserID<HouseholdKindConfig> {
+        ConfigUserID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<EmploymentStatisticsRequesterID> for ImmigrationManagerID {
+    fn into(self) -> EmploymentStatisticsRequesterID {
+        EmploymentStatisticsRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl Into<SatisfactionMapRequesterID> for ImmigrationManagerID {
+    fn into(self) -> SatisfactionMapRequesterID {
+        SatisfactionMapRequesterID::from_raw(self.as_raw())
+    }
+}
 impl Actor for DevelopmentManager {
     type ID = DevelopmentManagerID;
 
@@ -159,12 +201,39 @@ impl Into<SleeperID> for DevelopmentManagerID {
 pub fn auto_setup(system: &mut ActorSystem) {
     
     SleeperID::register_implementor::<ImmigrationManager>(system);
+    ConfigUserID::<HouseholdKindConfig>::register_implementor::<ImmigrationManager>(system);
+    EmploymentStatisticsRequesterID::register_implementor::<ImmigrationManager>(system);
+    SatisfactionMapRequesterID::register_implementor::<ImmigrationManager>(system);
     system.add_spawner::<ImmigrationManager, _, _>(
         |&MSG_ImmigrationManager_spawn(id, time, development_manager), world| {
             ImmigrationManager::spawn(id, time, development_manager, world)
         }, false
     );
     
+    system.add_handler::<ImmigrationManager, _, _>(
+        |&MSG_ImmigrationManager_on_unmet_demand(resource), instance, world| {
+            instance.on_unmet_demand(resource, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<ImmigrationManager, _, _>(
+        |&MSG_ImmigrationManager_on_vacant_dwellings_changed(delta), instance, world| {
+            instance.on_vacant_dwellings_changed(delta, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<ImmigrationManager, _, _>(
+        |&MSG_ImmigrationManager_on_emigration(), instance, world| {
+            instance.on_emigration(world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<ImmigrationManager, _, _>(
+        |&MSG_ImmigrationManager_on_household_forming(), instance, world| {
+            instance.on_household_forming(world); Fate::Live
+        }, false
+    );
+    
     system.add_handler::<ImmigrationManager, _, _>(
         |&MSG_ImmigrationManager_on_unit_offer(building_id, unit_idx), instance, world| {
             instance.on_unit_offer(building_id, unit_idx, world); Fate::Live
@@ -1,13 +1,20 @@
 use kay::{World, ActorSystem, TypedID};
-use compact::COption;
+use compact::{CVec, COption, CHashMap};
 use land_use::buildings::{UnitType, BuildingID, UnitIdx};
 use cb_time::actors::{Sleeper, SleeperID, TimeID};
 use cb_time::units::{Instant, Duration};
 use cb_util::random::{seed, Rng};
 use cb_util::log::{debug};
+use cb_util::config_manager::{Name, ConfigUser, ConfigUserID};
+use economy::resources::{Resource, Inventory, Entry};
+use economy::households::jobs::{EmploymentStatisticsID, EmploymentStatisticsRequester,
+EmploymentStatisticsRequesterID, EmploymentSummary};
+use economy::households::satisfaction::{SatisfactionMapID, SatisfactionMapRequester,
+SatisfactionMapRequesterID, AreaSatisfaction, SatisfactionSummary};
 const LOG_T: &str = "Immigration/Development";
 
 use economy::households::household_kinds;
+use economy::ledger::LedgerID;
 use self::household_kinds::family::FamilyID;
 use self::household_kinds::grocery_shop::GroceryShopID;
 use self::household_kinds::cow_farm::CowFarmID;
@@ -16,6 +23,7 @@ use self::household_kinds::vegetable_farm::VegetableFarmID;
 use self::household_kinds::mill::MillID;
 use self::household_kinds::bakery::BakeryID;
 use self::household_kinds::neighboring_town_trade::NeighboringTownTradeID;
+use self::household_kinds::configurable::{ConfigurableHouseholdID, HouseholdKindConfig};
 use land_use::buildings::BuildingStyle;
 use land_use::vacant_lots::VacantLotID;
 use land_use::zone_planning::BuildingIntent;
@@ -35,6 +43,7 @@ pub enum HouseholdTypeToSpawn {
     Mill,
     Bakery,
     NeighboringTownTrade,
+    Configured(Name, UnitType, BuildingStyle),
 }
 
 pub fn unit_type_for(household_type: HouseholdTypeToSpawn) -> UnitType {
@@ -47,6 +56,22 @@ pub fn unit_type_for(household_type: HouseholdTypeToSpawn) -> UnitType {
         HouseholdTypeToSpawn::Mill => UnitType::Mill,
         HouseholdTypeToSpawn::Bakery => UnitType::Bakery,
         HouseholdTypeToSpawn::NeighboringTownTrade => UnitType::NeighboringTownTrade,
+        HouseholdTypeToSpawn::Configured(_, unit_type, _) => unit_type,
+    }
+}
+
+// what a household type sells, so unmet demand for it attracts that type
+pub fn provided_resources_for(household_type: HouseholdTypeToSpawn) -> &'static [Resource] {
+    match household_type {
+        HouseholdTypeToSpawn::GroceryShop => &[Resource::Groceries],
+        HouseholdTypeToSpawn::GrainFarm => &[Resource::Grain],
+        HouseholdTypeToSpawn::CowFarm => &[Resource::Meat, Resource::DairyGoods],
+        HouseholdTypeToSpawn::VegetableFarm => &[Resource::Produce],
+        HouseholdTypeToSpawn::Mill => &[Resource::Flour],
+        HouseholdTypeToSpawn::Bakery => &[Resource::BakedGoods],
+        HouseholdTypeToSpawn::Family
+        | HouseholdTypeToSpawn::NeighboringTownTrade
+        | HouseholdTypeToSpawn::Configured(..) => &[],
     }
 }
 
@@ -60,15 +85,41 @@ pub fn building_style_for(household_type: HouseholdTypeToSpawn) -> BuildingStyle
         HouseholdTypeToSpawn::Mill => BuildingStyle::Mill,
         HouseholdTypeToSpawn::Bakery => BuildingStyle::Bakery,
         HouseholdTypeToSpawn::NeighboringTownTrade => BuildingStyle::NeighboringTownConnection,
+        HouseholdTypeToSpawn::Configured(_, _, building_style) => building_style,
     }
 }
 
+// how households of this type are labeled in the ledger
+pub fn kind_name_for(household_type: HouseholdTypeToSpawn) -> Name {
+    let name = match household_type {
+        HouseholdTypeToSpawn::Family => "Family",
+        HouseholdTypeToSpawn::GroceryShop => "GroceryShop",
+        HouseholdTypeToSpawn::GrainFarm => "GrainFarm",
+        HouseholdTypeToSpawn::CowFarm => "CowFarm",
+        HouseholdTypeToSpawn::VegetableFarm => "VegetableFarm",
+        HouseholdTypeToSpawn::Mill => "Mill",
+        HouseholdTypeToSpawn::Bakery => "Bakery",
+        HouseholdTypeToSpawn::NeighboringTownTrade => "NeighboringTown",
+        HouseholdTypeToSpawn::Configured(name, ..) => return name,
+    };
+    Name::from(name).unwrap()
+}
+
 #[derive(Compact, Clone)]
 pub struct ImmigrationManager {
     id: ImmigrationManagerID,
     time: TimeID,
     development_manager: DevelopmentManagerID,
     state: ImmigrationManagerState,
+    household_kinds: CHashMap<Name, HouseholdKindConfig>,
+    // failed searches per resource, fading over time
+    unmet_demand: Inventory,
+    vacant_dwellings: i32,
+    employment: Option<EmploymentSummary>,
+    satisfaction: Option<SatisfactionSummary>,
+    recent_emigrations: f32,
+    // members who moved out of their family and still need a home of their own
+    forming_households: u32,
 }
 
 impl ImmigrationManager {
@@ -80,12 +131,60 @@ impl ImmigrationManager {
     ) -> ImmigrationManager {
         time.wake_up_in(IMMIGRATION_PACE.into(), id.into(), world);
 
-        ImmigrationManager {
+        let manager = ImmigrationManager {
             id,
             time,
             development_manager,
             state: ImmigrationManagerState::Idle,
-        }
+            household_kinds: CHashMap::new(),
+            unmet_demand: Inventory::new(),
+            vacant_dwellings: 0,
+            employment: None,
+            satisfaction: None,
+            recent_emigrations: 0.0,
+            forming_households: 0,
+        };
+        manager.get_initial_config(world);
+        manager
+    }
+
+    pub fn on_unmet_demand(&mut self, resource: Resource, _: &mut World) {
+        *self.unmet_demand.mut_entry_or(resource, 0.0) += 1.0;
+    }
+
+    pub fn on_vacant_dwellings_changed(&mut self, delta: i32, _: &mut World) {
+        self.vacant_dwellings += delta;
+    }
+
+    pub fn on_emigration(&mut self, _: &mut World) {
+        self.recent_emigrations += 1.0;
+    }
+
+    pub fn on_household_forming(&mut self, _: &mut World) {
+        self.forming_households += 1;
+    }
+
+    fn demand_for(&self, resources: &[Resource]) -> f32 {
+        resources
+            .iter()
+            .map(|&resource| self.unmet_demand.get(resource).cloned().unwrap_or(0.0))
+            .sum()
+    }
+
+    // low unemployment, satisfied residents and few recent emigrations make the city
+    // attractive to families
+    fn attractiveness(&self) -> f32 {
+        let employment_factor = self
+            .employment
+            .map(|employment| 1.0 - employment.unemployment_rate)
+            .unwrap_or(1.0);
+        let satisfaction_factor = self
+            .satisfaction
+            .filter(|satisfaction| satisfaction.n_households > 0)
+            .map(|satisfaction| satisfaction.average)
+            .unwrap_or(1.0);
+        employment_factor * satisfaction_factor
+            / (1.0 + EMIGRATION_DETERRENCE * self.recent_emigrations)
     }
 }
 
@@ -96,57 +195,99 @@ pub enum ImmigrationManagerState {
 }
 
 const IMMIGRATION_PACE: Duration = Duration(10);
+// scales the shares of household types, so that city conditions dominate
+const BASE_SHARE_FACTOR: f32 = 0.2;
+const HOUSING_PULL: f32 = 0.1;
+const JOB_PULL: f32 = 0.2;
+const DEMAND_PULL: f32 = 0.05;
+const UNMET_DEMAND_DECAY: f32 = 0.98;
+const EMIGRATION_DECAY: f32 = 0.99;
+const EMIGRATION_DETERRENCE: f32 = 0.5;
+const FORMING_HOUSEHOLD_PULL: f32 = 1.0;
+const IMMIGRANT_FAMILY_SIZE: u32 = 3;
 
 impl Sleeper for ImmigrationManager {
     fn wake(&mut self, current_instant: Instant, world: &mut World) {
         self.state = match self.state {
             ImmigrationManagerState::Idle => {
-                let family_share = 1.0;
-                let grocery_share = 0.2;
-                let cow_farm_share = 0.3;
-                let veg_farm_share = 0.26;
-                let grain_farm_share = 0.2; //0.0016;
-                let mill_share = 0.2; //0.001;
-                let bakery_share = 0.2; //0.01;
-
-                let total_share = family_share
-                    + grocery_share
-                    + cow_farm_share
-                    + veg_farm_share
-                    + grain_farm_share
-                    + mill_share
-                    + bakery_share;
-
-                let dot = seed(current_instant).gen_range(0.0, total_share);
-
-                let household_type_to_spawn = if dot < family_share {
-                    HouseholdTypeToSpawn::Family
-                } else if dot < family_share + grocery_share {
-                    HouseholdTypeToSpawn::GroceryShop
-                } else if dot < family_share + grocery_share + cow_farm_share {
-                    HouseholdTypeToSpawn::CowFarm
-                } else if dot < family_share + grocery_share + cow_farm_share + veg_farm_share {
-                    HouseholdTypeToSpawn::VegetableFarm
-                } else if dot
-                    < family_share
-                        + grocery_share
-                        + cow_farm_share
-                        + veg_farm_share
-                        + grain_farm_share
-                {
-                    HouseholdTypeToSpawn::GrainFarm
-                } else if dot
-                    < family_share
-                        + grocery_share
-                        + cow_farm_share
-                        + veg_farm_share
-                        + grain_farm_share
-                        + mill_share
-                {
-                    HouseholdTypeToSpawn::Mill
-                } else {
-                    HouseholdTypeToSpawn::Bakery
-                };
+                let vacancies = self
+                    .employment
+                    .map(|employment| employment.vacancies)
+                    .unwrap_or(0);
+                let family_share = (BASE_SHARE_FACTOR
+                    + HOUSING_PULL * self.vacant_dwellings.max(0) as f32
+                    + JOB_PULL * vacancies as f32)
+                    * self.attractiveness()
+                    + FORMING_HOUSEHOLD_PULL * self.forming_households as f32;
+
+                let mut candidates = vec![(HouseholdTypeToSpawn::Family, family_share, vec![])];
+
+                for &(household_type, base_share) in &[
+                    (HouseholdTypeToSpawn::GroceryShop, 0.2),
+                    (HouseholdTypeToSpawn::CowFarm, 0.3),
+                    (HouseholdTypeToSpawn::VegetableFarm, 0.26),
+                    (HouseholdTypeToSpawn::GrainFarm, 0.2),
+                    (HouseholdTypeToSpawn::Mill, 0.2),
+                    (HouseholdTypeToSpawn::Bakery, 0.2),
+                ] {
+                    let provided = provided_resources_for(household_type);
+                    candidates.push((
+                        household_type,
+                        base_share * BASE_SHARE_FACTOR + DEMAND_PULL * self.demand_for(provided),
+                        provided.to_vec(),
+                    ));
+                }
+
+                // kinds from modding/household_kinds.yaml, sorted to pick deterministically
+                let mut configured_kinds = self.household_kinds.pairs().collect::<Vec<_>>();
+                configured_kinds.sort_by_key(|&(name, _)| *name);
+
+                for (name, kind) in configured_kinds {
+                    let provided = kind
+                        .offers
+                        .iter()
+                        .filter(|offer| !offer.internal)
+                        .flat_map(|offer| offer.deal.iter())
+                        .filter_map(|&Entry(resource, amount)| {
+                            if resource != Resource::Money && amount > 0.0 {
+                                Some(resource)
+                            } else {
+                                None
+                            }
+                        })
+                        .collect::<Vec<_>>();
+                    candidates.push((
+                        HouseholdTypeToSpawn::Configured(
+                            *name,
+                            kind.unit_type,
+                            kind.building_style,
+                        ),
+                        kind.immigration_share * BASE_SHARE_FACTOR
+                            + DEMAND_PULL * self.demand_for(&provided),
+                        provided,
+                    ));
+                }
+
+                let total_share: f32 = candidates.iter().map(|&(_, share, _)| share).sum();
+                let mut dot = seed(current_instant).gen_range(0.0, total_share);
+
+                let (household_type_to_spawn, _, ref provided) = *candidates
+                    .iter()
+                    .find(|&&(_, share, _)| {
+                        if dot < share {
+                            true
+                        } else {
+                            dot -= share;
+                            false
+                        }
+                    })
+                    .or_else(|| candidates.last())
+                    .expect("Should have a household type to spawn");
+
+                // the demand is being taken care of
+                for &resource in provided {
+                    self.unmet_demand.remove(resource);
+                }
 
                 debug(
                     LOG_T,
@@ -174,11 +315,43 @@ impl Sleeper for ImmigrationManager {
             }
         };
 
+        self.unmet_demand = self
+            .unmet_demand
+            .iter()
+            .map(|&Entry(resource, demand)| (resource, demand * UNMET_DEMAND_DECAY))
+            .collect();
+        self.recent_emigrations *= EMIGRATION_DECAY;
+        EmploymentStatisticsID::local_first(world).get_employment_statistics(self.id.into(), world);
+        SatisfactionMapID::local_first(world).get_satisfaction_map(self.id.into(), world);
+
         self.time
             .wake_up_in(IMMIGRATION_PACE.into(), self.id.into(), world);
     }
 }
 
+impl ConfigUser<HouseholdKindConfig> for ImmigrationManager {
+    fn local_cache(&mut self) -> &mut CHashMap<Name, HouseholdKindConfig> {
+        &mut self.household_kinds
+    }
+}
+
+impl EmploymentStatisticsRequester for ImmigrationManager {
+    fn on_employment_statistics(&mut self, summary: EmploymentSummary, _: &mut World) {
+        self.employment = Some(summary);
+    }
+}
+
+impl SatisfactionMapRequester for ImmigrationManager {
+    fn on_satisfaction_map(
+        &mut self,
+        _areas: &CVec<AreaSatisfaction>,
+        summary: SatisfactionSummary,
+        _: &mut World,
+    ) {
+        self.satisfaction = Some(summary);
+    }
+}
+
 impl ImmigrationManager {
     pub fn on_unit_offer(&mut self, building_id: BuildingID, unit_idx: UnitIdx, world: &mut World) {
         debug(LOG_T, "Got offer", self.id, world);
@@ -188,7 +361,14 @@ impl ImmigrationManager {
 
                 let household_id = match household_type_to_spawn {
                     HouseholdTypeToSpawn::Family => {
-                        FamilyID::move_into(3, building_id, self.time, world).into()
+                        // a member moving out on their own takes precedence over immigrants
+                        let n_members = if self.forming_households > 0 {
+                            self.forming_households -= 1;
+                            1
+                        } else {
+                            IMMIGRANT_FAMILY_SIZE
+                        };
+                        FamilyID::move_into(n_members, building_id, self.time, world).into()
                     }
                     HouseholdTypeToSpawn::GroceryShop => {
                         GroceryShopID::move_into(building_id, self.time, world).into()
@@ -211,8 +391,37 @@ impl ImmigrationManager {
                     HouseholdTypeToSpawn::NeighboringTownTrade => {
                         NeighboringTownTradeID::move_into(building_id, self.time, world).into()
                     }
+                    HouseholdTypeToSpawn::Configured(kind, ..) => {
+                        // the kind might have been removed from the config meanwhile
+                        let maybe_config = self.household_kinds.get(kind).cloned();
+                        let config = if let Some(config) = maybe_config {
+                            config
+                        } else {
+                            debug(
+                                LOG_T,
+                                format!("Household kind {} is gone, not moving in", kind),
+                                self.id,
+                                world,
+                            );
+                            self.state = ImmigrationManagerState::Idle;
+                            return;
+                        };
+                        ConfigurableHouseholdID::move_into(
+                            kind,
+                            config,
+                            building_id,
+                            self.time,
+                            world,
+                        )
+                        .into()
+                    }
                 };
 
+                LedgerID::global_first(world).on_household_kind(
+                    household_id,
+                    kind_name_for(household_type_to_spawn),
+                    world,
+                );
                 building_id.add_household(household_id, unit_idx, world);
 
                 ImmigrationManagerState::Idle
@@ -0,0 +1,234 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct LedgerRequesterID {
+    _raw_id: RawID
+}
+
+impl Copy for LedgerRequesterID {}
+impl Clone for LedgerRequesterID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for LedgerRequesterID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "LedgerRequesterID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for LedgerRequesterID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for LedgerRequesterID {
+    fn eq(&self, other: &LedgerRequesterID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for LedgerRequesterID {}
+
+pub struct LedgerRequesterRepresentative;
+
+impl ActorOrActorTrait for LedgerRequesterRepresentative {
+    type ID = LedgerRequesterID;
+}
+
+impl TypedID for LedgerRequesterID {
+    type Target = LedgerRequesterRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        LedgerRequesterID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + LedgerRequester> TraitIDFrom<Act> for LedgerRequesterID {}
+
+impl LedgerRequesterID {
+    pub fn on_money_flows(self, flows: CVec < MoneyFlow >, n_entries: u32, world: &mut World) {
+        world.send(self.as_raw(), MSG_LedgerRequester_on_money_flows(flows, n_entries));
+    }
+    
+    pub fn on_supply_chain(self, household: HouseholdID, resource: Resource, links: CVec < SupplyLink >, world: &mut World) {
+        world.send(self.as_raw(), MSG_LedgerRequester_on_supply_chain(household, resource, links));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<LedgerRequesterRepresentative>();
+        system.register_trait_message::<MSG_LedgerRequester_on_money_flows>();
+        system.register_trait_message::<MSG_LedgerRequester_on_supply_chain>();
+    }
+
+    pub fn register_implementor<Act: Actor + LedgerRequester>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, LedgerRequesterRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_LedgerRequester_on_money_flows(ref flows, n_entries), instance, world| {
+                instance.on_money_flows(flows, n_entries, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_LedgerRequester_on_supply_chain(household, resource, ref links), instance, world| {
+                instance.on_supply_chain(household, resource, links, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_LedgerRequester_on_money_flows(pub CVec < MoneyFlow >, pub u32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_LedgerRequester_on_supply_chain(pub HouseholdID, pub Resource, pub CVec < SupplyLink >);
+impl Actor for Ledger {
+    type ID = LedgerID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct LedgerID {
+    _raw_id: RawID
+}
+
+impl Copy for LedgerID {}
+impl Clone for LedgerID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for LedgerID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "LedgerID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for LedgerID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for LedgerID {
+    fn eq(&self, other: &LedgerID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for LedgerID {}
+
+impl TypedID for LedgerID {
+    type Target = Ledger;
+
+    fn from_raw(id: RawID) -> Self {
+        LedgerID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl LedgerID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = LedgerID::from_raw(world.allocate_instance_id::<Ledger>());
+        let swarm = world.local_broadcast::<Ledger>();
+        world.send(swarm, MSG_Ledger_spawn(id, ));
+        id
+    }
+    
+    pub fn set_enabled(self, enabled: bool, world: &mut World) {
+        world.send(self.as_raw(), MSG_Ledger_set_enabled(enabled));
+    }
+    
+    pub fn on_household_kind(self, household: HouseholdID, kind: Name, world: &mut World) {
+        world.send(self.as_raw(), MSG_Ledger_on_household_kind(household, kind));
+    }
+    
+    pub fn on_household_gone(self, household: HouseholdID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Ledger_on_household_gone(household));
+    }
+    
+    pub fn record(self, provider: HouseholdID, receiver: HouseholdID, delta: Inventory, location: RoughLocationID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Ledger_record(provider, receiver, delta, location));
+    }
+    
+    pub fn get_money_flows(self, requester: LedgerRequesterID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Ledger_get_money_flows(requester));
+    }
+    
+    pub fn trace_supply_chain(self, household: HouseholdID, resource: Resource, requester: LedgerRequesterID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Ledger_trace_supply_chain(household, resource, requester));
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Ledger_spawn(pub LedgerID, );
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Ledger_set_enabled(pub bool);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Ledger_on_household_kind(pub HouseholdID, pub Name);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Ledger_on_household_gone(pub HouseholdID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Ledger_record(pub HouseholdID, pub HouseholdID, pub Inventory, pub RoughLocationID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Ledger_get_money_flows(pub LedgerRequesterID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Ledger_trace_supply_chain(pub HouseholdID, pub Resource, pub LedgerRequesterID);
+
+impl Into<TemporalID> for LedgerID {
+    fn into(self) -> TemporalID {
+        TemporalID::from_raw(self.as_raw())
+    }
+}
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    LedgerRequesterID::register_trait(system);
+    TemporalID::register_implementor::<Ledger>(system);
+    system.add_spawner::<Ledger, _, _>(
+        |&MSG_Ledger_spawn(id, ), world| {
+            Ledger::spawn(id, world)
+        }, false
+    );
+    
+    system.add_handler::<Ledger, _, _>(
+        |&MSG_Ledger_set_enabled(enabled), instance, world| {
+            instance.set_enabled(enabled, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Ledger, _, _>(
+        |&MSG_Ledger_on_household_kind(household, kind), instance, world| {
+            instance.on_household_kind(household, kind, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Ledger, _, _>(
+        |&MSG_Ledger_on_household_gone(household), instance, world| {
+            instance.on_household_gone(household, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Ledger, _, _>(
+        |&MSG_Ledger_record(provider, receiver, ref delta, location), instance, world| {
+            instance.record(provider, receiver, delta, location, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Ledger, _, _>(
+        |&MSG_Ledger_get_money_flows(requester), instance, world| {
+            instance.get_money_flows(requester, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Ledger, _, _>(
+        |&MSG_Ledger_trace_supply_chain(household, resource, requester), instance, world| {
+            instance.trace_supply_chain(household, resource, requester, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,269 @@
+use kay::{ActorSystem, World};
+use compact::{CVec, CHashMap};
+use cb_time::actors::{Temporal, TemporalID};
+use cb_time::units::Instant;
+use cb_util::config_manager::Name;
+use std::collections::{HashMap, HashSet};
+use economy::resources::{Resource, Inventory, Entry};
+use economy::households::HouseholdID;
+use transport::pathfinding::RoughLocationID;
+
+const MAX_ENTRIES: usize = 100_000;
+const MAX_TRACE_DEPTH: u32 = 4;
+const MAX_SOURCES_PER_RESOURCE: usize = 3;
+
+// one executed deal, from the point of view of the receiver
+#[derive(Compact, Clone, Debug, Serialize, Deserialize)]
+pub struct LedgerEntry {
+    pub instant: Instant,
+    pub provider: HouseholdID,
+    pub receiver: HouseholdID,
+    pub delta: Inventory,
+    pub location: RoughLocationID,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct MoneyFlow {
+    pub from: Name,
+    pub to: Name,
+    pub amount: f32,
+}
+
+// one deal on the way to a household's resource, depth 0 being the final one
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct SupplyLink {
+    pub depth: u32,
+    pub instant: Instant,
+    pub provider: HouseholdID,
+    pub provider_kind: Name,
+    pub receiver: HouseholdID,
+    pub resource: Resource,
+    pub amount: f32,
+    pub location: RoughLocationID,
+}
+
+pub trait LedgerRequester {
+    fn on_money_flows(&mut self, flows: &CVec<MoneyFlow>, n_entries: u32, world: &mut World);
+    fn on_supply_chain(
+        &mut self,
+        household: HouseholdID,
+        resource: Resource,
+        links: &CVec<SupplyLink>,
+        world: &mut World,
+    );
+}
+
+// Optionally keeps a record of every executed deal, for auditing the economy
+#[derive(Compact, Clone)]
+pub struct Ledger {
+    id: LedgerID,
+    enabled: bool,
+    current_instant: Instant,
+    entries: CVec<LedgerEntry>,
+    household_kinds: CHashMap<HouseholdID, Name>,
+    // still named in the entries, forgotten once no entry names them anymore
+    gone_households: CVec<HouseholdID>,
+}
+
+impl Ledger {
+    pub fn spawn(id: LedgerID, _: &mut World) -> Ledger {
+        Ledger {
+            id,
+            enabled: false,
+            current_instant: Instant::new(0),
+            entries: CVec::new(),
+            household_kinds: CHashMap::new(),
+            gone_households: CVec::new(),
+        }
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool, _: &mut World) {
+        if enabled && !self.enabled {
+            self.entries = CVec::new();
+            self.forget_gone_households();
+        }
+        self.enabled = enabled;
+    }
+
+    // kinds are remembered even while disabled, to be able to name all households later
+    pub fn on_household_kind(&mut self, household: HouseholdID, kind: Name, _: &mut World) {
+        self.household_kinds.insert(household, kind);
+    }
+
+    pub fn on_household_gone(&mut self, household: HouseholdID, _: &mut World) {
+        if self.is_named_in_entries(household) {
+            self.gone_households.push(household);
+        } else {
+            self.household_kinds.remove(household);
+        }
+    }
+
+    fn is_named_in_entries(&self, household: HouseholdID) -> bool {
+        self.entries
+            .iter()
+            .any(|entry| entry.provider == household || entry.receiver == household)
+    }
+
+    fn forget_gone_households(&mut self) {
+        let (still_named, forgotten): (Vec<_>, Vec<_>) = self
+            .gone_households
+            .iter()
+            .cloned()
+            .partition(|household| self.is_named_in_entries(*household));
+        for household in forgotten {
+            self.household_kinds.remove(household);
+        }
+        self.gone_households = still_named.into_iter().collect();
+    }
+
+    pub fn record(
+        &mut self,
+        provider: HouseholdID,
+        receiver: HouseholdID,
+        delta: &Inventory,
+        location: RoughLocationID,
+        _: &mut World,
+    ) {
+        if !self.enabled || delta.is_empty() {
+            return;
+        }
+
+        if self.entries.len() >= MAX_ENTRIES {
+            self.entries = self.entries[MAX_ENTRIES / 2..].iter().cloned().collect();
+            self.forget_gone_households();
+        }
+
+        self.entries.push(LedgerEntry {
+            instant: self.current_instant,
+            provider,
+            receiver,
+            delta: delta.clone(),
+            location,
+        });
+    }
+
+    fn kind_of(&self, household: HouseholdID) -> Name {
+        self.household_kinds
+            .get(household)
+            .cloned()
+            .unwrap_or_else(|| Name::from("Unknown").unwrap())
+    }
+
+    pub fn get_money_flows(&mut self, requester: LedgerRequesterID, world: &mut World) {
+        // money paid by receivers to providers, so undone deals cancel out
+        let mut paid = HashMap::<(Name, Name), f32>::new();
+
+        for entry in self.entries.iter() {
+            let money = entry.delta.get(Resource::Money).cloned().unwrap_or(0.0);
+            if money != 0.0 {
+                *paid
+                    .entry((self.kind_of(entry.provider), self.kind_of(entry.receiver)))
+                    .or_insert(0.0) -= money;
+            }
+        }
+
+        let mut flows = paid
+            .into_iter()
+            .map(|((provider_kind, receiver_kind), amount)| {
+                if amount >= 0.0 {
+                    MoneyFlow {
+                        from: receiver_kind,
+                        to: provider_kind,
+                        amount,
+                    }
+                } else {
+                    MoneyFlow {
+                        from: provider_kind,
+                        to: receiver_kind,
+                        amount: -amount,
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+        flows.sort_by(|a, b| (a.from, a.to).cmp(&(b.from, b.to)));
+
+        requester.on_money_flows(
+            flows.into_iter().collect(),
+            self.entries.len() as u32,
+            world,
+        );
+    }
+
+    // follows goods received by a household back to where the provider's own supplies came
+    // from, e.g. from bread to the flour of the bakery and the grain of the mill
+    pub fn trace_supply_chain(
+        &mut self,
+        household: HouseholdID,
+        resource: Resource,
+        requester: LedgerRequesterID,
+        world: &mut World,
+    ) {
+        let mut links = Vec::new();
+        let mut visited = HashSet::new();
+        let mut frontier = vec![(household, Some(resource), self.current_instant)];
+
+        for depth in 0..MAX_TRACE_DEPTH {
+            let mut next_frontier = Vec::new();
+
+            for (receiver, maybe_resource, before) in frontier {
+                if !visited.insert((receiver, maybe_resource)) {
+                    continue;
+                }
+
+                // the latest few deliveries of each relevant resource before the given instant
+                let mut n_per_resource = HashMap::<Resource, usize>::new();
+
+                for entry in self.entries.iter().rev() {
+                    if entry.receiver != receiver || entry.instant > before {
+                        continue;
+                    }
+
+                    for &Entry(entry_resource, amount) in entry.delta.iter() {
+                        let relevant = match maybe_resource {
+                            Some(resource) => entry_resource == resource,
+                            None => entry_resource != Resource::Money,
+                        };
+                        let n_traced = n_per_resource.entry(entry_resource).or_insert(0);
+
+                        if relevant && amount > 0.0 && *n_traced < MAX_SOURCES_PER_RESOURCE {
+                            *n_traced += 1;
+                            links.push(SupplyLink {
+                                depth,
+                                instant: entry.instant,
+                                provider: entry.provider,
+                                provider_kind: self.kind_of(entry.provider),
+                                receiver,
+                                resource: entry_resource,
+                                amount,
+                                location: entry.location,
+                            });
+                            next_frontier.push((entry.provider, None, entry.instant));
+                        }
+                    }
+                }
+            }
+
+            frontier = next_frontier;
+        }
+
+        requester.on_supply_chain(household, resource, links.into_iter().collect(), world);
+    }
+}
+
+impl Temporal for Ledger {
+    fn tick(&mut self, _dt: f32, current_instant: Instant, _: &mut World) {
+        self.current_instant = current_instant;
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<Ledger>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) {
+    LedgerID::spawn(world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -85,6 +85,72 @@ struct MSG_EvaluationRequester_expect_n_results(pub Resource, pub u32);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_EvaluationRequester_on_result(pub EvaluatedSearchResult);
 
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct PriceIndexRequesterID {
+    _raw_id: RawID
+}
+
+impl Copy for PriceIndexRequesterID {}
+impl Clone for PriceIndexRequesterID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for PriceIndexRequesterID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "PriceIndexRequesterID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for PriceIndexRequesterID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for PriceIndexRequesterID {
+    fn eq(&self, other: &PriceIndexRequesterID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for PriceIndexRequesterID {}
+
+pub struct PriceIndexRequesterRepresentative;
+
+impl ActorOrActorTrait for PriceIndexRequesterRepresentative {
+    type ID = PriceIndexRequesterID;
+}
+
+impl TypedID for PriceIndexRequesterID {
+    type Target = PriceIndexRequesterRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        PriceIndexRequesterID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + PriceIndexRequester> TraitIDFrom<Act> for PriceIndexRequesterID {}
+
+impl PriceIndexRequesterID {
+    pub fn on_price_index(self, price_index: PriceIndex, world: &mut World) {
+        world.send(self.as_raw(), MSG_PriceIndexRequester_on_price_index(price_index));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<PriceIndexRequesterRepresentative>();
+        system.register_trait_message::<MSG_PriceIndexRequester_on_price_index>();
+    }
+
+    pub fn register_implementor<Act: Actor + PriceIndexRequester>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, PriceIndexRequesterRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_PriceIndexRequester_on_price_index(price_index), instance, world| {
+                instance.on_price_index(price_index, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_PriceIndexRequester_on_price_index(pub PriceIndex);
 impl Actor for Market {
     type ID = MarketID;
 
@@ -151,6 +217,14 @@ impl MarketID {
     pub fn withdraw(self, resource: Resource, offer: OfferID, world: &mut World) {
         world.send(self.as_raw(), MSG_Market_withdraw(resource, offer));
     }
+    
+    pub fn report_price(self, resource: Resource, offer: OfferID, unit_price: f32, base_unit_price: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_Market_report_price(resource, offer, unit_price, base_unit_price));
+    }
+    
+    pub fn get_price_index(self, resource: Resource, requester: PriceIndexRequesterID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Market_get_price_index(resource, requester));
+    }
 }
 
 #[derive(Copy, Clone)] #[allow(non_camel_case_types)]
@@ -161,6 +235,10 @@ struct MSG_Market_search(pub Instant, pub RoughLocationID, pub Resource, pub Eva
 struct MSG_Market_register(pub Resource, pub OfferID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Market_withdraw(pub Resource, pub OfferID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Market_report_price(pub Resource, pub OfferID, pub f32, pub f32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Market_get_price_index(pub Resource, pub PriceIndexRequesterID);
 
 
 impl Actor for TripCostEstimator {
@@ -244,6 +322,7 @@ impl Into<DistanceRequesterID> for TripCostEstimatorID {
 #[allow(unused_mut)]
 pub fn auto_setup(system: &mut ActorSystem) {
     EvaluationRequesterID::register_trait(system);
+    PriceIndexRequesterID::register_trait(system);
     
     system.add_spawner::<Market, _, _>(
         |&MSG_Market_spawn(id, ), world| {
@@ -268,6 +347,18 @@ pub fn auto_setup(system: &mut ActorSystem) {
             instance.withdraw(resource, offer, world); Fate::Live
         }, false
     );
+    
+    system.add_handler::<Market, _, _>(
+        |&MSG_Market_report_price(resource, offer, unit_price, base_unit_price), instance, world| {
+            instance.report_price(resource, offer, unit_price, base_unit_price, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Market, _, _>(
+        |&MSG_Market_get_price_index(resource, requester), instance, world| {
+            instance.get_price_index(resource, requester, world); Fate::Live
+        }, false
+    );
     LocationRequesterID::register_implementor::<TripCostEstimator>(system);
     DistanceRequesterID::register_implementor::<TripCostEstimator>(system);
     system.add_spawner::<TripCostEstimator, _, _>(
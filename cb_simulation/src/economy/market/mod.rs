@@ -31,6 +31,47 @@ impl Deal {
             .next()
             .unwrap()
     }
+
+    pub fn freight(&self) -> Option<Deal> {
+        let cargo: Inventory = self
+            .delta
+            .iter()
+            .filter(|&&Entry(resource, amount)| resource.is_bulk() && amount > 0.0)
+            .map(|&Entry(resource, amount)| (resource, amount))
+            .collect();
+
+        if cargo.is_empty() {
+            None
+        } else {
+            Some(Deal {
+                duration: self.duration,
+                delta: cargo,
+            })
+        }
+    }
+
+    pub fn without_freight(&self) -> Deal {
+        Deal {
+            duration: self.duration,
+            delta: self
+                .delta
+                .iter()
+                .filter(|&&Entry(resource, amount)| !(resource.is_bulk() && amount > 0.0))
+                .map(|&Entry(resource, amount)| (resource, amount))
+                .collect(),
+        }
+    }
+
+    pub fn reversed(&self) -> Deal {
+        Deal {
+            duration: self.duration,
+            delta: self
+                .delta
+                .iter()
+                .map(|&Entry(resource, amount)| (resource, -amount))
+                .collect(),
+        }
+    }
 }
 
 pub trait EvaluationRequester {
@@ -38,10 +79,31 @@ pub trait EvaluationRequester {
     fn on_result(&mut self, result: &EvaluatedSearchResult, world: &mut World);
 }
 
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct PriceIndex {
+    pub resource: Resource,
+    // current over initial prices of all offers, 1.0 without offers
+    pub index: f32,
+    pub mean_unit_price: Option<f32>,
+    pub n_offers: u32,
+}
+
+pub trait PriceIndexRequester {
+    fn on_price_index(&mut self, price_index: PriceIndex, world: &mut World);
+}
+
+#[derive(Copy, Clone)]
+pub struct OfferPrice {
+    offer: OfferID,
+    unit_price: f32,
+    base_unit_price: f32,
+}
+
 #[derive(Compact, Clone)]
 pub struct Market {
     id: MarketID,
     offers_by_resource: CDict<Resource, CVec<OfferID>>,
+    prices_by_resource: CDict<Resource, CVec<OfferPrice>>,
 }
 
 impl Market {
@@ -49,6 +111,7 @@ impl Market {
         Market {
             id,
             offers_by_resource: CDict::new(),
+            prices_by_resource: CDict::new(),
         }
     }
 
@@ -76,15 +139,92 @@ impl Market {
     }
 
     pub fn register(&mut self, resource: Resource, offer: OfferID, _: &mut World) {
-        self.offers_by_resource.push_at(resource, offer);
+        let is_listed = self
+            .offers_by_resource
+            .get(resource)
+            .map(|offers| offers.contains(&offer))
+            .unwrap_or(false);
+
+        if !is_listed {
+            self.offers_by_resource.push_at(resource, offer);
+        }
     }
 
     pub fn withdraw(&mut self, resource: Resource, offer: OfferID, world: &mut World) {
         if let Some(offers) = self.offers_by_resource.get_mut(resource) {
             offers.retain(|o| *o != offer);
         }
+        if let Some(prices) = self.prices_by_resource.get_mut(resource) {
+            prices.retain(|price| price.offer != offer);
+        }
         offer.household.withdrawal_confirmed(offer.idx, world);
     }
+
+    pub fn report_price(
+        &mut self,
+        resource: Resource,
+        offer: OfferID,
+        unit_price: f32,
+        base_unit_price: f32,
+        _: &mut World,
+    ) {
+        let is_listed = self
+            .offers_by_resource
+            .get(resource)
+            .map(|offers| offers.contains(&offer))
+            .unwrap_or(false);
+
+        if is_listed {
+            let new_price = OfferPrice {
+                offer,
+                unit_price,
+                base_unit_price,
+            };
+
+            if let Some(prices) = self.prices_by_resource.get_mut(resource) {
+                if let Some(price) = prices.iter_mut().find(|price| price.offer == offer) {
+                    *price = new_price;
+                    return;
+                }
+            }
+
+            self.prices_by_resource.push_at(resource, new_price);
+        }
+    }
+
+    pub fn get_price_index(
+        &mut self,
+        resource: Resource,
+        requester: PriceIndexRequesterID,
+        world: &mut World,
+    ) {
+        let prices = self
+            .prices_by_resource
+            .get(resource)
+            .map(|prices| prices.iter().cloned().collect::<Vec<_>>())
+            .unwrap_or_else(Vec::new);
+
+        let total_price: f32 = prices.iter().map(|price| price.unit_price).sum();
+        let total_base_price: f32 = prices.iter().map(|price| price.base_unit_price).sum();
+
+        requester.on_price_index(
+            PriceIndex {
+                resource,
+                index: if total_base_price > 0.0 {
+                    total_price / total_base_price
+                } else {
+                    1.0
+                },
+                mean_unit_price: if prices.is_empty() {
+                    None
+                } else {
+                    Some(total_price / prices.len() as f32)
+                },
+                n_offers: prices.len() as u32,
+            },
+            world,
+        );
+    }
 }
 
 #[derive(Compact, Clone, Debug, Serialize, Deserialize)]
@@ -92,6 +232,8 @@ pub struct EvaluatedDeal {
     pub offer: OfferID,
     pub deal: Deal,
     pub opening_hours: TimeOfDayRange,
+    // known once the trip to the offer was estimated
+    pub travel_distance: Option<f32>,
 }
 
 #[derive(Compact, Clone)]
@@ -206,6 +348,7 @@ impl DistanceRequester for TripCostEstimator {
                         new_deal.deal.duration += estimated_travel_time;
                         new_deal.opening_hours =
                             new_deal.opening_hours.earlier_by(estimated_travel_time);
+                        new_deal.travel_distance = Some(distance);
                         // TODO: adjust resources to incorporate travel costs
                         new_deal
                     })
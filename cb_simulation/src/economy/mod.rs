@@ -6,16 +6,24 @@ use planning::CBPlanManagerID;
 pub mod resources;
 pub mod market;
 pub mod households;
+pub mod freight;
+pub mod treasury;
+pub mod ledger;
 pub mod immigration_and_development;
 
 pub fn setup(system: &mut ActorSystem) {
     market::setup(system);
     households::setup(system);
+    freight::setup(system);
+    treasury::setup(system);
+    ledger::setup(system);
     immigration_and_development::setup(system);
 }
 
 pub fn spawn(world: &mut World, time: TimeID, plan_manager: CBPlanManagerID) {
     market::spawn(world);
     households::spawn(world);
+    treasury::spawn(world);
+    ledger::spawn(world);
     immigration_and_development::spawn(world, time, plan_manager);
 }
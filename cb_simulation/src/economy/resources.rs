@@ -2,8 +2,8 @@
 pub enum Resource {
     Wakefulness,
     Satiety,
-    //Entertainment,
-    //Services,
+    Entertainment,
+    Services,
     Money,
     Groceries,
     Produce,
@@ -12,11 +12,11 @@ pub enum Resource {
     BakedGoods,
     Meat,
     DairyGoods,
-    /* Wood,
-     *Furniture,
-     *TextileGoods,
-     *Clothes,
-     *Devices, */
+    Wood,
+    Furniture,
+    TextileGoods,
+    Clothes,
+    Devices,
 }
 
 use self::Resource::*;
@@ -32,8 +32,8 @@ impl Resource {
         match self {
             Wakefulness => "How much energy a person has.",
             Satiety => "How little hungry a person is.",
-            // Entertainment => "How entertained a person is.",
-            // Services => "How many services a person or business needs.",
+            Entertainment => "How entertained a person is.",
+            Services => "How many services a person or business needs.",
             Money => "Money.",
             Groceries => "Mixed food for daily consumption.",
             Produce => "Agricultural fruits & vegeteables produce",
@@ -42,11 +42,18 @@ impl Resource {
             BakedGoods => "Baked Goods",
             Meat => "Meat",
             DairyGoods => "Dairy Goods",
-            /* Wood => "Wood",
-             * Furniture => "Furniture",
-             * TextileGoods => "Textile Goods",
-             * Clothes => "Clothes",
-             * Devices => "Devices", */
+            Wood => "Sawn timber",
+            Furniture => "Furniture",
+            TextileGoods => "Woven and spun textiles",
+            Clothes => "Clothes",
+            Devices => "Electronic devices",
+        }
+    }
+
+    pub fn is_bulk(self) -> bool {
+        match self {
+            Produce | Grain | Flour | BakedGoods | Meat | DairyGoods | Wood | TextileGoods => true,
+            _ => false,
         }
     }
 }
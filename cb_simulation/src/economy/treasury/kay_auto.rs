@@ -0,0 +1,166 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct TreasuryInfoRequesterID {
+    _raw_id: RawID
+}
+
+impl Copy for TreasuryInfoRequesterID {}
+impl Clone for TreasuryInfoRequesterID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for TreasuryInfoRequesterID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "TreasuryInfoRequesterID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for TreasuryInfoRequesterID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for TreasuryInfoRequesterID {
+    fn eq(&self, other: &TreasuryInfoRequesterID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for TreasuryInfoRequesterID {}
+
+pub struct TreasuryInfoRequesterRepresentative;
+
+impl ActorOrActorTrait for TreasuryInfoRequesterRepresentative {
+    type ID = TreasuryInfoRequesterID;
+}
+
+impl TypedID for TreasuryInfoRequesterID {
+    type Target = TreasuryInfoRequesterRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        TreasuryInfoRequesterID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + TreasuryInfoRequester> TraitIDFrom<Act> for TreasuryInfoRequesterID {}
+
+impl TreasuryInfoRequesterID {
+    pub fn on_treasury_info(self, info: TreasuryInfo, world: &mut World) {
+        world.send(self.as_raw(), MSG_TreasuryInfoRequester_on_treasury_info(info));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<TreasuryInfoRequesterRepresentative>();
+        system.register_trait_message::<MSG_TreasuryInfoRequester_on_treasury_info>();
+    }
+
+    pub fn register_implementor<Act: Actor + TreasuryInfoRequester>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, TreasuryInfoRequesterRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_TreasuryInfoRequester_on_treasury_info(info), instance, world| {
+                instance.on_treasury_info(info, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TreasuryInfoRequester_on_treasury_info(pub TreasuryInfo);
+impl Actor for CityTreasury {
+    type ID = CityTreasuryID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct CityTreasuryID {
+    _raw_id: RawID
+}
+
+impl Copy for CityTreasuryID {}
+impl Clone for CityTreasuryID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for CityTreasuryID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "CityTreasuryID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for CityTreasuryID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for CityTreasuryID {
+    fn eq(&self, other: &CityTreasuryID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for CityTreasuryID {}
+
+impl TypedID for CityTreasuryID {
+    type Target = CityTreasury;
+
+    fn from_raw(id: RawID) -> Self {
+        CityTreasuryID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl CityTreasuryID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = CityTreasuryID::from_raw(world.allocate_instance_id::<CityTreasury>());
+        let swarm = world.local_broadcast::<CityTreasury>();
+        world.send(swarm, MSG_CityTreasury_spawn(id, ));
+        id
+    }
+    
+    pub fn receive_rent(self, amount: f32, world: &mut World) {
+        world.send(self.as_raw(), MSG_CityTreasury_receive_rent(amount));
+    }
+    
+    pub fn get_treasury_info(self, requester: TreasuryInfoRequesterID, world: &mut World) {
+        world.send(self.as_raw(), MSG_CityTreasury_get_treasury_info(requester));
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_CityTreasury_spawn(pub CityTreasuryID, );
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_CityTreasury_receive_rent(pub f32);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_CityTreasury_get_treasury_info(pub TreasuryInfoRequesterID);
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    TreasuryInfoRequesterID::register_trait(system);
+    system.add_spawner::<CityTreasury, _, _>(
+        |&MSG_CityTreasury_spawn(id, ), world| {
+            CityTreasury::spawn(id, world)
+        }, false
+    );
+    
+    system.add_handler::<CityTreasury, _, _>(
+        |&MSG_CityTreasury_receive_rent(amount), instance, world| {
+            instance.receive_rent(amount, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<CityTreasury, _, _>(
+        |&MSG_CityTreasury_get_treasury_info(requester), instance, world| {
+            instance.get_treasury_info(requester, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
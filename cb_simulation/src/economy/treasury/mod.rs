@@ -0,0 +1,56 @@
+use kay::{ActorSystem, World};
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct TreasuryInfo {
+    pub balance: f32,
+    pub rent_collected: f32,
+}
+
+pub trait TreasuryInfoRequester {
+    fn on_treasury_info(&mut self, info: TreasuryInfo, world: &mut World);
+}
+
+// The city as landlord of all units, collecting their rent
+#[derive(Compact, Clone)]
+pub struct CityTreasury {
+    id: CityTreasuryID,
+    balance: f32,
+    rent_collected: f32,
+}
+
+impl CityTreasury {
+    pub fn spawn(id: CityTreasuryID, _: &mut World) -> CityTreasury {
+        CityTreasury {
+            id,
+            balance: 0.0,
+            rent_collected: 0.0,
+        }
+    }
+
+    pub fn receive_rent(&mut self, amount: f32, _: &mut World) {
+        self.balance += amount;
+        self.rent_collected += amount;
+    }
+
+    pub fn get_treasury_info(&mut self, requester: TreasuryInfoRequesterID, world: &mut World) {
+        requester.on_treasury_info(
+            TreasuryInfo {
+                balance: self.balance,
+                rent_collected: self.rent_collected,
+            },
+            world,
+        );
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<CityTreasury>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World) {
+    CityTreasuryID::spawn(world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -25,6 +25,10 @@ pub fn ideal_lot_shape(building_style: BuildingStyle) -> (N, N, N) {
         BuildingStyle::Mill => (20.0, 30.0, 0.5),
         BuildingStyle::Field => (50.0, 100.0, 0.1),
         BuildingStyle::NeighboringTownConnection => (5.0, 5.0, 0.1),
+        BuildingStyle::Sawmill | BuildingStyle::TextileMill => (30.0, 40.0, 0.5),
+        BuildingStyle::FurnitureWorkshop => (20.0, 30.0, 0.5),
+        BuildingStyle::Tailor | BuildingStyle::ElectronicsStore => (15.0, 20.0, 0.5),
+        BuildingStyle::EntertainmentVenue => (25.0, 30.0, 0.5),
     }
 }
 
@@ -119,7 +123,7 @@ pub fn build_building(
             building_rule.collect_geometry(&mut collector, lot, architecture_rules)?;
             collector.into_geometry()
         }
-        BuildingStyle::GroceryShop => {
+        BuildingStyle::GroceryShop | BuildingStyle::Tailor | BuildingStyle::ElectronicsStore => {
             let height = 3.0 + rng.gen::<f32>();
             let entrance_height = height - 0.7;
             let business_color = [
@@ -224,7 +228,10 @@ pub fn build_building(
                 props: HashMap::new(),
             }
         }
-        BuildingStyle::Mill => {
+        BuildingStyle::Mill
+        | BuildingStyle::Sawmill
+        | BuildingStyle::FurnitureWorkshop
+        | BuildingStyle::TextileMill => {
             let height = 3.0 + rng.gen::<f32>();
             let tower_height = 5.0 + rng.gen::<f32>();
 
@@ -271,7 +278,7 @@ pub fn build_building(
                 .collect(),
             }
         }
-        BuildingStyle::Bakery => {
+        BuildingStyle::Bakery | BuildingStyle::EntertainmentVenue => {
             let height = 3.0 + rng.gen::<f32>();
             let entrance_height = height;
             let business_color = [
@@ -82,9 +82,17 @@ impl BuildingID {
         world.send(self.as_raw(), MSG_Building_get_ui_info(requester));
     }
     
+    pub fn report_attachment_for_validation(self, validator: NetworkValidatorID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Building_report_attachment_for_validation(validator));
+    }
+    
     pub fn reconnect(self, new_location: PreciseLocation, new_connection_point: P2, world: &mut World) {
         world.send(self.as_raw(), MSG_Building_reconnect(new_location, new_connection_point));
     }
+    
+    pub fn reconnect_bike_lane(self, new_location: PreciseLocation, world: &mut World) {
+        world.send(self.as_raw(), MSG_Building_reconnect_bike_lane(new_location));
+    }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
@@ -100,7 +108,11 @@ struct MSG_Building_finally_destroy();
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Building_get_ui_info(pub LandUseUIID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Building_report_attachment_for_validation(pub NetworkValidatorID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Building_reconnect(pub PreciseLocation, pub P2);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Building_reconnect_bike_lane(pub PreciseLocation);
 
 impl Into<ConstructableID<CBPrototypeKind>> for BuildingID {
     fn into(self) -> ConstructableID<CBPrototypeKind> {
@@ -126,6 +138,12 @@ impl Into<RoughLocationID> for BuildingID {
     }
 }
 
+impl Into<ParkingRequesterID> for BuildingID {
+    fn into(self) -> ParkingRequesterID {
+        ParkingRequesterID::from_raw(self.as_raw())
+    }
+}
+
 #[allow(unused_variables)]
 #[allow(unused_mut)]
 pub fn auto_setup(system: &mut ActorSystem) {
@@ -134,6 +152,7 @@ pub fn auto_setup(system: &mut ActorSystem) {
     AttacheeID::register_implementor::<Building>(system);
     SleeperID::register_implementor::<Building>(system);
     RoughLocationID::register_implementor::<Building>(system);
+    ParkingRequesterID::register_implementor::<Building>(system);
     system.add_spawner::<Building, _, _>(
         |&MSG_Building_spawn(id, style, ref lot), world| {
             Building::spawn(id, style, lot, world)
@@ -170,9 +189,21 @@ pub fn auto_setup(system: &mut ActorSystem) {
         }, false
     );
     
+    system.add_handler::<Building, _, _>(
+        |&MSG_Building_report_attachment_for_validation(validator), instance, world| {
+            instance.report_attachment_for_validation(validator, world); Fate::Live
+        }, false
+    );
+    
     system.add_handler::<Building, _, _>(
         |&MSG_Building_reconnect(new_location, new_connection_point), instance, world| {
             instance.reconnect(new_location, new_connection_point, world); Fate::Live
         }, false
     );
+    
+    system.add_handler::<Building, _, _>(
+        |&MSG_Building_reconnect_bike_lane(new_location), instance, world| {
+            instance.reconnect_bike_lane(new_location, world); Fate::Live
+        }, false
+    );
 }
\ No newline at end of file
@@ -13,7 +13,12 @@ pub mod rendering;
 pub mod architecture;
 
 use economy::households::HouseholdID;
+use economy::households::satisfaction::Area;
 use transport::pathfinding::PreciseLocation;
+use transport::pathfinding::trip::TripID;
+use transport::parking::{ParkingSpaces, ParkingRequester, ParkingRequesterID, ParkingTicket};
+use transport::parking::MAX_PARKING_SEARCH_HOPS;
+use transport::validation::NetworkValidatorID;
 use economy::immigration_and_development::ImmigrationManagerID;
 use land_use::zone_planning::{Lot, LandUse};
 use super::ui::{LandUseUIID};
@@ -24,7 +29,7 @@ const LOG_T: &str = "Buildings";
 #[derive(Copy, Clone)]
 pub struct Unit(Option<HouseholdID>, UnitType);
 
-#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub enum UnitType {
     Dwelling,
     Retail,
@@ -32,6 +37,12 @@ pub enum UnitType {
     Mill,
     Bakery,
     NeighboringTownTrade,
+    Sawmill,
+    FurnitureWorkshop,
+    TextileMill,
+    Tailor,
+    ElectronicsStore,
+    EntertainmentVenue,
 }
 
 #[derive(Copy, Clone)]
@@ -45,6 +56,12 @@ pub enum BuildingStyle {
     Mill,
     Bakery,
     NeighboringTownConnection,
+    Sawmill,
+    FurnitureWorkshop,
+    TextileMill,
+    Tailor,
+    ElectronicsStore,
+    EntertainmentVenue,
 }
 
 impl BuildingStyle {
@@ -55,6 +72,12 @@ impl BuildingStyle {
             (BuildingStyle::Mill, LandUse::Commercial) => true,
             (BuildingStyle::Bakery, LandUse::Commercial) => true,
             (BuildingStyle::Field, LandUse::Agricultural) => true,
+            (BuildingStyle::Sawmill, LandUse::Industrial) => true,
+            (BuildingStyle::FurnitureWorkshop, LandUse::Industrial) => true,
+            (BuildingStyle::TextileMill, LandUse::Industrial) => true,
+            (BuildingStyle::Tailor, LandUse::Commercial) => true,
+            (BuildingStyle::ElectronicsStore, LandUse::Commercial) => true,
+            (BuildingStyle::EntertainmentVenue, LandUse::Commercial) => true,
             _ => false,
         }
     }
@@ -66,7 +89,11 @@ pub struct Building {
     units: CVec<Unit>,
     lot: Lot,
     pub location: Option<PreciseLocation>,
+    // where cyclists leave and arrive, if there is a bike lane in front of the lot
+    pub bike_location: Option<PreciseLocation>,
     style: BuildingStyle,
+    parking: ParkingSpaces,
+    parked_on_street: CVec<LaneID>,
     being_destroyed_for: COption<CBConstructionID>,
     started_reconnect: bool,
 }
@@ -85,12 +112,22 @@ impl Building {
             world,
         );
 
+        let units = units_for_style(style);
+        let n_dwellings = count_dwellings(&units);
+        if n_dwellings > 0 {
+            ImmigrationManagerID::local_first(world)
+                .on_vacant_dwellings_changed(n_dwellings, world);
+        }
+
         Building {
             id,
-            units: units_for_style(style),
+            units,
             lot: lot.clone(),
             location: None,
+            bike_location: None,
             style,
+            parking: parking_for_style(style),
+            parked_on_street: CVec::new(),
             being_destroyed_for: COption(None),
             started_reconnect: false,
         }
@@ -127,6 +164,24 @@ impl Building {
 
     pub fn add_household(&mut self, household: HouseholdID, unit: UnitIdx, world: &mut World) {
         self.units[unit.0].0 = Some(household);
+        let unit_type = self.units[unit.0].1;
+        if unit_type == UnitType::Dwelling {
+            ImmigrationManagerID::local_first(world).on_vacant_dwellings_changed(-1, world);
+        }
+        household.set_upkeep(
+            rent_for_unit_type(unit_type),
+            operating_costs_for_unit_type(unit_type),
+            world,
+        );
+        household.set_home_area(Area::containing(self.lot.center_point()), world);
+        let lot_area: f32 = self
+            .lot
+            .area
+            .primitives
+            .iter()
+            .map(|primitive| primitive.area().abs())
+            .sum();
+        household.set_storage_capacities(lot_area / self.units.len() as f32, world);
         // Refresh appearance
         rendering::on_destroy(self.id, world);
         rendering::on_add(self.id, &self.lot, self.all_households(), self.style, world);
@@ -139,6 +194,9 @@ impl Building {
             .position(|&Unit(user, _)| user == Some(household))
             .expect("Tried to remove a household not in the building");
         self.units[position].0 = None;
+        if self.units[position].1 == UnitType::Dwelling {
+            ImmigrationManagerID::local_first(world).on_vacant_dwellings_changed(1, world);
+        }
 
         if self.being_destroyed_for.is_some() && self.all_households().is_empty() {
             self.id.finally_destroy(world);
@@ -158,9 +216,18 @@ impl Building {
 
     pub fn finally_destroy(&mut self, world: &mut World) -> Fate {
         rendering::on_destroy(self.id, world);
+        // all units are vacant by now
+        let n_dwellings = count_dwellings(&self.units);
+        if n_dwellings > 0 {
+            ImmigrationManagerID::local_first(world)
+                .on_vacant_dwellings_changed(-n_dwellings, world);
+        }
         if let Some(location) = self.location {
             location.link.remove_attachee(self.id_as(), world);
         }
+        for lane in &self.parked_on_street {
+            lane.leave_on_street_parking(self.id_as(), world);
+        }
         self.being_destroyed_for
             .unwrap()
             .action_done(self.id.into(), world);
@@ -168,7 +235,24 @@ impl Building {
     }
 
     pub fn get_ui_info(&mut self, requester: LandUseUIID, world: &mut World) {
-        requester.on_building_ui_info(self.id, self.style, self.all_households().into(), world);
+        requester.on_building_ui_info(
+            self.id,
+            self.style,
+            self.all_households().into(),
+            self.parking,
+            self.parked_on_street.len() as u32,
+            world,
+        );
+    }
+
+    pub fn report_attachment_for_validation(
+        &mut self,
+        validator: NetworkValidatorID,
+        world: &mut World,
+    ) {
+        if self.location.is_none() {
+            validator.on_unattached_building(self.lot.center_point(), world);
+        }
     }
 }
 
@@ -211,11 +295,24 @@ use cb_time::units::Duration;
 impl Attachee for Building {
     fn location_changed(
         &mut self,
-        _old: Option<Location>,
+        old: Option<Location>,
         maybe_new: Option<Location>,
         world: &mut World,
     ) {
-        if let Some(new) = maybe_new {
+        let is_bike_location = self
+            .bike_location
+            .map_or(false, |bike_location| Some(bike_location.location) == old);
+
+        if is_bike_location {
+            if let Some(new) = maybe_new {
+                self.bike_location
+                    .as_mut()
+                    .expect("Only an existing location can change")
+                    .location = new;
+            } else {
+                self.bike_location = None;
+            }
+        } else if let Some(new) = maybe_new {
             self.location
                 .as_mut()
                 .expect("Only an existing location can change")
@@ -279,6 +376,13 @@ impl Building {
             new_location.link.add_attachee(self.id_as(), world);
         }
     }
+
+    pub fn reconnect_bike_lane(&mut self, new_location: PreciseLocation, world: &mut World) {
+        if self.bike_location.is_none() {
+            self.bike_location = Some(new_location);
+            new_location.link.add_attachee(self.id_as(), world);
+        }
+    }
 }
 
 use transport::pathfinding::{RoughLocation, RoughLocationID, RoughLocationResolve};
@@ -288,6 +392,67 @@ impl RoughLocation for Building {
     fn resolve(&self) -> RoughLocationResolve {
         RoughLocationResolve::Done(self.location, self.lot.center_point())
     }
+
+    // bike lanes and car lanes aren't connected, so there's no falling back to the road here
+    fn resolve_for_cycling(&self) -> RoughLocationResolve {
+        RoughLocationResolve::Done(self.bike_location, self.lot.center_point())
+    }
+
+    fn find_parking(&mut self, trip: TripID, instant: Instant, world: &mut World) {
+        if self.parking.try_occupy() {
+            let ticket = ParkingTicket {
+                at: self.id_as(),
+                on_street: None,
+            };
+            trip.parked(Some(ticket), instant, world);
+        } else if let Some(location) = self.location {
+            // TODO: ugly: untyped RawID shenanigans
+            let lane = LaneID::from_raw(location.link.as_raw());
+            lane.find_on_street_parking(
+                trip,
+                self.id_as(),
+                MAX_PARKING_SEARCH_HOPS,
+                instant,
+                world,
+            );
+        } else {
+            trip.parked(None, instant, world);
+        }
+    }
+
+    fn leave_parking(&mut self, on_street: Option<LaneID>, world: &mut World) {
+        if let Some(lane) = on_street {
+            if let Some(idx) = self.parked_on_street.iter().position(|&l| l == lane) {
+                self.parked_on_street.remove(idx);
+                lane.leave_on_street_parking(self.id_as(), world);
+            }
+        } else {
+            self.parking.release();
+        }
+    }
+}
+
+impl ParkingRequester for Building {
+    fn on_street_parking_found(
+        &mut self,
+        trip: TripID,
+        maybe_lane: Option<LaneID>,
+        instant: Instant,
+        world: &mut World,
+    ) {
+        let maybe_ticket = maybe_lane.map(|lane| {
+            self.parked_on_street.push(lane);
+            ParkingTicket {
+                at: self.id_as(),
+                on_street: Some(lane),
+            }
+        });
+        trip.parked(maybe_ticket, instant, world);
+    }
+
+    fn on_street_parking_gone(&mut self, lane: LaneID, _: &mut World) {
+        self.parked_on_street.retain(|&l| l != lane);
+    }
 }
 
 const FAMILIES_PER_NEIGHBORING_TOWN: usize = 5;
@@ -299,6 +464,12 @@ pub fn units_for_style(style: BuildingStyle) -> CVec<Unit> {
         BuildingStyle::Bakery => vec![Unit(None, UnitType::Bakery)],
         BuildingStyle::Mill => vec![Unit(None, UnitType::Mill)],
         BuildingStyle::Field => vec![Unit(None, UnitType::Agriculture)],
+        BuildingStyle::Sawmill => vec![Unit(None, UnitType::Sawmill)],
+        BuildingStyle::FurnitureWorkshop => vec![Unit(None, UnitType::FurnitureWorkshop)],
+        BuildingStyle::TextileMill => vec![Unit(None, UnitType::TextileMill)],
+        BuildingStyle::Tailor => vec![Unit(None, UnitType::Tailor)],
+        BuildingStyle::ElectronicsStore => vec![Unit(None, UnitType::ElectronicsStore)],
+        BuildingStyle::EntertainmentVenue => vec![Unit(None, UnitType::EntertainmentVenue)],
         BuildingStyle::NeighboringTownConnection => {
             Some(Unit(None, UnitType::NeighboringTownTrade))
                 .into_iter()
@@ -312,6 +483,65 @@ pub fn units_for_style(style: BuildingStyle) -> CVec<Unit> {
     .into()
 }
 
+fn count_dwellings(units: &[Unit]) -> i32 {
+    units
+        .iter()
+        .filter(|&&Unit(_, unit_type)| unit_type == UnitType::Dwelling)
+        .count() as i32
+}
+
+pub fn parking_for_style(style: BuildingStyle) -> ParkingSpaces {
+    match style {
+        BuildingStyle::FamilyHouse => ParkingSpaces::new(2),
+        BuildingStyle::GroceryShop => ParkingSpaces::new(8),
+        BuildingStyle::Bakery => ParkingSpaces::new(4),
+        BuildingStyle::Mill => ParkingSpaces::new(4),
+        BuildingStyle::Field => ParkingSpaces::new(2),
+        BuildingStyle::NeighboringTownConnection => ParkingSpaces::new(50),
+        BuildingStyle::Sawmill => ParkingSpaces::new(4),
+        BuildingStyle::FurnitureWorkshop => ParkingSpaces::new(4),
+        BuildingStyle::TextileMill => ParkingSpaces::new(6),
+        BuildingStyle::Tailor => ParkingSpaces::new(4),
+        BuildingStyle::ElectronicsStore => ParkingSpaces::new(8),
+        BuildingStyle::EntertainmentVenue => ParkingSpaces::new(20),
+    }
+}
+
+// daily, paid to the city as landlord
+pub fn rent_for_unit_type(unit_type: UnitType) -> f32 {
+    match unit_type {
+        UnitType::Dwelling => 30.0,
+        UnitType::Retail => 60.0,
+        UnitType::Agriculture => 40.0,
+        UnitType::Mill => 60.0,
+        UnitType::Bakery => 50.0,
+        UnitType::NeighboringTownTrade => 0.0,
+        UnitType::Sawmill => 60.0,
+        UnitType::FurnitureWorkshop => 50.0,
+        UnitType::TextileMill => 60.0,
+        UnitType::Tailor => 50.0,
+        UnitType::ElectronicsStore => 60.0,
+        UnitType::EntertainmentVenue => 80.0,
+    }
+}
+
+// daily, for utilities and maintenance of businesses
+pub fn operating_costs_for_unit_type(unit_type: UnitType) -> f32 {
+    match unit_type {
+        UnitType::Dwelling | UnitType::NeighboringTownTrade => 0.0,
+        UnitType::Retail => 40.0,
+        UnitType::Agriculture => 30.0,
+        UnitType::Mill => 50.0,
+        UnitType::Bakery => 40.0,
+        UnitType::Sawmill => 50.0,
+        UnitType::FurnitureWorkshop => 40.0,
+        UnitType::TextileMill => 50.0,
+        UnitType::Tailor => 30.0,
+        UnitType::ElectronicsStore => 40.0,
+        UnitType::EntertainmentVenue => 60.0,
+    }
+}
+
 #[derive(Compact, Clone, Default)]
 pub struct BuildingPlanResultDelta {
     buildings_to_destroy: CVec<BuildingID>,
@@ -58,8 +58,8 @@ impl LandUseUIID {
         world.send(self.as_raw(), MSG_LandUseUI_on_building_destructed(id));
     }
     
-    pub fn on_building_ui_info(self, id: BuildingID, style: BuildingStyle, households: CVec < HouseholdID >, world: &mut World) {
-        world.send(self.as_raw(), MSG_LandUseUI_on_building_ui_info(id, style, households));
+    pub fn on_building_ui_info(self, id: BuildingID, style: BuildingStyle, households: CVec < HouseholdID >, parking: ParkingSpaces, parked_on_street: u32, world: &mut World) {
+        world.send(self.as_raw(), MSG_LandUseUI_on_building_ui_info(id, style, households, parking, parked_on_street));
     }
 
     pub fn register_trait(system: &mut ActorSystem) {
@@ -84,8 +84,8 @@ impl LandUseUIID {
         );
         
         system.add_handler::<Act, _, _>(
-            |&MSG_LandUseUI_on_building_ui_info(id, style, ref households), instance, world| {
-                instance.on_building_ui_info(id, style, households, world); Fate::Live
+            |&MSG_LandUseUI_on_building_ui_info(id, style, ref households, parking, parked_on_street), instance, world| {
+                instance.on_building_ui_info(id, style, households, parking, parked_on_street, world); Fate::Live
             }, false
         );
     }
@@ -96,7 +96,7 @@ struct MSG_LandUseUI_on_building_constructed(pub BuildingID, pub Lot, pub CVec <
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_LandUseUI_on_building_destructed(pub BuildingID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_LandUseUI_on_building_ui_info(pub BuildingID, pub BuildingStyle, pub CVec < HouseholdID >);
+struct MSG_LandUseUI_on_building_ui_info(pub BuildingID, pub BuildingStyle, pub CVec < HouseholdID >, pub ParkingSpaces, pub u32);
 
 
 
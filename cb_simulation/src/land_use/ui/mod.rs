@@ -3,6 +3,7 @@ use compact::CVec;
 use super::buildings::{BuildingID, BuildingStyle};
 use economy::households::HouseholdID;
 use super::zone_planning::Lot;
+use transport::parking::ParkingSpaces;
 
 pub trait LandUseUI {
     fn on_building_constructed(
@@ -21,6 +22,8 @@ pub trait LandUseUI {
         id: BuildingID,
         style: BuildingStyle,
         households: &CVec<HouseholdID>,
+        parking: ParkingSpaces,
+        parked_on_street: u32,
         _world: &mut World,
     );
 }
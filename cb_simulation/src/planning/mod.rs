@@ -3,8 +3,9 @@ use compact::CVec;
 use transport::transport_planning::{RoadIntent, RoadPrototype};
 use land_use::zone_planning::{ZoneIntent, BuildingIntent, LotPrototype};
 use environment::vegetation::{PlantIntent, PlantPrototype};
+use transport::validation::NetworkValidatorID;
 use cb_planning::{PlanningLogic, PrototypeID, PlanningStepFn};
-use cb_planning::plan_manager::{PlanManager, PlanManagerID};
+use cb_planning::plan_manager::{PlanManager, PlanManagerID, ProjectID};
 use cb_planning::construction::{
     Construction, ConstructionID, PrototypeKind, GestureIntent, ConstructableID,
 };
@@ -23,6 +24,10 @@ impl PlanningLogic for CBPlanningLogic {
             ::environment::vegetation::calculate_prototypes,
         ]
     }
+
+    fn on_implemented(project_id: ProjectID, world: &mut World) {
+        NetworkValidatorID::global_first(world).validate_after_construction(project_id, world);
+    }
 }
 
 pub type CBPlanManager = PlanManager<CBPlanningLogic>;
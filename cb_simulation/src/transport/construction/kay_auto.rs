@@ -10,10 +10,10 @@ use super::*;
 
 
 impl LaneID {
-    pub fn spawn_and_connect(path: LinePath, on_intersection: bool, timings: CVec < bool >, report_to: CBConstructionID, world: &mut World) -> Self {
+    pub fn spawn_and_connect(path: LinePath, on_intersection: bool, timings: CVec < bool >, kind: LaneKind, report_to: CBConstructionID, world: &mut World) -> Self {
         let id = LaneID::from_raw(world.allocate_instance_id::<Lane>());
         let swarm = world.local_broadcast::<Lane>();
-        world.send(swarm, MSG_Lane_spawn_and_connect(id, path, on_intersection, timings, report_to));
+        world.send(swarm, MSG_Lane_spawn_and_connect(id, path, on_intersection, timings, kind, report_to));
         id
     }
     
@@ -21,8 +21,8 @@ impl LaneID {
         world.send(self.as_raw(), MSG_Lane_start_connecting_overlaps(lanes));
     }
     
-    pub fn connect(self, other_id: LaneID, other_start: P2, other_end: P2, other_length: N, reply_needed: bool, world: &mut World) {
-        world.send(self.as_raw(), MSG_Lane_connect(other_id, other_start, other_end, other_length, reply_needed));
+    pub fn connect(self, other_id: LaneID, other_start: P2, other_end: P2, other_length: N, other_kind: LaneKind, reply_needed: bool, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_connect(other_id, other_start, other_end, other_length, other_kind, reply_needed));
     }
     
     pub fn connect_overlaps(self, other_id: LaneID, other_path: LinePath, reply_needed: bool, world: &mut World) {
@@ -59,11 +59,11 @@ impl LaneID {
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Lane_spawn_and_connect(pub LaneID, pub LinePath, pub bool, pub CVec < bool >, pub CBConstructionID);
+struct MSG_Lane_spawn_and_connect(pub LaneID, pub LinePath, pub bool, pub CVec < bool >, pub LaneKind, pub CBConstructionID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Lane_start_connecting_overlaps(pub CVec < LaneID >);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Lane_connect(pub LaneID, pub P2, pub P2, pub N, pub bool);
+struct MSG_Lane_connect(pub LaneID, pub P2, pub P2, pub N, pub LaneKind, pub bool);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Lane_connect_overlaps(pub LaneID, pub LinePath, pub bool);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
@@ -136,8 +136,8 @@ pub fn auto_setup(system: &mut ActorSystem) {
     
     ConstructableID::<CBPrototypeKind>::register_implementor::<Lane>(system);
     system.add_spawner::<Lane, _, _>(
-        |&MSG_Lane_spawn_and_connect(id, ref path, on_intersection, ref timings, report_to), world| {
-            Lane::spawn_and_connect(id, path, on_intersection, timings, report_to, world)
+        |&MSG_Lane_spawn_and_connect(id, ref path, on_intersection, ref timings, kind, report_to), world| {
+            Lane::spawn_and_connect(id, path, on_intersection, timings, kind, report_to, world)
         }, false
     );
     
@@ -148,8 +148,8 @@ pub fn auto_setup(system: &mut ActorSystem) {
     );
     
     system.add_handler::<Lane, _, _>(
-        |&MSG_Lane_connect(other_id, other_start, other_end, other_length, reply_needed), instance, world| {
-            instance.connect(other_id, other_start, other_end, other_length, reply_needed, world); Fate::Live
+        |&MSG_Lane_connect(other_id, other_start, other_end, other_length, other_kind, reply_needed), instance, world| {
+            instance.connect(other_id, other_start, other_end, other_length, other_kind, reply_needed, world); Fate::Live
         }, false
     );
     
@@ -6,7 +6,7 @@ use descartes::{
 use itertools::Itertools;
 use ordered_float::OrderedFloat;
 
-use super::lane::{Lane, LaneID, SwitchLane, SwitchLaneID};
+use super::lane::{Lane, LaneID, LaneKind, SwitchLane, SwitchLaneID};
 use super::lane::connectivity::Interaction;
 use super::microtraffic::LaneLikeID;
 
@@ -29,13 +29,16 @@ impl RoadPrototype {
         world: &mut World,
     ) -> CVec<ConstructableID<CBPrototypeKind>> {
         match *self {
-            RoadPrototype::Lane(LanePrototype(ref path, _)) => {
-                vec![
-                    LaneID::spawn_and_connect(path.clone(), false, CVec::new(), report_to, world)
-                        .into(),
-                ]
-                .into()
-            }
+            RoadPrototype::Lane(LanePrototype(ref path, _, kind)) => vec![LaneID::spawn_and_connect(
+                path.clone(),
+                false,
+                CVec::new(),
+                kind,
+                report_to,
+                world,
+            )
+            .into()]
+            .into(),
             RoadPrototype::SwitchLane(SwitchLanePrototype(ref path)) => {
                 vec![SwitchLaneID::spawn_and_connect(path.clone(), report_to, world).into()].into()
             }
@@ -48,11 +51,12 @@ impl RoadPrototype {
                     .flat_map(|group| {
                         group
                             .iter()
-                            .map(|&LanePrototype(ref path, ref timings)| {
+                            .map(|&LanePrototype(ref path, ref timings, kind)| {
                                 LaneID::spawn_and_connect(
                                     path.clone(),
                                     true,
                                     timings.clone(),
+                                    kind,
                                     report_to,
                                     world,
                                 )
@@ -76,12 +80,36 @@ impl RoadPrototype {
 }
 
 impl Constructable<CBPrototypeKind> for Lane {
+    // morphable prototypes only differ slightly, so the lane stays connected as it is
+    // and just takes over its new path, or that of its counterpart on an intersection
     fn morph(
         &mut self,
-        _new_prototype: &Prototype<CBPrototypeKind>,
+        new_prototype: &Prototype<CBPrototypeKind>,
         report_to: CBConstructionID,
         world: &mut World,
     ) {
+        let maybe_new_path = match new_prototype.kind {
+            CBPrototypeKind::Road(RoadPrototype::Lane(LanePrototype(ref path, ..))) => {
+                Some(path.clone())
+            }
+            CBPrototypeKind::Road(RoadPrototype::Intersection(IntersectionPrototype {
+                ref connecting_lanes,
+                ..
+            })) => connecting_lanes
+                .values()
+                .flat_map(|group| group.iter())
+                .find(|&&LanePrototype(ref path, ..)| {
+                    path.rough_eq_by(&self.construction.path, 0.05)
+                })
+                .map(|&LanePrototype(ref path, ..)| path.clone()),
+            _ => None,
+        };
+
+        if let Some(new_path) = maybe_new_path {
+            self.construction.length = new_path.length();
+            self.construction.path = new_path;
+        }
+
         report_to.action_done(self.id_as(), world);
     }
     fn destruct(&mut self, report_to: CBConstructionID, world: &mut World) -> Fate {
@@ -93,10 +121,17 @@ impl Constructable<CBPrototypeKind> for Lane {
 impl Constructable<CBPrototypeKind> for SwitchLane {
     fn morph(
         &mut self,
-        _new_prototype: &Prototype<CBPrototypeKind>,
+        new_prototype: &Prototype<CBPrototypeKind>,
         report_to: CBConstructionID,
         world: &mut World,
     ) {
+        if let CBPrototypeKind::Road(RoadPrototype::SwitchLane(SwitchLanePrototype(ref path))) =
+            new_prototype.kind
+        {
+            self.construction.length = path.length();
+            self.construction.path = path.clone();
+        }
+
         report_to.action_done(self.id_as(), world);
     }
     fn destruct(&mut self, report_to: CBConstructionID, world: &mut World) -> Fate {
@@ -137,6 +172,7 @@ impl Lane {
         path: &LinePath,
         on_intersection: bool,
         timings: &CVec<bool>,
+        kind: LaneKind,
         report_to: CBConstructionID,
         world: &mut World,
     ) -> Lane {
@@ -145,14 +181,15 @@ impl Lane {
             path.start(),
             path.end(),
             path.length(),
+            kind,
             true,
             world,
         );
-        if !on_intersection {
+        if !on_intersection && kind == LaneKind::Mixed {
             SwitchLaneID::global_broadcast(world).connect_switch_to_normal(id, path.clone(), world);
         }
         report_to.action_done(id.into(), world);
-        Lane::spawn(id, path, on_intersection, timings, world)
+        Lane::spawn(id, path, on_intersection, timings, kind, world)
     }
 
     pub fn start_connecting_overlaps(&mut self, lanes: &CVec<LaneID>, world: &mut World) {
@@ -167,10 +204,11 @@ impl Lane {
         other_start: P2,
         other_end: P2,
         other_length: N,
+        other_kind: LaneKind,
         reply_needed: bool,
         world: &mut World,
     ) {
-        if other_id == self.id {
+        if other_id == self.id || other_kind != self.connectivity.kind {
             return;
         };
 
@@ -225,6 +263,7 @@ impl Lane {
                 path.start(),
                 path.end(),
                 path.length(),
+                self.connectivity.kind,
                 false,
                 world,
             );
@@ -322,7 +361,9 @@ impl Lane {
     }
 
     pub fn connect_to_switch(&mut self, other_id: SwitchLaneID, world: &mut World) {
-        other_id.connect_switch_to_normal(self.id, self.construction.path.clone(), world);
+        if self.connectivity.kind == LaneKind::Mixed {
+            other_id.connect_switch_to_normal(self.id, self.construction.path.clone(), world);
+        }
     }
 
     pub fn add_switch_lane_interaction(&mut self, interaction: Interaction, _: &mut World) {
@@ -355,6 +396,7 @@ impl Lane {
                 TripResult {
                     location_now: Some(self_as_rough_location),
                     fate: TripFate::HopDisconnected,
+                    parked_at: None,
                 },
                 world,
             );
@@ -378,6 +420,7 @@ impl Lane {
                 TripResult {
                     location_now: Some(self_as_rough_location),
                     fate: TripFate::HopDisconnected,
+                    parked_at: None,
                 },
                 world,
             );
@@ -454,12 +497,14 @@ impl Lane {
                 TripResult {
                     location_now: None,
                     fate: TripFate::LaneUnbuilt,
+                    parked_at: None,
                 },
                 world,
             );
         }
 
         ::transport::pathfinding::road_pathfinding::on_unbuild(self, world);
+        ::transport::parking::on_unbuild(self, world);
     }
 }
 
@@ -494,11 +539,15 @@ impl Lane {
                         path.project_with_max_distance(lot_position, 0.5, 3.0 * LANE_DISTANCE)
                     {
                         debug(LOG_T, format!("Projected: {}", offset), self.id(), world);
-                        building.reconnect(
-                            PreciseLocation { location, offset },
-                            projected_point,
-                            world,
-                        );
+                        let precise_location = PreciseLocation { location, offset };
+                        match self.connectivity.kind {
+                            LaneKind::Mixed => {
+                                building.reconnect(precise_location, projected_point, world)
+                            }
+                            LaneKind::BikeOnly => {
+                                building.reconnect_bike_lane(precise_location, world)
+                            }
+                        }
                     }
                 }
             }
@@ -675,6 +724,7 @@ impl SwitchLane {
                 TripResult {
                     location_now: None,
                     fate: TripFate::LaneUnbuilt,
+                    parked_at: None,
                 },
                 world,
             );
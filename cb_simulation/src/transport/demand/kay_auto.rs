@@ -0,0 +1,133 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+impl Actor for TrafficDemandGenerator {
+    type ID = TrafficDemandGeneratorID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct TrafficDemandGeneratorID {
+    _raw_id: RawID
+}
+
+impl Copy for TrafficDemandGeneratorID {}
+impl Clone for TrafficDemandGeneratorID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for TrafficDemandGeneratorID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "TrafficDemandGeneratorID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for TrafficDemandGeneratorID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for TrafficDemandGeneratorID {
+    fn eq(&self, other: &TrafficDemandGeneratorID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for TrafficDemandGeneratorID {}
+
+impl TypedID for TrafficDemandGeneratorID {
+    type Target = TrafficDemandGenerator;
+
+    fn from_raw(id: RawID) -> Self {
+        TrafficDemandGeneratorID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl TrafficDemandGeneratorID {
+    pub fn spawn(time: TimeID, world: &mut World) -> Self {
+        let id = TrafficDemandGeneratorID::from_raw(world.allocate_instance_id::<TrafficDemandGenerator>());
+        let swarm = world.local_broadcast::<TrafficDemandGenerator>();
+        world.send(swarm, MSG_TrafficDemandGenerator_spawn(id, time));
+        id
+    }
+    
+    pub fn set_demand(self, config: DemandConfig, world: &mut World) {
+        world.send(self.as_raw(), MSG_TrafficDemandGenerator_set_demand(config));
+    }
+    
+    pub fn stop(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_TrafficDemandGenerator_stop());
+    }
+    
+    pub fn add_endpoint(self, lane: LaneID, position: P2, world: &mut World) {
+        world.send(self.as_raw(), MSG_TrafficDemandGenerator_add_endpoint(lane, position));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TrafficDemandGenerator_spawn(pub TrafficDemandGeneratorID, pub TimeID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TrafficDemandGenerator_set_demand(pub DemandConfig);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TrafficDemandGenerator_stop();
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TrafficDemandGenerator_add_endpoint(pub LaneID, pub P2);
+
+impl Into<SleeperID> for TrafficDemandGeneratorID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+impl LaneID {
+    pub fn report_as_demand_endpoint(self, generator: TrafficDemandGeneratorID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_report_as_demand_endpoint(generator));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_report_as_demand_endpoint(pub TrafficDemandGeneratorID);
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    SleeperID::register_implementor::<TrafficDemandGenerator>(system);
+    system.add_spawner::<TrafficDemandGenerator, _, _>(
+        |&MSG_TrafficDemandGenerator_spawn(id, time), world| {
+            TrafficDemandGenerator::spawn(id, time, world)
+        }, false
+    );
+    
+    system.add_handler::<TrafficDemandGenerator, _, _>(
+        |&MSG_TrafficDemandGenerator_set_demand(ref config), instance, world| {
+            instance.set_demand(config, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TrafficDemandGenerator, _, _>(
+        |&MSG_TrafficDemandGenerator_stop(), instance, world| {
+            instance.stop(world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TrafficDemandGenerator, _, _>(
+        |&MSG_TrafficDemandGenerator_add_endpoint(lane, position), instance, world| {
+            instance.add_endpoint(lane, position, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_report_as_demand_endpoint(generator), instance, world| {
+            instance.report_as_demand_endpoint(generator, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
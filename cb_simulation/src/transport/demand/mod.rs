@@ -0,0 +1,262 @@
+use kay::{ActorSystem, World, TypedID, Actor};
+use compact::{CVec, COption};
+use descartes::{N, P2};
+use cb_time::actors::{Sleeper, SleeperID, TimeID};
+use cb_time::units::{Instant, Ticks, TimeOfDay, TICKS_PER_SIM_MINUTE};
+use rand::Rng;
+
+use super::lane::{Lane, LaneID, LaneKind};
+use super::pathfinding::trip::TripID;
+use super::microtraffic::VehicleType;
+
+use cb_util::log::info;
+const LOG_T: &str = "Traffic Demand";
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct DemandZone {
+    pub center: P2,
+    pub radius: N,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct DemandFlow {
+    pub origin: u32,
+    pub destination: u32,
+    pub trips_per_hour: f32,
+}
+
+// Synthetic traffic, independent of households. Rates apply as given
+// for a time of day factor of 1
+#[derive(Compact, Clone, Debug, Serialize, Deserialize)]
+pub struct DemandConfig {
+    pub zones: CVec<DemandZone>,
+    // origin-destination table, zones referred to by index
+    pub flows: CVec<DemandFlow>,
+    // trips per hour starting in each zone, towards a random other zone
+    pub generation_rates: CVec<f32>,
+    // factor for each hour of the day, all 1 if empty
+    pub time_of_day_profile: CVec<f32>,
+}
+
+impl DemandConfig {
+    fn time_of_day_factor(&self, instant: Instant) -> f32 {
+        let (hour, _) = TimeOfDay::from(instant).hours_minutes();
+        self.time_of_day_profile.get(hour).cloned().unwrap_or(1.0)
+    }
+
+    fn zone_of(&self, position: P2) -> Option<u32> {
+        self.zones
+            .iter()
+            .position(|zone| (zone.center - position).norm() <= zone.radius)
+            .map(|idx| idx as u32)
+    }
+
+    // OD flows first, then generation rates, with no fixed destination
+    fn rates(&self) -> Vec<(u32, Option<u32>, f32)> {
+        self.flows
+            .iter()
+            .map(|flow| (flow.origin, Some(flow.destination), flow.trips_per_hour))
+            .chain(
+                self.generation_rates
+                    .iter()
+                    .enumerate()
+                    .map(|(zone, rate)| (zone as u32, None, *rate)),
+            )
+            .collect()
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct DemandEndpoint {
+    zone: u32,
+    lane: LaneID,
+}
+
+#[derive(Compact, Clone)]
+pub struct TrafficDemandGenerator {
+    id: TrafficDemandGeneratorID,
+    time: TimeID,
+    config: COption<DemandConfig>,
+    running: bool,
+    endpoints: CVec<DemandEndpoint>,
+    collected_endpoints: CVec<DemandEndpoint>,
+    wakes_until_refresh: u32,
+    // fractional trips carried over between intervals, per rate
+    pending_trips: CVec<f32>,
+    spawned_trips: u32,
+}
+
+impl TrafficDemandGenerator {
+    pub fn spawn(id: TrafficDemandGeneratorID, time: TimeID, _: &mut World) -> Self {
+        TrafficDemandGenerator {
+            id,
+            time,
+            config: COption(None),
+            running: false,
+            endpoints: CVec::new(),
+            collected_endpoints: CVec::new(),
+            wakes_until_refresh: 0,
+            pending_trips: CVec::new(),
+            spawned_trips: 0,
+        }
+    }
+
+    pub fn set_demand(&mut self, config: &DemandConfig, world: &mut World) {
+        info(
+            LOG_T,
+            format!(
+                "Generating demand for {} zones, {} OD flows",
+                config.zones.len(),
+                config.flows.len()
+            ),
+            self.id,
+            world,
+        );
+
+        self.pending_trips = config.rates().iter().map(|_| 0.0).collect();
+        self.config = COption(Some(config.clone()));
+        self.endpoints = CVec::new();
+        self.collect_endpoints(world);
+        // use the collected endpoints right at the next interval
+        self.wakes_until_refresh = 1;
+
+        if !self.running {
+            self.running = true;
+            self.time.wake_up_in(
+                Ticks(DEMAND_INTERVAL_MINUTES * TICKS_PER_SIM_MINUTE),
+                self.id_as(),
+                world,
+            );
+        }
+    }
+
+    pub fn stop(&mut self, world: &mut World) {
+        info(
+            LOG_T,
+            format!("Stopped after spawning {} trips", self.spawned_trips),
+            self.id,
+            world,
+        );
+        self.config = COption(None);
+        self.endpoints = CVec::new();
+        self.collected_endpoints = CVec::new();
+        self.spawned_trips = 0;
+    }
+
+    fn collect_endpoints(&mut self, world: &mut World) {
+        self.collected_endpoints = CVec::new();
+        LaneID::global_broadcast(world).report_as_demand_endpoint(self.id, world);
+    }
+
+    pub fn add_endpoint(&mut self, lane: LaneID, position: P2, _: &mut World) {
+        if let Some(ref config) = *self.config {
+            if let Some(zone) = config.zone_of(position) {
+                self.collected_endpoints.push(DemandEndpoint { zone, lane });
+            }
+        }
+    }
+
+    fn random_lane_in(&self, zone: u32) -> Option<LaneID> {
+        let lanes = self
+            .endpoints
+            .iter()
+            .filter(|endpoint| endpoint.zone == zone)
+            .map(|endpoint| endpoint.lane)
+            .collect::<Vec<_>>();
+        ::rand::thread_rng().choose(&lanes).cloned()
+    }
+}
+
+impl Sleeper for TrafficDemandGenerator {
+    fn wake(&mut self, current_instant: Instant, world: &mut World) {
+        let config = if let Some(ref config) = *self.config {
+            config.clone()
+        } else {
+            self.running = false;
+            return;
+        };
+
+        self.wakes_until_refresh -= 1;
+        if self.wakes_until_refresh == 0 {
+            // lanes might have been built or removed since
+            self.endpoints = self.collected_endpoints.clone();
+            self.collect_endpoints(world);
+            self.wakes_until_refresh = DEMAND_ENDPOINT_REFRESH_INTERVALS;
+        }
+
+        let factor = config.time_of_day_factor(current_instant);
+        let interval_hours = DEMAND_INTERVAL_MINUTES as f32 / 60.0;
+        let n_zones = config.zones.len() as u32;
+        let mut rng = ::rand::thread_rng();
+
+        for (i, (origin, maybe_destination, trips_per_hour)) in
+            config.rates().into_iter().enumerate()
+        {
+            self.pending_trips[i] += trips_per_hour * factor * interval_hours;
+            let n_trips = self.pending_trips[i].floor();
+            self.pending_trips[i] -= n_trips;
+
+            for _ in 0..(n_trips as usize) {
+                let destination = maybe_destination.unwrap_or_else(|| {
+                    if n_zones > 1 {
+                        // any zone but the origin
+                        (origin + rng.gen_range(1, n_zones)) % n_zones
+                    } else {
+                        origin
+                    }
+                });
+
+                if let (Some(source_lane), Some(destination_lane)) = (
+                    self.random_lane_in(origin),
+                    self.random_lane_in(destination),
+                ) {
+                    if source_lane != destination_lane {
+                        TripID::spawn(
+                            source_lane.into(),
+                            destination_lane.into(),
+                            None,
+                            VehicleType::Car,
+                            current_instant,
+                            world,
+                        );
+                        self.spawned_trips += 1;
+                    }
+                }
+            }
+        }
+
+        self.time.wake_up_in(
+            Ticks(DEMAND_INTERVAL_MINUTES * TICKS_PER_SIM_MINUTE),
+            self.id_as(),
+            world,
+        );
+    }
+}
+
+impl Lane {
+    pub fn report_as_demand_endpoint(
+        &mut self,
+        generator: TrafficDemandGeneratorID,
+        world: &mut World,
+    ) {
+        if !self.connectivity.on_intersection && self.connectivity.kind == LaneKind::Mixed {
+            let path = &self.construction.path;
+            generator.add_endpoint(self.id, path.along(path.length() / 2.0), world);
+        }
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<TrafficDemandGenerator>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World, time: TimeID) {
+    TrafficDemandGeneratorID::spawn(time, world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
+
+const DEMAND_INTERVAL_MINUTES: u32 = 1;
+const DEMAND_ENDPOINT_REFRESH_INTERVALS: u32 = 60;
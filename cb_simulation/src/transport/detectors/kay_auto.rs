@@ -0,0 +1,49 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+impl LaneID {
+    pub fn add_detector_near(self, position: P2, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_add_detector_near(position));
+    }
+    
+    pub fn clear_detectors_near(self, position: P2, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_clear_detectors_near(position));
+    }
+    
+    pub fn update_detectors(self, dt: f32, instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_update_detectors(dt, instant));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_add_detector_near(pub P2);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_clear_detectors_near(pub P2);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_update_detectors(pub f32, pub Instant);
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_add_detector_near(position), instance, world| {
+            instance.add_detector_near(position, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_clear_detectors_near(position), instance, world| {
+            instance.clear_detectors_near(position, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_update_detectors(dt, instant), instance, world| {
+            instance.update_detectors(dt, instant, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,192 @@
+use kay::{ActorSystem, World};
+use compact::CVec;
+use descartes::{N, P2};
+use cb_time::units::Instant;
+
+use super::lane::{Lane, LaneID};
+use super::microtraffic::LaneCar;
+
+use dimensions::LANE_DISTANCE;
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct DetectorMeasurement {
+    pub until: Instant,
+    // passing vehicles per hour
+    pub flow: f32,
+    // time-mean speed of the passing vehicles, if any passed
+    pub mean_speed: Option<f32>,
+    // share of the interval during which a vehicle was above the loop
+    pub occupancy: f32,
+    // vehicles per km, estimated from occupancy and vehicle lengths
+    pub density: f32,
+}
+
+// a virtual induction loop at a fixed offset along a lane
+#[derive(Compact, Clone)]
+pub struct LoopDetector {
+    pub position: N,
+    elapsed: f32,
+    count: u32,
+    speed_sum: f32,
+    length_sum: f32,
+    occupied: f32,
+    pub measurements: CVec<DetectorMeasurement>,
+}
+
+impl LoopDetector {
+    pub fn new(position: N) -> Self {
+        LoopDetector {
+            position,
+            elapsed: 0.0,
+            count: 0,
+            speed_sum: 0.0,
+            length_sum: 0.0,
+            occupied: 0.0,
+            measurements: CVec::new(),
+        }
+    }
+
+    // car positions are their rear ends, as in intelligent acceleration
+    fn observe<'a, I: Iterator<Item = &'a LaneCar>>(&mut self, cars: I, dt: f32) {
+        let mut occupied = false;
+
+        for car in cars {
+            let rear = *car.position;
+            let length = car.vehicle_type.parameters().length;
+
+            if rear < self.position && rear + dt * car.velocity >= self.position {
+                self.count += 1;
+                self.speed_sum += car.velocity;
+                self.length_sum += length;
+            }
+
+            if rear < self.position + LOOP_DETECTOR_LENGTH && rear + length > self.position {
+                occupied = true;
+            }
+        }
+
+        self.elapsed += dt;
+        if occupied {
+            self.occupied += dt;
+        }
+    }
+
+    fn finish_interval(&mut self, instant: Instant) {
+        let occupancy = self.occupied / self.elapsed;
+        let mean_length = if self.count > 0 {
+            self.length_sum / self.count as f32
+        } else {
+            DEFAULT_EFFECTIVE_VEHICLE_LENGTH
+        };
+
+        let measurement = DetectorMeasurement {
+            until: instant,
+            flow: self.count as f32 * 3600.0 / self.elapsed,
+            mean_speed: if self.count > 0 {
+                Some(self.speed_sum / self.count as f32)
+            } else {
+                None
+            },
+            occupancy,
+            density: occupancy * 1000.0 / (mean_length + LOOP_DETECTOR_LENGTH),
+        };
+
+        if self.measurements.len() >= DETECTOR_HISTORY_LENGTH {
+            self.measurements.remove(0);
+        }
+        self.measurements.push(measurement);
+
+        self.elapsed = 0.0;
+        self.count = 0;
+        self.speed_sum = 0.0;
+        self.length_sum = 0.0;
+        self.occupied = 0.0;
+    }
+}
+
+#[derive(Compact, Clone, Serialize, Deserialize)]
+pub struct DetectorInfo {
+    pub location: P2,
+    pub measurements: CVec<DetectorMeasurement>,
+}
+
+impl Lane {
+    pub fn add_detector_near(&mut self, position: P2, world: &mut World) {
+        if self.connectivity.on_intersection {
+            return;
+        }
+
+        if let Some((offset, _)) =
+            self.construction
+                .path
+                .project_with_max_distance(position, 0.5, LANE_DISTANCE / 2.0)
+        {
+            self.microtraffic.detectors.push(LoopDetector::new(offset));
+            super::ui::on_detectors_changed(self, world);
+        }
+    }
+
+    pub fn clear_detectors_near(&mut self, position: P2, world: &mut World) {
+        if self.microtraffic.detectors.is_empty() {
+            return;
+        }
+
+        if let Some((offset, _)) =
+            self.construction
+                .path
+                .project_with_max_distance(position, 0.5, LANE_DISTANCE / 2.0)
+        {
+            self.microtraffic
+                .detectors
+                .retain(|detector| (detector.position - offset).abs() > DETECTOR_PICKING_TOLERANCE);
+            super::ui::on_detectors_changed(self, world);
+        }
+    }
+
+    // needs to see the cars right before they move in this tick to find crossings
+    pub fn update_detectors(&mut self, dt: f32, instant: Instant, world: &mut World) {
+        if self.microtraffic.detectors.is_empty() {
+            return;
+        }
+
+        let mut finished_interval = false;
+
+        for detector in self.microtraffic.detectors.iter_mut() {
+            detector.observe(self.microtraffic.cars.iter(), dt);
+
+            if detector.elapsed >= DETECTOR_INTERVAL {
+                detector.finish_interval(instant);
+                finished_interval = true;
+            }
+        }
+
+        if finished_interval {
+            super::ui::on_detectors_changed(self, world);
+        }
+    }
+
+    pub fn detector_infos(&self) -> CVec<DetectorInfo> {
+        self.microtraffic
+            .detectors
+            .iter()
+            .map(|detector| DetectorInfo {
+                location: self.construction.path.along(detector.position),
+                measurements: detector.measurements.clone(),
+            })
+            .collect()
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    auto_setup(system);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
+
+// in the slowed down seconds of microtraffic
+const DETECTOR_INTERVAL: f32 = 60.0;
+const DETECTOR_HISTORY_LENGTH: usize = 120;
+const LOOP_DETECTOR_LENGTH: N = 2.0;
+const DEFAULT_EFFECTIVE_VEHICLE_LENGTH: N = 4.0;
+const DETECTOR_PICKING_TOLERANCE: N = 5.0;
@@ -0,0 +1,49 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+impl LaneID {
+    pub fn add_incident_near(self, position: P2, kind: IncidentKind, length: N, from: Instant, until: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_add_incident_near(position, kind, length, from, until));
+    }
+    
+    pub fn clear_incidents_near(self, position: P2, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_clear_incidents_near(position));
+    }
+    
+    pub fn forget_expired_incidents(self, instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_forget_expired_incidents(instant));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_add_incident_near(pub P2, pub IncidentKind, pub N, pub Instant, pub Instant);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_clear_incidents_near(pub P2);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_forget_expired_incidents(pub Instant);
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_add_incident_near(position, kind, length, from, until), instance, world| {
+            instance.add_incident_near(position, kind, length, from, until, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_clear_incidents_near(position), instance, world| {
+            instance.clear_incidents_near(position, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_forget_expired_incidents(instant), instance, world| {
+            instance.forget_expired_incidents(instant, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
@@ -0,0 +1,251 @@
+use kay::{ActorSystem, World};
+use compact::CVec;
+use descartes::{N, P2, LinePath};
+use ordered_float::OrderedFloat;
+use cb_time::units::Instant;
+
+use super::lane::{Lane, LaneID};
+use super::lane::connectivity::Interaction;
+use super::microtraffic::{Obstacle, VehicleType};
+
+use dimensions::LANE_DISTANCE;
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum IncidentKind {
+    // a broken down car standing still at the start of the incident
+    StalledCar,
+    // the lane can't be passed from the start of the incident on
+    LaneClosure,
+    // cars can't drive faster than the given velocity along the incident
+    ReducedSpeed(f32),
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct Incident {
+    pub kind: IncidentKind,
+    pub start: N,
+    pub end: N,
+    pub from: Instant,
+    pub until: Instant,
+}
+
+impl Incident {
+    pub fn is_active(&self, instant: Instant) -> bool {
+        self.from <= instant && instant < self.until
+    }
+
+    pub fn is_blocking(&self) -> bool {
+        match self.kind {
+            IncidentKind::StalledCar | IncidentKind::LaneClosure => true,
+            IncidentKind::ReducedSpeed(_) => false,
+        }
+    }
+
+    pub fn as_obstacle(&self) -> Option<Obstacle> {
+        if self.is_blocking() {
+            Some(Obstacle {
+                position: OrderedFloat(self.start),
+                velocity: 0.0,
+                max_velocity: 0.0,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn speed_limit_at(&self, position: N) -> Option<f32> {
+        match self.kind {
+            IncidentKind::ReducedSpeed(speed_limit)
+                if position > self.start - SPEED_LIMIT_APPROACH_DISTANCE && position < self.end =>
+            {
+                Some(speed_limit)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Compact, Clone)]
+pub struct IncidentRenderInfo {
+    pub path: LinePath,
+    pub kind: IncidentKind,
+}
+
+impl Lane {
+    pub fn add_incident_near(
+        &mut self,
+        position: P2,
+        kind: IncidentKind,
+        length: N,
+        from: Instant,
+        until: Instant,
+        world: &mut World,
+    ) {
+        if self.connectivity.on_intersection {
+            return;
+        }
+
+        if let Some((offset, _)) =
+            self.construction
+                .path
+                .project_with_max_distance(position, 0.5, LANE_DISTANCE / 2.0)
+        {
+            let end = match kind {
+                IncidentKind::StalledCar => offset + VehicleType::Car.parameters().length,
+                _ => offset + length,
+            };
+
+            self.microtraffic.incidents.push(Incident {
+                kind,
+                start: offset,
+                end: end.min(self.construction.length),
+                from,
+                until,
+            });
+
+            super::ui::on_incidents_changed(self, world);
+        }
+    }
+
+    pub fn clear_incidents_near(&mut self, position: P2, world: &mut World) {
+        if self.microtraffic.incidents.is_empty() {
+            return;
+        }
+
+        if let Some((offset, _)) =
+            self.construction
+                .path
+                .project_with_max_distance(position, 0.5, LANE_DISTANCE / 2.0)
+        {
+            self.microtraffic.incidents.retain(|incident| {
+                offset < incident.start - INCIDENT_PICKING_TOLERANCE
+                    || offset > incident.end + INCIDENT_PICKING_TOLERANCE
+            });
+
+            super::ui::on_incidents_changed(self, world);
+        }
+    }
+
+    pub fn forget_expired_incidents(&mut self, instant: Instant, world: &mut World) {
+        let n_incidents_before = self.microtraffic.incidents.len();
+        self.microtraffic
+            .incidents
+            .retain(|incident| incident.until > instant);
+
+        if self.microtraffic.incidents.len() != n_incidents_before {
+            super::ui::on_incidents_changed(self, world);
+        }
+    }
+
+    pub fn incident_obstacles(&self, instant: Instant) -> CVec<Obstacle> {
+        self.microtraffic
+            .incidents
+            .iter()
+            .filter(|incident| incident.is_active(instant))
+            .filter_map(Incident::as_obstacle)
+            .collect()
+    }
+
+    pub fn blocked_from(&self, instant: Instant) -> Option<N> {
+        self.microtraffic
+            .incidents
+            .iter()
+            .filter(|incident| incident.is_active(instant) && incident.is_blocking())
+            .map(|incident| OrderedFloat(incident.start))
+            .min()
+            .map(|start| *start)
+    }
+
+    pub fn speed_limit_at(&self, position: N, instant: Instant) -> Option<f32> {
+        self.microtraffic
+            .incidents
+            .iter()
+            .filter(|incident| incident.is_active(instant))
+            .filter_map(|incident| incident.speed_limit_at(position))
+            .map(OrderedFloat)
+            .min()
+            .map(|speed_limit| *speed_limit)
+    }
+
+    // cars that would run into a blockage try to merge into a neighbouring lane in front of it
+    pub fn detour_around_incidents(&mut self, instant: Instant) {
+        if let Some(blocked_from) = self.blocked_from(instant) {
+            let interactions = &self.connectivity.interactions;
+
+            let maybe_detour = interactions
+                .iter()
+                .enumerate()
+                .filter_map(|(i, interaction)| match *interaction {
+                    Interaction::Switch { start, end, .. } if start < blocked_from => {
+                        Some((i as u8, end.min(blocked_from)))
+                    }
+                    _ => None,
+                })
+                .max_by_key(|&(_, usable_end)| OrderedFloat(usable_end));
+
+            if let Some((detour, usable_end)) = maybe_detour {
+                for car in self.microtraffic.cars.iter_mut() {
+                    let runs_into_blockage = match car
+                        .next_hop_interaction
+                        .map(|hop| interactions[hop as usize])
+                    {
+                        Some(Interaction::Next { .. }) => true,
+                        Some(Interaction::Switch { start, .. }) => start >= blocked_from,
+                        _ => false,
+                    };
+
+                    if runs_into_blockage && *car.position < usable_end {
+                        car.next_hop_interaction = Some(detour);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn incident_travel_cost_factor(&self, instant: Instant) -> f32 {
+        let normal_velocity = VehicleType::Car.parameters().max_velocity;
+
+        self.microtraffic
+            .incidents
+            .iter()
+            .filter(|incident| incident.is_active(instant))
+            .map(|incident| match incident.kind {
+                IncidentKind::StalledCar | IncidentKind::LaneClosure => {
+                    BLOCKED_LANE_TRAVEL_COST_FACTOR
+                }
+                IncidentKind::ReducedSpeed(speed_limit) => {
+                    let affected_share =
+                        (incident.end - incident.start) / self.construction.length.max(1.0);
+                    1.0 + (normal_velocity / speed_limit.max(1.0) - 1.0).max(0.0) * affected_share
+                }
+            })
+            .fold(1.0, f32::max)
+    }
+
+    pub fn incident_render_infos(&self) -> CVec<IncidentRenderInfo> {
+        self.microtraffic
+            .incidents
+            .iter()
+            .filter_map(|incident| {
+                self.construction
+                    .path
+                    .subsection(incident.start, incident.end)
+                    .map(|path| IncidentRenderInfo {
+                        path,
+                        kind: incident.kind,
+                    })
+            })
+            .collect()
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    auto_setup(system);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
+
+const SPEED_LIMIT_APPROACH_DISTANCE: N = 30.0;
+const INCIDENT_PICKING_TOLERANCE: N = 5.0;
+const BLOCKED_LANE_TRAVEL_COST_FACTOR: f32 = 10.0;
@@ -1,19 +1,21 @@
 use compact::CVec;
 use descartes::N;
-use super::{LaneID, SwitchLaneID};
+use super::{LaneID, SwitchLaneID, LaneKind};
 use transport::microtraffic::LaneLikeID;
 
 #[derive(Compact, Clone)]
 pub struct ConnectivityInfo {
     pub interactions: CVec<Interaction>,
     pub on_intersection: bool,
+    pub kind: LaneKind,
 }
 
 impl ConnectivityInfo {
-    pub fn new(on_intersection: bool) -> Self {
+    pub fn new(on_intersection: bool, kind: LaneKind) -> Self {
         ConnectivityInfo {
             interactions: CVec::new(),
             on_intersection,
+            kind,
         }
     }
 }
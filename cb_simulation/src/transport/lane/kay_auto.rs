@@ -55,16 +55,16 @@ impl TypedID for LaneID {
 }
 
 impl LaneID {
-    pub fn spawn(path: LinePath, on_intersection: bool, timings: CVec < bool >, world: &mut World) -> Self {
+    pub fn spawn(path: LinePath, on_intersection: bool, timings: CVec < bool >, kind: LaneKind, world: &mut World) -> Self {
         let id = LaneID::from_raw(world.allocate_instance_id::<Lane>());
         let swarm = world.local_broadcast::<Lane>();
-        world.send(swarm, MSG_Lane_spawn(id, path, on_intersection, timings));
+        world.send(swarm, MSG_Lane_spawn(id, path, on_intersection, timings, kind));
         id
     }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Lane_spawn(pub LaneID, pub LinePath, pub bool, pub CVec < bool >);
+struct MSG_Lane_spawn(pub LaneID, pub LinePath, pub bool, pub CVec < bool >, pub LaneKind);
 
 
 impl Actor for SwitchLane {
@@ -133,8 +133,8 @@ pub fn auto_setup(system: &mut ActorSystem) {
     
     
     system.add_spawner::<Lane, _, _>(
-        |&MSG_Lane_spawn(id, ref path, on_intersection, ref timings), world| {
-            Lane::spawn(id, path, on_intersection, timings, world)
+        |&MSG_Lane_spawn(id, ref path, on_intersection, ref timings, kind), world| {
+            Lane::spawn(id, path, on_intersection, timings, kind, world)
         }, false
     );
     
@@ -7,6 +7,15 @@ pub mod connectivity;
 use self::connectivity::{ConnectivityInfo, SwitchConnectivityInfo};
 use super::microtraffic::{Microtraffic, TransferringMicrotraffic};
 use super::pathfinding::PathfindingCore;
+use super::parking::{ParkingSpaces, ParkingRequesterID};
+
+// bike lanes only ever connect to other bike lanes,
+// so they form a network of their own that cars never enter
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum LaneKind {
+    Mixed,
+    BikeOnly,
+}
 
 #[derive(Compact, Clone)]
 pub struct Lane {
@@ -15,6 +24,9 @@ pub struct Lane {
     pub connectivity: ConnectivityInfo,
     pub microtraffic: Microtraffic,
     pub pathfinding: PathfindingCore,
+    pub parking: ParkingSpaces,
+    // who took the occupied spots, to tell them when the lane is gone
+    pub parked_for: CVec<ParkingRequesterID>,
 }
 
 impl Lane {
@@ -23,14 +35,17 @@ impl Lane {
         path: &LinePath,
         on_intersection: bool,
         timings: &CVec<bool>,
+        kind: LaneKind,
         world: &mut World,
     ) -> Self {
         let lane = Lane {
             id,
             construction: ConstructionInfo::from_path(path.clone()),
-            connectivity: ConnectivityInfo::new(on_intersection),
+            connectivity: ConnectivityInfo::new(on_intersection, kind),
             microtraffic: Microtraffic::new(timings.clone()),
             pathfinding: PathfindingCore::default(),
+            parking: ParkingSpaces::on_street(path.length(), on_intersection),
+            parked_for: CVec::new(),
         };
 
         super::ui::on_build(&lane, world);
@@ -1,20 +1,22 @@
-use super::Obstacle;
-
-pub const COMFORTABLE_BREAKING_DECELERATION: f32 = 0.4;
+use super::{LaneCar, Obstacle};
 
 pub fn intelligent_acceleration(
-    car: &Obstacle,
+    car: &LaneCar,
     obstacle: &Obstacle,
     safe_time_headway: f32,
 ) -> f32 {
     // http://en.wikipedia.org/wiki/Intelligent_driver_model
 
-    let car_length = 4.0;
-    let acceleration = 0.4;
-    let max_deceleration: f32 = 5.0;
+    let parameters = car.vehicle_type.parameters();
+
+    let car_length = parameters.length;
+    let acceleration = parameters.acceleration;
+    let max_deceleration = parameters.max_deceleration;
+    let comfortable_deceleration = parameters.comfortable_deceleration;
     let desired_velocity = car.max_velocity;
     let acceleration_exponent = 4.0;
-    let minimum_spacing = 4.0;
+    let minimum_spacing = parameters.minimum_spacing;
+    let safe_time_headway = safe_time_headway * parameters.safe_time_headway_factor;
 
     let net_distance = *obstacle.position - *car.position - car_length;
     let velocity_difference = car.velocity - obstacle.velocity;
@@ -23,7 +25,7 @@ pub fn intelligent_acceleration(
         + 0.0f32.max(
             car.velocity * safe_time_headway
                 + (car.velocity * velocity_difference
-                    / (2.0 * (acceleration * COMFORTABLE_BREAKING_DECELERATION).sqrt())),
+                    / (2.0 * (acceleration * comfortable_deceleration).sqrt())),
         );
 
     (-max_deceleration).max(
@@ -7,9 +7,15 @@ use std::ops::{Deref, DerefMut};
 use super::lane::{Lane, LaneID, SwitchLane, SwitchLaneID};
 use super::lane::connectivity::{Interaction};
 use super::pathfinding;
+use super::incidents::Incident;
+use super::detectors::LoopDetector;
 
 mod intelligent_acceleration;
 use self::intelligent_acceleration::intelligent_acceleration;
+mod vehicle_types;
+pub use self::vehicle_types::{VehicleType, VehicleParameters};
+pub mod trajectories;
+use self::trajectories::TrajectoryRecording;
 
 use cb_util::log::debug;
 const LOG_T: &str = "Microtraffic";
@@ -24,6 +30,10 @@ pub struct Microtraffic {
     pub green: bool,
     pub yellow_to_green: bool,
     pub yellow_to_red: bool,
+    pub relative_speed: f32,
+    pub incidents: CVec<Incident>,
+    pub trajectory_recording: Option<TrajectoryRecording>,
+    pub detectors: CVec<LoopDetector>,
 }
 
 impl Microtraffic {
@@ -35,10 +45,44 @@ impl Microtraffic {
             green: false,
             yellow_to_green: false,
             yellow_to_red: false,
+            relative_speed: 1.0,
+            incidents: CVec::new(),
+            trajectory_recording: None,
+            detectors: CVec::new(),
         }
     }
+
+    // exponentially smoothed ratio of observed to desired car speed,
+    // empty lanes slowly recover to free flow. cars held by a red signal
+    // are expected to wait and don't count as congestion
+    fn update_relative_speed(&mut self, interactions: &CVec<Interaction>) {
+        let (speed_sum, n_moving_cars) = self
+            .cars
+            .iter()
+            .filter(|car| match car.next_hop_interaction {
+                Some(hop) => match interactions[hop as usize] {
+                    Interaction::Next { green, .. } => green,
+                    _ => true,
+                },
+                None => true,
+            })
+            .fold((0.0, 0), |(sum, n), car| {
+                (sum + car.velocity / car.max_velocity, n + 1)
+            });
+
+        let relative_speed_now = if n_moving_cars == 0 {
+            1.0
+        } else {
+            speed_sum / n_moving_cars as f32
+        };
+
+        self.relative_speed +=
+            RELATIVE_SPEED_SMOOTHING * (relative_speed_now - self.relative_speed);
+    }
 }
 
+const RELATIVE_SPEED_SMOOTHING: f32 = 0.05;
+
 // makes "time pass slower" for traffic, so we can still use realistic
 // unit values while traffic happening at a slower pace to be visible
 const MICROTRAFFIC_UNREALISTIC_SLOWDOWN: f32 = 6.0;
@@ -48,6 +92,7 @@ pub struct TransferringMicrotraffic {
     pub left_obstacles: CVec<Obstacle>,
     pub right_obstacles: CVec<Obstacle>,
     pub cars: CVec<TransferringLaneCar>,
+    pub trajectory_recording: Option<TrajectoryRecording>,
 }
 
 #[derive(Copy, Clone)]
@@ -79,6 +124,7 @@ use super::pathfinding::Link;
 #[derive(Copy, Clone)]
 pub struct LaneCar {
     pub trip: TripID,
+    pub vehicle_type: VehicleType,
     pub as_obstacle: Obstacle,
     pub acceleration: f32,
     pub destination: pathfinding::PreciseLocation,
@@ -146,7 +192,7 @@ pub trait LaneLike {
 
 use self::pathfinding::StoredRoutingEntry;
 
-use cb_time::actors::{Temporal, TemporalID};
+use cb_time::actors::{Temporal, TemporalID, TimeID};
 
 const TRAFFIC_LOGIC_THROTTLING: usize = 10;
 const PATHFINDING_THROTTLING: usize = 10;
@@ -163,13 +209,7 @@ impl LaneLike for Lane {
             if car.destination.location == self_as_location
                 && *car.position >= car.destination.offset
             {
-                car.trip.finish(
-                    TripResult {
-                        location_now: None,
-                        fate: TripFate::Success(instant),
-                    },
-                    world,
-                );
+                car.trip.arrive(instant, world);
 
                 return;
             }
@@ -193,7 +233,33 @@ impl LaneLike for Lane {
                 (maybe_hop, false)
             };
 
-        if maybe_next_hop_interaction.is_some() || almost_there {
+        // the router tells all links of a cell at once, so it's only asked
+        // again when a car enters a cell or starts a trip in it
+        let use_router = self.pathfinding.router.enabled && !almost_there;
+        let maybe_router_hop_interaction = if use_router {
+            self.pathfinding
+                .router_hops
+                .get(car.destination.location.link)
+                .and_then(|&hop| self.map_connected_link_to_idx(hop))
+        } else {
+            None
+        };
+        let maybe_next_hop_interaction =
+            maybe_router_hop_interaction.or(maybe_next_hop_interaction);
+
+        // with the hierarchical router, cars keep driving until it answers with a hop
+        let ask_router = use_router && maybe_router_hop_interaction.is_none();
+
+        if maybe_next_hop_interaction.is_some() || almost_there || ask_router {
+            if ask_router {
+                pathfinding::hierarchical_routing::request_route(
+                    self,
+                    car.trip,
+                    car.destination.location,
+                    world,
+                );
+            }
+
             let routed_car = LaneCar {
                 next_hop_interaction: maybe_next_hop_interaction.map(|hop| hop as u8),
                 ..car
@@ -215,6 +281,7 @@ impl LaneLike for Lane {
                 TripResult {
                     location_now: Some(self.id_as()),
                     fate: TripFate::NoRoute,
+                    parked_at: None,
                 },
                 world,
             );
@@ -286,10 +353,21 @@ impl Temporal for Lane {
         if current_instant.ticks() % PATHFINDING_THROTTLING
             == self.id.as_raw().instance_id as usize % PATHFINDING_THROTTLING
         {
+            self.update_travel_cost_factor(current_instant);
             self.pathfinding_tick(world);
+            pathfinding::hierarchical_routing::update_router(self, world);
         }
 
         if do_traffic {
+            self.forget_expired_incidents(current_instant, world);
+            let incident_obstacles = self.incident_obstacles(current_instant);
+            let self_as_lane_like = self.id_as();
+            self.add_obstacles(&incident_obstacles, self_as_lane_like, world);
+            self.detour_around_incidents(current_instant);
+
+            self.microtraffic
+                .update_relative_speed(&self.connectivity.interactions);
+
             // TODO: optimize using BinaryHeap?
             self.microtraffic
                 .obstacles
@@ -308,6 +386,8 @@ impl Temporal for Lane {
                     .cars
                     .get(c + 1)
                     .map_or(Obstacle::far_ahead(), |car| car.as_obstacle);
+                let maybe_speed_limit =
+                    self.speed_limit_at(*self.microtraffic.cars[c].position, current_instant);
                 let car = &mut self.microtraffic.cars[c];
                 let next_car_acceleration = intelligent_acceleration(car, &next_obstacle, 2.0);
 
@@ -329,6 +409,21 @@ impl Temporal for Lane {
 
                 car.acceleration = next_car_acceleration.min(next_obstacle_acceleration);
 
+                if let Some(speed_limit) = maybe_speed_limit {
+                    let limited_car = LaneCar {
+                        as_obstacle: Obstacle {
+                            max_velocity: speed_limit.min(car.max_velocity),
+                            ..car.as_obstacle
+                        },
+                        ..*car
+                    };
+                    car.acceleration = car.acceleration.min(intelligent_acceleration(
+                        &limited_car,
+                        &Obstacle::far_ahead(),
+                        2.0,
+                    ));
+                }
+
                 if let Some(next_hop_interaction) = car.next_hop_interaction {
                     if let Interaction::Next { green, .. } =
                         self.connectivity.interactions[next_hop_interaction as usize]
@@ -349,6 +444,8 @@ impl Temporal for Lane {
             }
         }
 
+        self.update_detectors(dt, current_instant, world);
+
         for car in &mut self.microtraffic.cars {
             *car.position += dt * car.velocity;
             car.velocity = (car.velocity + dt * car.acceleration)
@@ -369,6 +466,8 @@ impl Temporal for Lane {
             }
         }
 
+        self.record_trajectories(current_instant, world);
+
         // TODO: move all iteration, updates, etc into one huge retain loop
 
         if let Some(self_as_location) = self.pathfinding.location {
@@ -376,13 +475,7 @@ impl Temporal for Lane {
                 if car.destination.location == self_as_location
                     && *car.position >= car.destination.offset
                 {
-                    car.trip.finish(
-                        TripResult {
-                            location_now: None,
-                            fate: TripFate::Success(current_instant),
-                        },
-                        world,
-                    );
+                    car.trip.arrive(current_instant, world);
 
                     false
                 } else {
@@ -391,6 +484,8 @@ impl Temporal for Lane {
             });
         }
 
+        let blocked_from = self.blocked_from(current_instant);
+
         loop {
             let maybe_switch_car: Option<(usize, LaneLikeID, f32)> = self
                 .microtraffic
@@ -407,7 +502,12 @@ impl Temporal for Lane {
                         Some(Interaction::Switch {
                             start, end, via, ..
                         }) => {
-                            if *car.position > start && *car.position > end - 300.0 {
+                            // cars in front of a blockage switch as early as they can
+                            let blocked_ahead = blocked_from
+                                .map_or(false, |blocked_from| *car.position < blocked_from);
+                            if *car.position > start
+                                && (*car.position > end - 300.0 || blocked_ahead)
+                            {
                                 Some((i, via.into(), start))
                             } else {
                                 None
@@ -642,6 +742,8 @@ impl Temporal for SwitchLane {
             }
         }
 
+        self.record_trajectories(current_instant, world);
+
         if let (Some((left, left_start, _)), Some((right, right_start, _))) =
             (self.connectivity.left, self.connectivity.right)
         {
@@ -738,6 +840,11 @@ impl Temporal for SwitchLane {
 
 pub fn setup(system: &mut ActorSystem) {
     auto_setup(system);
+    trajectories::setup(system);
+}
+
+pub fn spawn(world: &mut World, time: TimeID) {
+    trajectories::spawn(world, time);
 }
 
 fn obstacles_for_interaction(
@@ -0,0 +1,196 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+impl Actor for TrajectoryRecorder {
+    type ID = TrajectoryRecorderID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct TrajectoryRecorderID {
+    _raw_id: RawID
+}
+
+impl Copy for TrajectoryRecorderID {}
+impl Clone for TrajectoryRecorderID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for TrajectoryRecorderID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "TrajectoryRecorderID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for TrajectoryRecorderID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for TrajectoryRecorderID {
+    fn eq(&self, other: &TrajectoryRecorderID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for TrajectoryRecorderID {}
+
+impl TypedID for TrajectoryRecorderID {
+    type Target = TrajectoryRecorder;
+
+    fn from_raw(id: RawID) -> Self {
+        TrajectoryRecorderID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl TrajectoryRecorderID {
+    pub fn spawn(time: TimeID, world: &mut World) -> Self {
+        let id = TrajectoryRecorderID::from_raw(world.allocate_instance_id::<TrajectoryRecorder>());
+        let swarm = world.local_broadcast::<TrajectoryRecorder>();
+        world.send(swarm, MSG_TrajectoryRecorder_spawn(id, time));
+        id
+    }
+    
+    pub fn start_recording(self, from: Instant, duration: Duration, world: &mut World) {
+        world.send(self.as_raw(), MSG_TrajectoryRecorder_start_recording(from, duration));
+    }
+    
+    pub fn stop_recording(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_TrajectoryRecorder_stop_recording());
+    }
+    
+    pub fn record_samples(self, lane: u32, on_switch_lane: bool, instant: Instant, samples: CVec < TrajectorySample >, world: &mut World) {
+        world.send(self.as_raw(), MSG_TrajectoryRecorder_record_samples(lane, on_switch_lane, instant, samples));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TrajectoryRecorder_spawn(pub TrajectoryRecorderID, pub TimeID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TrajectoryRecorder_start_recording(pub Instant, pub Duration);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TrajectoryRecorder_stop_recording();
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TrajectoryRecorder_record_samples(pub u32, pub bool, pub Instant, pub CVec < TrajectorySample >);
+
+impl Into<SleeperID> for TrajectoryRecorderID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+impl LaneID {
+    pub fn start_recording_trajectories(self, recording: TrajectoryRecording, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_start_recording_trajectories(recording));
+    }
+    
+    pub fn stop_recording_trajectories(self, recorder: TrajectoryRecorderID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_stop_recording_trajectories(recorder));
+    }
+    
+    pub fn record_trajectories(self, instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_record_trajectories(instant));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_start_recording_trajectories(pub TrajectoryRecording);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_stop_recording_trajectories(pub TrajectoryRecorderID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_record_trajectories(pub Instant);
+
+impl SwitchLaneID {
+    pub fn start_recording_trajectories(self, recording: TrajectoryRecording, world: &mut World) {
+        world.send(self.as_raw(), MSG_SwitchLane_start_recording_trajectories(recording));
+    }
+    
+    pub fn stop_recording_trajectories(self, recorder: TrajectoryRecorderID, world: &mut World) {
+        world.send(self.as_raw(), MSG_SwitchLane_stop_recording_trajectories(recorder));
+    }
+    
+    pub fn record_trajectories(self, instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_SwitchLane_record_trajectories(instant));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_SwitchLane_start_recording_trajectories(pub TrajectoryRecording);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_SwitchLane_stop_recording_trajectories(pub TrajectoryRecorderID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_SwitchLane_record_trajectories(pub Instant);
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    SleeperID::register_implementor::<TrajectoryRecorder>(system);
+    system.add_spawner::<TrajectoryRecorder, _, _>(
+        |&MSG_TrajectoryRecorder_spawn(id, time), world| {
+            TrajectoryRecorder::spawn(id, time, world)
+        }, false
+    );
+    
+    system.add_handler::<TrajectoryRecorder, _, _>(
+        |&MSG_TrajectoryRecorder_start_recording(from, duration), instance, world| {
+            instance.start_recording(from, duration, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TrajectoryRecorder, _, _>(
+        |&MSG_TrajectoryRecorder_stop_recording(), instance, world| {
+            instance.stop_recording(world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TrajectoryRecorder, _, _>(
+        |&MSG_TrajectoryRecorder_record_samples(lane, on_switch_lane, instant, ref samples), instance, world| {
+            instance.record_samples(lane, on_switch_lane, instant, samples, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_start_recording_trajectories(recording), instance, world| {
+            instance.start_recording_trajectories(recording, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_stop_recording_trajectories(recorder), instance, world| {
+            instance.stop_recording_trajectories(recorder, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_record_trajectories(instant), instance, world| {
+            instance.record_trajectories(instant, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<SwitchLane, _, _>(
+        |&MSG_SwitchLane_start_recording_trajectories(recording), instance, world| {
+            instance.start_recording_trajectories(recording, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<SwitchLane, _, _>(
+        |&MSG_SwitchLane_stop_recording_trajectories(recorder), instance, world| {
+            instance.stop_recording_trajectories(recorder, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<SwitchLane, _, _>(
+        |&MSG_SwitchLane_record_trajectories(instant), instance, world| {
+            instance.record_trajectories(instant, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
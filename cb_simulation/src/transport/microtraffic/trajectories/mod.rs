@@ -0,0 +1,340 @@
+use kay::{ActorSystem, World, TypedID, Actor};
+use compact::CVec;
+use cb_time::actors::{Sleeper, SleeperID, TimeID};
+use cb_time::units::{Instant, Duration, TICKS_PER_SIM_SECOND};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+
+use transport::lane::{Lane, LaneID, SwitchLane, SwitchLaneID};
+use transport::pathfinding::trip::TripID;
+use super::{LaneCar, VehicleType, MICROTRAFFIC_UNREALISTIC_SLOWDOWN};
+
+use cb_util::log::{info, warn};
+const LOG_T: &str = "Trajectories";
+
+// what a lane needs to know to report its cars to an ongoing recording
+#[derive(Copy, Clone)]
+pub struct TrajectoryRecording {
+    pub recorder: TrajectoryRecorderID,
+    pub from: Instant,
+    pub until: Instant,
+}
+
+impl TrajectoryRecording {
+    pub fn is_active(&self, instant: Instant) -> bool {
+        self.from <= instant && instant < self.until
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct TrajectorySample {
+    pub trip: TripID,
+    pub vehicle_type: VehicleType,
+    pub position: f32,
+    pub velocity: f32,
+    pub acceleration: f32,
+    // lateral progress of a car transferring between lanes, from -1.0 (left) to 1.0 (right)
+    pub switch_position: Option<f32>,
+}
+
+impl TrajectorySample {
+    fn of_car(car: &LaneCar) -> TrajectorySample {
+        TrajectorySample {
+            trip: car.trip,
+            vehicle_type: car.vehicle_type,
+            position: *car.position,
+            velocity: car.velocity,
+            acceleration: car.acceleration,
+            switch_position: None,
+        }
+    }
+}
+
+// samples are kept column by column, in the same layout as the exported file
+#[derive(Compact, Clone, Default)]
+pub struct TrajectoryColumns {
+    trip: CVec<u32>,
+    vehicle_type: CVec<VehicleType>,
+    lane: CVec<u32>,
+    on_switch_lane: CVec<bool>,
+    tick: CVec<u32>,
+    position: CVec<f32>,
+    velocity: CVec<f32>,
+    acceleration: CVec<f32>,
+    switch_position: CVec<f32>,
+}
+
+impl TrajectoryColumns {
+    fn len(&self) -> usize {
+        self.tick.len()
+    }
+
+    fn push(
+        &mut self,
+        lane: u32,
+        on_switch_lane: bool,
+        instant: Instant,
+        sample: &TrajectorySample,
+    ) {
+        self.trip.push(sample.trip.as_raw().instance_id as u32);
+        self.vehicle_type.push(sample.vehicle_type);
+        self.lane.push(lane);
+        self.on_switch_lane.push(on_switch_lane);
+        self.tick.push(instant.ticks() as u32);
+        self.position.push(sample.position);
+        self.velocity.push(sample.velocity);
+        self.acceleration.push(sample.acceleration);
+        self.switch_position
+            .push(sample.switch_position.unwrap_or(0.0));
+    }
+
+    fn write_csv(&self, path: &PathBuf, from: Instant) -> ::std::io::Result<()> {
+        let mut file = BufWriter::new(File::create(path)?);
+
+        writeln!(
+            file,
+            "trip,vehicle_type,lane,on_switch_lane,tick,time,position,velocity,acceleration,\
+             switch_position"
+        )?;
+
+        // time is given in the same (slowed down) seconds that velocity and acceleration use
+        let seconds_per_tick =
+            1.0 / (TICKS_PER_SIM_SECOND as f32 * MICROTRAFFIC_UNREALISTIC_SLOWDOWN);
+
+        for i in 0..self.len() {
+            writeln!(
+                file,
+                "{},{:?},{},{},{},{},{},{},{},{}",
+                self.trip[i],
+                self.vehicle_type[i],
+                self.lane[i],
+                self.on_switch_lane[i],
+                self.tick[i],
+                (self.tick[i] as usize - from.ticks()) as f32 * seconds_per_tick,
+                self.position[i],
+                self.velocity[i],
+                self.acceleration[i],
+                self.switch_position[i]
+            )?;
+        }
+
+        file.flush()
+    }
+}
+
+#[derive(Compact, Clone)]
+pub struct TrajectoryRecorder {
+    id: TrajectoryRecorderID,
+    time: TimeID,
+    window: Option<(Instant, Instant)>,
+    columns: TrajectoryColumns,
+}
+
+impl TrajectoryRecorder {
+    pub fn spawn(id: TrajectoryRecorderID, time: TimeID, _: &mut World) -> TrajectoryRecorder {
+        TrajectoryRecorder {
+            id,
+            time,
+            window: None,
+            columns: TrajectoryColumns::default(),
+        }
+    }
+
+    pub fn start_recording(&mut self, from: Instant, duration: Duration, world: &mut World) {
+        if self.window.is_some() {
+            self.stop_recording(world);
+        }
+
+        let until = from + duration;
+        self.window = Some((from, until));
+        self.columns = TrajectoryColumns::default();
+
+        let recording = TrajectoryRecording {
+            recorder: self.id,
+            from,
+            until,
+        };
+        LaneID::global_broadcast(world).start_recording_trajectories(recording, world);
+        SwitchLaneID::global_broadcast(world).start_recording_trajectories(recording, world);
+
+        self.time.wake_up_in(duration.into(), self.id_as(), world);
+
+        info(
+            LOG_T,
+            format!(
+                "Recording trajectories from tick {} until tick {}",
+                from.ticks(),
+                until.ticks()
+            ),
+            self.id,
+            world,
+        );
+    }
+
+    pub fn stop_recording(&mut self, world: &mut World) {
+        LaneID::global_broadcast(world).stop_recording_trajectories(self.id, world);
+        SwitchLaneID::global_broadcast(world).stop_recording_trajectories(self.id, world);
+        self.finish_recording(world);
+    }
+
+    pub fn record_samples(
+        &mut self,
+        lane: u32,
+        on_switch_lane: bool,
+        instant: Instant,
+        samples: &CVec<TrajectorySample>,
+        _: &mut World,
+    ) {
+        if let Some((from, until)) = self.window {
+            if from <= instant && instant < until {
+                for sample in samples.iter() {
+                    self.columns.push(lane, on_switch_lane, instant, sample);
+                }
+            }
+        }
+    }
+
+    fn finish_recording(&mut self, world: &mut World) {
+        if let Some((from, until)) = self.window.take() {
+            let folder = PathBuf::from(TRAJECTORY_EXPORT_FOLDER);
+            let path = folder.join(format!(
+                "trajectories_{}_{}.csv",
+                from.ticks(),
+                until.ticks()
+            ));
+
+            let written = ::std::fs::create_dir_all(&folder)
+                .and_then(|_| self.columns.write_csv(&path, from));
+
+            match written {
+                Ok(()) => info(
+                    LOG_T,
+                    format!(
+                        "Exported {} trajectory samples to {}",
+                        self.columns.len(),
+                        path.display()
+                    ),
+                    self.id,
+                    world,
+                ),
+                Err(error) => warn(
+                    LOG_T,
+                    format!(
+                        "Couldn't export trajectories to {}: {}",
+                        path.display(),
+                        error
+                    ),
+                    self.id,
+                    world,
+                ),
+            }
+
+            self.columns = TrajectoryColumns::default();
+        }
+    }
+}
+
+impl Sleeper for TrajectoryRecorder {
+    fn wake(&mut self, current_instant: Instant, world: &mut World) {
+        // a recording that was stopped early and restarted keeps its own, later wake up
+        if let Some((_, until)) = self.window {
+            if current_instant >= until {
+                self.stop_recording(world);
+            }
+        }
+    }
+}
+
+impl Lane {
+    pub fn start_recording_trajectories(&mut self, recording: TrajectoryRecording, _: &mut World) {
+        self.microtraffic.trajectory_recording = Some(recording);
+    }
+
+    pub fn stop_recording_trajectories(&mut self, recorder: TrajectoryRecorderID, _: &mut World) {
+        if self
+            .microtraffic
+            .trajectory_recording
+            .map_or(false, |recording| recording.recorder == recorder)
+        {
+            self.microtraffic.trajectory_recording = None;
+        }
+    }
+
+    pub fn record_trajectories(&mut self, instant: Instant, world: &mut World) {
+        if let Some(recording) = self.microtraffic.trajectory_recording {
+            if instant >= recording.until {
+                self.microtraffic.trajectory_recording = None;
+            } else if recording.is_active(instant) && !self.microtraffic.cars.is_empty() {
+                let samples = self
+                    .microtraffic
+                    .cars
+                    .iter()
+                    .map(TrajectorySample::of_car)
+                    .collect();
+                recording.recorder.record_samples(
+                    self.id.as_raw().instance_id as u32,
+                    false,
+                    instant,
+                    samples,
+                    world,
+                );
+            }
+        }
+    }
+}
+
+impl SwitchLane {
+    pub fn start_recording_trajectories(&mut self, recording: TrajectoryRecording, _: &mut World) {
+        self.microtraffic.trajectory_recording = Some(recording);
+    }
+
+    pub fn stop_recording_trajectories(&mut self, recorder: TrajectoryRecorderID, _: &mut World) {
+        if self
+            .microtraffic
+            .trajectory_recording
+            .map_or(false, |recording| recording.recorder == recorder)
+        {
+            self.microtraffic.trajectory_recording = None;
+        }
+    }
+
+    pub fn record_trajectories(&mut self, instant: Instant, world: &mut World) {
+        if let Some(recording) = self.microtraffic.trajectory_recording {
+            if instant >= recording.until {
+                self.microtraffic.trajectory_recording = None;
+            } else if recording.is_active(instant) && !self.microtraffic.cars.is_empty() {
+                let samples = self
+                    .microtraffic
+                    .cars
+                    .iter()
+                    .map(|car| TrajectorySample {
+                        switch_position: Some(car.switch_position),
+                        ..TrajectorySample::of_car(&car.as_lane_car)
+                    })
+                    .collect();
+                recording.recorder.record_samples(
+                    self.id.as_raw().instance_id as u32,
+                    true,
+                    instant,
+                    samples,
+                    world,
+                );
+            }
+        }
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<TrajectoryRecorder>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World, time: TimeID) {
+    TrajectoryRecorderID::spawn(time, world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
+
+const TRAJECTORY_EXPORT_FOLDER: &str = "./trajectories";
@@ -0,0 +1,71 @@
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
+pub enum VehicleType {
+    Car,
+    DeliveryVan,
+    Truck,
+    Bus,
+    Bicycle,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct VehicleParameters {
+    pub length: f32,
+    pub max_velocity: f32,
+    pub acceleration: f32,
+    pub comfortable_deceleration: f32,
+    pub max_deceleration: f32,
+    pub minimum_spacing: f32,
+    pub safe_time_headway_factor: f32,
+}
+
+impl VehicleType {
+    pub fn parameters(self) -> VehicleParameters {
+        match self {
+            VehicleType::Car => VehicleParameters {
+                length: 4.0,
+                max_velocity: 8.0,
+                acceleration: 0.4,
+                comfortable_deceleration: 0.4,
+                max_deceleration: 5.0,
+                minimum_spacing: 4.0,
+                safe_time_headway_factor: 1.0,
+            },
+            VehicleType::DeliveryVan => VehicleParameters {
+                length: 5.5,
+                max_velocity: 7.5,
+                acceleration: 0.3,
+                comfortable_deceleration: 0.4,
+                max_deceleration: 4.5,
+                minimum_spacing: 4.0,
+                safe_time_headway_factor: 1.1,
+            },
+            VehicleType::Truck => VehicleParameters {
+                length: 10.0,
+                max_velocity: 6.5,
+                acceleration: 0.15,
+                comfortable_deceleration: 0.3,
+                max_deceleration: 3.5,
+                minimum_spacing: 5.0,
+                safe_time_headway_factor: 1.5,
+            },
+            VehicleType::Bus => VehicleParameters {
+                length: 12.0,
+                max_velocity: 7.0,
+                acceleration: 0.2,
+                comfortable_deceleration: 0.35,
+                max_deceleration: 4.0,
+                minimum_spacing: 5.0,
+                safe_time_headway_factor: 1.3,
+            },
+            VehicleType::Bicycle => VehicleParameters {
+                length: 1.8,
+                max_velocity: 4.5,
+                acceleration: 0.5,
+                comfortable_deceleration: 0.5,
+                max_deceleration: 3.0,
+                minimum_spacing: 1.5,
+                safe_time_headway_factor: 0.8,
+            },
+        }
+    }
+}
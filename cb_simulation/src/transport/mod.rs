@@ -5,6 +5,11 @@ pub mod ui;
 
 pub mod transport_planning;
 pub mod pathfinding;
+pub mod parking;
+pub mod incidents;
+pub mod detectors;
+pub mod validation;
+pub mod demand;
 
 use kay::{ActorSystem, World};
 use cb_time::actors::TimeID;
@@ -14,9 +19,17 @@ pub fn setup(system: &mut ActorSystem) {
     self::construction::setup(system);
     self::microtraffic::setup(system);
     self::pathfinding::setup(system);
+    self::parking::setup(system);
+    self::incidents::setup(system);
+    self::detectors::setup(system);
+    self::validation::setup(system);
+    self::demand::setup(system);
     self::ui::setup(system);
 }
 
 pub fn spawn(world: &mut World, time: TimeID) {
     self::pathfinding::spawn(world, time);
+    self::microtraffic::spawn(world, time);
+    self::validation::spawn(world, time);
+    self::demand::spawn(world, time);
 }
@@ -0,0 +1,119 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct ParkingRequesterID {
+    _raw_id: RawID
+}
+
+impl Copy for ParkingRequesterID {}
+impl Clone for ParkingRequesterID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for ParkingRequesterID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "ParkingRequesterID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for ParkingRequesterID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for ParkingRequesterID {
+    fn eq(&self, other: &ParkingRequesterID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for ParkingRequesterID {}
+
+pub struct ParkingRequesterRepresentative;
+
+impl ActorOrActorTrait for ParkingRequesterRepresentative {
+    type ID = ParkingRequesterID;
+}
+
+impl TypedID for ParkingRequesterID {
+    type Target = ParkingRequesterRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        ParkingRequesterID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + ParkingRequester> TraitIDFrom<Act> for ParkingRequesterID {}
+
+impl ParkingRequesterID {
+    pub fn on_street_parking_found(self, trip: TripID, maybe_lane: Option < LaneID >, instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_ParkingRequester_on_street_parking_found(trip, maybe_lane, instant));
+    }
+    
+    pub fn on_street_parking_gone(self, lane: LaneID, world: &mut World) {
+        world.send(self.as_raw(), MSG_ParkingRequester_on_street_parking_gone(lane));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<ParkingRequesterRepresentative>();
+        system.register_trait_message::<MSG_ParkingRequester_on_street_parking_found>();
+        system.register_trait_message::<MSG_ParkingRequester_on_street_parking_gone>();
+    }
+
+    pub fn register_implementor<Act: Actor + ParkingRequester>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, ParkingRequesterRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_ParkingRequester_on_street_parking_found(trip, maybe_lane, instant), instance, world| {
+                instance.on_street_parking_found(trip, maybe_lane, instant, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_ParkingRequester_on_street_parking_gone(lane), instance, world| {
+                instance.on_street_parking_gone(lane, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ParkingRequester_on_street_parking_found(pub TripID, pub Option < LaneID >, pub Instant);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_ParkingRequester_on_street_parking_gone(pub LaneID);
+
+
+impl LaneID {
+    pub fn find_on_street_parking(self, trip: TripID, requester: ParkingRequesterID, hops_left: u8, instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_find_on_street_parking(trip, requester, hops_left, instant));
+    }
+    
+    pub fn leave_on_street_parking(self, requester: ParkingRequesterID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_leave_on_street_parking(requester));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_find_on_street_parking(pub TripID, pub ParkingRequesterID, pub u8, pub Instant);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_leave_on_street_parking(pub ParkingRequesterID);
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    ParkingRequesterID::register_trait(system);
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_find_on_street_parking(trip, requester, hops_left, instant), instance, world| {
+            instance.find_on_street_parking(trip, requester, hops_left, instant, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_leave_on_street_parking(requester), instance, world| {
+            instance.leave_on_street_parking(requester, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
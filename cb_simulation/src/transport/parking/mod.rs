@@ -0,0 +1,132 @@
+use kay::{ActorSystem, World};
+use cb_time::units::Instant;
+
+use super::lane::{Lane, LaneID};
+use super::lane::connectivity::Interaction;
+use super::pathfinding::RoughLocationID;
+use super::pathfinding::trip::TripID;
+
+#[derive(Copy, Clone, Default, Debug, Serialize, Deserialize)]
+pub struct ParkingSpaces {
+    pub capacity: u32,
+    pub occupied: u32,
+}
+
+impl ParkingSpaces {
+    pub fn new(capacity: u32) -> Self {
+        ParkingSpaces {
+            capacity,
+            occupied: 0,
+        }
+    }
+
+    pub fn on_street(lane_length: f32, on_intersection: bool) -> Self {
+        if on_intersection {
+            ParkingSpaces::default()
+        } else {
+            ParkingSpaces::new((lane_length / ON_STREET_PARKING_SPACE_LENGTH) as u32)
+        }
+    }
+
+    pub fn try_occupy(&mut self) -> bool {
+        if self.occupied < self.capacity {
+            self.occupied += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn release(&mut self) -> bool {
+        if self.occupied > 0 {
+            self.occupied -= 1;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+// handed out when a car parks and given back when the same car leaves again,
+// so that exactly the spot it took is freed up
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct ParkingTicket {
+    pub at: RoughLocationID,
+    pub on_street: Option<LaneID>,
+}
+
+impl ParkingTicket {
+    pub fn give_back(self, world: &mut World) {
+        self.at.leave_parking(self.on_street, world);
+    }
+}
+
+pub trait ParkingRequester {
+    fn on_street_parking_found(
+        &mut self,
+        trip: TripID,
+        maybe_lane: Option<LaneID>,
+        instant: Instant,
+        world: &mut World,
+    );
+    fn on_street_parking_gone(&mut self, lane: LaneID, world: &mut World);
+}
+
+impl Lane {
+    pub fn find_on_street_parking(
+        &mut self,
+        trip: TripID,
+        requester: ParkingRequesterID,
+        hops_left: u8,
+        instant: Instant,
+        world: &mut World,
+    ) {
+        // only streets with buildings along them offer on-street parking
+        let offers_parking = !self.connectivity.on_intersection && self.pathfinding.has_attachees();
+
+        if offers_parking && self.parking.try_occupy() {
+            self.parked_for.push(requester);
+            requester.on_street_parking_found(trip, Some(self.id), instant, world);
+        } else {
+            let maybe_next = self
+                .connectivity
+                .interactions
+                .iter()
+                .filter_map(|interaction| match *interaction {
+                    Interaction::Next { next, .. } => Some(next),
+                    _ => None,
+                })
+                .next();
+
+            match maybe_next {
+                Some(next) if hops_left > 0 => {
+                    next.find_on_street_parking(trip, requester, hops_left - 1, instant, world);
+                }
+                _ => requester.on_street_parking_found(trip, None, instant, world),
+            }
+        }
+    }
+
+    pub fn leave_on_street_parking(&mut self, requester: ParkingRequesterID, _: &mut World) {
+        if let Some(idx) = self.parked_for.iter().position(|&r| r == requester) {
+            self.parked_for.remove(idx);
+            self.parking.release();
+        }
+    }
+}
+
+pub fn on_unbuild(lane: &Lane, world: &mut World) {
+    for requester in &lane.parked_for {
+        requester.on_street_parking_gone(lane.id, world);
+    }
+}
+
+const ON_STREET_PARKING_SPACE_LENGTH: f32 = 6.0;
+pub const MAX_PARKING_SEARCH_HOPS: u8 = 4;
+
+pub fn setup(system: &mut ActorSystem) {
+    auto_setup(system);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
@@ -0,0 +1,217 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+impl Actor for HierarchicalRouter {
+    type ID = HierarchicalRouterID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct HierarchicalRouterID {
+    _raw_id: RawID
+}
+
+impl Copy for HierarchicalRouterID {}
+impl Clone for HierarchicalRouterID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for HierarchicalRouterID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "HierarchicalRouterID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for HierarchicalRouterID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for HierarchicalRouterID {
+    fn eq(&self, other: &HierarchicalRouterID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for HierarchicalRouterID {}
+
+impl TypedID for HierarchicalRouterID {
+    type Target = HierarchicalRouter;
+
+    fn from_raw(id: RawID) -> Self {
+        HierarchicalRouterID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl HierarchicalRouterID {
+    pub fn spawn(time: TimeID, world: &mut World) -> Self {
+        let id = HierarchicalRouterID::from_raw(world.allocate_instance_id::<HierarchicalRouter>());
+        let swarm = world.local_broadcast::<HierarchicalRouter>();
+        world.send(swarm, MSG_HierarchicalRouter_spawn(id, time));
+        id
+    }
+    
+    pub fn set_enabled(self, enabled: bool, world: &mut World) {
+        world.send(self.as_raw(), MSG_HierarchicalRouter_set_enabled(enabled));
+    }
+    
+    pub fn update_link(self, link: LinkID, position: P2, length: f32, edges: CVec < ( LinkID , f32 ) >, world: &mut World) {
+        world.send(self.as_raw(), MSG_HierarchicalRouter_update_link(link, position, length, edges));
+    }
+    
+    pub fn remove_link(self, link: LinkID, world: &mut World) {
+        world.send(self.as_raw(), MSG_HierarchicalRouter_remove_link(link));
+    }
+    
+    pub fn route(self, from: LaneID, destination: LinkID, trip: TripID, world: &mut World) {
+        world.send(self.as_raw(), MSG_HierarchicalRouter_route(from, destination, trip));
+    }
+    
+    pub fn check_consistency(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_HierarchicalRouter_check_consistency());
+    }
+    
+    pub fn compare_routes(self, from: LaneID, gossip_routes: CVec < ( LinkID , f32 , LinkID ) >, world: &mut World) {
+        world.send(self.as_raw(), MSG_HierarchicalRouter_compare_routes(from, gossip_routes));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_HierarchicalRouter_spawn(pub HierarchicalRouterID, pub TimeID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_HierarchicalRouter_set_enabled(pub bool);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_HierarchicalRouter_update_link(pub LinkID, pub P2, pub f32, pub CVec < ( LinkID , f32 ) >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_HierarchicalRouter_remove_link(pub LinkID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_HierarchicalRouter_route(pub LaneID, pub LinkID, pub TripID);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_HierarchicalRouter_check_consistency();
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_HierarchicalRouter_compare_routes(pub LaneID, pub CVec < ( LinkID , f32 , LinkID ) >);
+
+impl Into<SleeperID> for HierarchicalRouterID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+impl LaneID {
+    pub fn set_hierarchical_routing(self, enabled: bool, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_set_hierarchical_routing(enabled));
+    }
+    
+    pub fn learn_hierarchical_route(self, destination: LinkID, hop: LinkID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_learn_hierarchical_route(destination, hop));
+    }
+    
+    pub fn forget_hierarchical_routes(self, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_forget_hierarchical_routes());
+    }
+    
+    pub fn on_hierarchical_route(self, trip: TripID, destination: LinkID, maybe_first_hop: Option < LinkID >, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_on_hierarchical_route(trip, destination, maybe_first_hop));
+    }
+    
+    pub fn report_routes_for_check(self, router: HierarchicalRouterID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_report_routes_for_check(router));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_set_hierarchical_routing(pub bool);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_learn_hierarchical_route(pub LinkID, pub LinkID);
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_forget_hierarchical_routes();
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_on_hierarchical_route(pub TripID, pub LinkID, pub Option < LinkID >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_report_routes_for_check(pub HierarchicalRouterID);
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    SleeperID::register_implementor::<HierarchicalRouter>(system);
+    system.add_spawner::<HierarchicalRouter, _, _>(
+        |&MSG_HierarchicalRouter_spawn(id, time), world| {
+            HierarchicalRouter::spawn(id, time, world)
+        }, false
+    );
+    
+    system.add_handler::<HierarchicalRouter, _, _>(
+        |&MSG_HierarchicalRouter_set_enabled(enabled), instance, world| {
+            instance.set_enabled(enabled, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<HierarchicalRouter, _, _>(
+        |&MSG_HierarchicalRouter_update_link(link, position, length, ref edges), instance, world| {
+            instance.update_link(link, position, length, edges, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<HierarchicalRouter, _, _>(
+        |&MSG_HierarchicalRouter_remove_link(link), instance, world| {
+            instance.remove_link(link, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<HierarchicalRouter, _, _>(
+        |&MSG_HierarchicalRouter_route(from, destination, trip), instance, world| {
+            instance.route(from, destination, trip, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<HierarchicalRouter, _, _>(
+        |&MSG_HierarchicalRouter_check_consistency(), instance, world| {
+            instance.check_consistency(world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<HierarchicalRouter, _, _>(
+        |&MSG_HierarchicalRouter_compare_routes(from, ref gossip_routes), instance, world| {
+            instance.compare_routes(from, gossip_routes, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_set_hierarchical_routing(enabled), instance, world| {
+            instance.set_hierarchical_routing(enabled, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_learn_hierarchical_route(destination, hop), instance, world| {
+            instance.learn_hierarchical_route(destination, hop, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_forget_hierarchical_routes(), instance, world| {
+            instance.forget_hierarchical_routes(world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_on_hierarchical_route(trip, destination, maybe_first_hop), instance, world| {
+            instance.on_hierarchical_route(trip, destination, maybe_first_hop, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_report_routes_for_check(router), instance, world| {
+            instance.report_routes_for_check(router, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
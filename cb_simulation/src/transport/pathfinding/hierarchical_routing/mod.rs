@@ -0,0 +1,697 @@
+use kay::{ActorSystem, World, TypedID, Actor};
+use compact::{CVec, CHashMap};
+use descartes::P2;
+use ordered_float::OrderedFloat;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use cb_time::actors::{Sleeper, SleeperID, TimeID};
+use cb_time::units::{Instant, Ticks};
+
+use transport::lane::{Lane, LaneID};
+use super::{Link, LinkID, Location};
+use super::trip::{TripID, TripResult, TripFate};
+
+use cb_util::log::{info, warn};
+const LOG_T: &str = "Hierarchical Routing";
+
+// A central alternative to the gossiped routing tables. The network is split into
+// square cells, each cell stores shortcuts from all of its links to its exit links,
+// so a query only searches the source and target cells locally and otherwise
+// jumps between cells. Only cells in which a link changed are rebuilt.
+// A query answers for all links of the source cell at once, so cars only
+// need to ask again when they start a trip or enter another cell.
+
+#[derive(Copy, Clone, Default)]
+pub struct RouterLinkState {
+    pub enabled: bool,
+    pub outdated: bool,
+    // the travel cost factor the router knows about
+    pub reported_cost_factor: f32,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct RouterCell(i32, i32);
+
+impl RouterCell {
+    fn containing(position: P2) -> RouterCell {
+        RouterCell(
+            (position.x / ROUTER_CELL_SIZE).floor() as i32,
+            (position.y / ROUTER_CELL_SIZE).floor() as i32,
+        )
+    }
+}
+
+#[derive(Compact, Clone)]
+pub struct RouterNode {
+    cell: RouterCell,
+    length: f32,
+    edges: CVec<(LinkID, f32)>,
+    // shortest distances to all exits of the cell, staying inside the cell
+    shortcuts: CVec<(LinkID, f32)>,
+}
+
+#[derive(Compact, Clone, Default)]
+pub struct RouterCellInfo {
+    links: CVec<LinkID>,
+    dirty: bool,
+    // destinations the links of this cell were told their next hops to
+    routed_destinations: CVec<LinkID>,
+}
+
+#[derive(Copy, Clone, Default)]
+pub struct ConsistencyReport {
+    checked: u32,
+    distance_agreed: u32,
+    hop_agreed: u32,
+    unroutable: u32,
+    max_relative_error: f32,
+}
+
+#[derive(Compact, Clone)]
+pub struct HierarchicalRouter {
+    id: HierarchicalRouterID,
+    time: TimeID,
+    enabled: bool,
+    nodes: CHashMap<LinkID, RouterNode>,
+    cells: CHashMap<RouterCell, RouterCellInfo>,
+    report: ConsistencyReport,
+}
+
+impl HierarchicalRouter {
+    pub fn spawn(id: HierarchicalRouterID, time: TimeID, _: &mut World) -> HierarchicalRouter {
+        HierarchicalRouter {
+            id,
+            time,
+            enabled: false,
+            nodes: CHashMap::new(),
+            cells: CHashMap::new(),
+            report: ConsistencyReport::default(),
+        }
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool, world: &mut World) {
+        if enabled && !self.enabled {
+            // lanes report themselves anew, also forgetting lanes removed in the meantime
+            self.nodes = CHashMap::new();
+            self.cells = CHashMap::new();
+        }
+        self.enabled = enabled;
+        LaneID::global_broadcast(world).set_hierarchical_routing(enabled, world);
+    }
+
+    pub fn update_link(
+        &mut self,
+        link: LinkID,
+        position: P2,
+        length: f32,
+        edges: &CVec<(LinkID, f32)>,
+        _: &mut World,
+    ) {
+        if !self.enabled {
+            return;
+        }
+
+        let cell = RouterCell::containing(position);
+
+        if self.nodes.get(link).is_none() {
+            let mut cell_info = self.cells.get(cell).cloned().unwrap_or_default();
+            cell_info.links.push(link);
+            self.cells.insert(cell, cell_info);
+        }
+
+        self.nodes.insert(
+            link,
+            RouterNode {
+                cell,
+                length,
+                edges: edges.clone(),
+                shortcuts: CVec::new(),
+            },
+        );
+        self.mark_dirty(cell);
+    }
+
+    pub fn remove_link(&mut self, link: LinkID, _: &mut World) {
+        if let Some(node) = self.nodes.remove(link) {
+            if let Some(cell_info) = self.cells.get_mut(node.cell) {
+                cell_info.links.retain(|other| *other != link);
+                cell_info.dirty = true;
+            }
+        }
+    }
+
+    fn mark_dirty(&mut self, cell: RouterCell) {
+        if let Some(cell_info) = self.cells.get_mut(cell) {
+            cell_info.dirty = true;
+        }
+    }
+
+    fn is_exit(&self, node: &RouterNode) -> bool {
+        node.edges.iter().any(|&(target, _)| {
+            self.nodes
+                .get(target)
+                .map_or(true, |target_node| target_node.cell != node.cell)
+        })
+    }
+
+    fn rebuild_dirty_cells(&mut self) -> bool {
+        let dirty_cells = self
+            .cells
+            .pairs()
+            .filter(|(_, cell_info)| cell_info.dirty)
+            .map(|(cell, cell_info)| (*cell, cell_info.links.clone()))
+            .collect::<Vec<_>>();
+        let any_rebuilt = !dirty_cells.is_empty();
+
+        for (cell, links) in dirty_cells {
+            let exits = links
+                .iter()
+                .filter(|link| {
+                    self.nodes
+                        .get(**link)
+                        .map_or(false, |node| self.is_exit(node))
+                })
+                .cloned()
+                .collect::<Vec<_>>();
+
+            let all_shortcuts = links
+                .iter()
+                .map(|link| {
+                    let reached = self.search_in_cell(cell, *link, false);
+                    let shortcuts = exits
+                        .iter()
+                        .filter_map(|exit| {
+                            reached.get(exit).map(|&(distance, _)| (*exit, distance))
+                        })
+                        .collect::<CVec<_>>();
+                    (*link, shortcuts)
+                })
+                .collect::<Vec<_>>();
+
+            for (link, shortcuts) in all_shortcuts {
+                if let Some(node) = self.nodes.get_mut(link) {
+                    node.shortcuts = shortcuts;
+                }
+            }
+
+            if let Some(cell_info) = self.cells.get_mut(cell) {
+                cell_info.dirty = false;
+            }
+        }
+
+        any_rebuilt
+    }
+
+    // routes might go through any rebuilt cell, so all told next hops become doubtful
+    fn forget_route_tables(&mut self, world: &mut World) {
+        let routed_cells = self
+            .cells
+            .pairs()
+            .filter(|(_, cell_info)| !cell_info.routed_destinations.is_empty())
+            .map(|(cell, _)| *cell)
+            .collect::<Vec<_>>();
+
+        for cell in routed_cells {
+            if let Some(cell_info) = self.cells.get_mut(cell) {
+                cell_info.routed_destinations = CVec::new();
+                for link in cell_info.links.iter() {
+                    // TODO: ugly: untyped RawID shenanigans
+                    LaneID::from_raw(link.as_raw()).forget_hierarchical_routes(world);
+                }
+            }
+        }
+    }
+
+    // next hops of all links of a cell towards a destination
+    fn route_table(&mut self, cell: RouterCell, destination: LinkID) -> Vec<(LinkID, LinkID)> {
+        let (links, destination_length) = match (self.cells.get(cell), self.nodes.get(destination))
+        {
+            (Some(cell_info), Some(destination_node)) => {
+                (cell_info.links.clone(), destination_node.length)
+            }
+            _ => return Vec::new(),
+        };
+
+        let exits = links
+            .iter()
+            .filter(|link| {
+                **link != destination
+                    && self
+                        .nodes
+                        .get(**link)
+                        .map_or(false, |node| self.is_exit(node))
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let mut seeds = Vec::new();
+        if links.contains(&destination) {
+            seeds.push((destination, destination_length, None));
+        }
+        for exit in exits {
+            if let Some((distance, first_hop)) = self.find_route(exit, destination) {
+                seeds.push((exit, distance, Some(first_hop)));
+            }
+        }
+
+        self.next_hops_in_cell(cell, seeds)
+            .into_iter()
+            .filter_map(|(link, maybe_hop)| maybe_hop.map(|hop| (link, hop)))
+            .collect()
+    }
+
+    // Dijkstra backwards along the edges inside a cell, starting from links
+    // of which the distance to the destination and the next hop is already known
+    fn next_hops_in_cell(
+        &self,
+        cell: RouterCell,
+        seeds: Vec<(LinkID, f32, Option<LinkID>)>,
+    ) -> HashMap<LinkID, Option<LinkID>> {
+        let mut reverse_edges = HashMap::<LinkID, Vec<(LinkID, f32)>>::new();
+        if let Some(cell_info) = self.cells.get(cell) {
+            for link in cell_info.links.iter() {
+                if let Some(node) = self.nodes.get(*link) {
+                    for &(target, cost) in node.edges.iter() {
+                        reverse_edges
+                            .entry(target)
+                            .or_insert_with(Vec::new)
+                            .push((*link, cost));
+                    }
+                }
+            }
+        }
+
+        let mut next_hops = HashMap::new();
+        let mut queue = SearchQueue::new();
+        for (link, distance, maybe_hop) in seeds {
+            queue.push(distance, (link, maybe_hop));
+        }
+
+        while let Some((distance, (link, maybe_hop))) = queue.pop() {
+            if next_hops.contains_key(&link) {
+                continue;
+            }
+            next_hops.insert(link, maybe_hop);
+
+            for &(predecessor, cost) in reverse_edges.get(&link).into_iter().flatten() {
+                let in_cell = self
+                    .nodes
+                    .get(predecessor)
+                    .map_or(false, |node| node.cell == cell);
+                if in_cell && !next_hops.contains_key(&predecessor) {
+                    queue.push(distance + cost, (predecessor, Some(link)));
+                }
+            }
+        }
+
+        next_hops
+    }
+
+    // Dijkstra along (or against) the edges that stay inside a cell,
+    // also remembers which successor of the start each link is reached through
+    fn search_in_cell(
+        &self,
+        cell: RouterCell,
+        start: LinkID,
+        backwards: bool,
+    ) -> HashMap<LinkID, (f32, Option<LinkID>)> {
+        let mut reverse_edges = HashMap::<LinkID, Vec<(LinkID, f32)>>::new();
+        if backwards {
+            if let Some(cell_info) = self.cells.get(cell) {
+                for link in cell_info.links.iter() {
+                    if let Some(node) = self.nodes.get(*link) {
+                        for &(target, cost) in node.edges.iter() {
+                            reverse_edges
+                                .entry(target)
+                                .or_insert_with(Vec::new)
+                                .push((*link, cost));
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut reached = HashMap::new();
+        let mut queue = SearchQueue::new();
+        queue.push(0.0, (start, None));
+
+        while let Some((distance, (link, first_hop))) = queue.pop() {
+            if reached.contains_key(&link) {
+                continue;
+            }
+            reached.insert(link, (distance, first_hop));
+
+            let neighbours = if backwards {
+                reverse_edges.get(&link).cloned().unwrap_or_default()
+            } else {
+                self.nodes
+                    .get(link)
+                    .map(|node| node.edges.iter().cloned().collect())
+                    .unwrap_or_default()
+            };
+
+            for (neighbour, cost) in neighbours {
+                let in_cell = self
+                    .nodes
+                    .get(neighbour)
+                    .map_or(false, |node| node.cell == cell);
+                if in_cell && !reached.contains_key(&neighbour) {
+                    queue.push(distance + cost, (neighbour, first_hop.or(Some(neighbour))));
+                }
+            }
+        }
+
+        reached
+    }
+
+    // distance (including the destination itself, like the gossiped routes)
+    // and the successor of `from` to take
+    fn find_route(&mut self, from: LinkID, destination: LinkID) -> Option<(f32, LinkID)> {
+        self.rebuild_dirty_cells();
+
+        let (from_cell, destination_cell, destination_length) =
+            match (self.nodes.get(from), self.nodes.get(destination)) {
+                (Some(from_node), Some(destination_node)) => (
+                    from_node.cell,
+                    destination_node.cell,
+                    destination_node.length,
+                ),
+                _ => return None,
+            };
+
+        let mut best: Option<(f32, LinkID)> = None;
+
+        let from_local = self.search_in_cell(from_cell, from, false);
+        if from_cell == destination_cell {
+            if let Some(&(distance, Some(first_hop))) = from_local.get(&destination) {
+                best = Some((distance, first_hop));
+            }
+        }
+
+        let to_destination_local = self.search_in_cell(destination_cell, destination, true);
+
+        // search between cells, starting from the exits of the own cell
+        let mut settled = HashSet::new();
+        let mut queue = SearchQueue::new();
+        for (link, &(distance, first_hop)) in &from_local {
+            let is_exit = self
+                .nodes
+                .get(*link)
+                .map_or(false, |node| self.is_exit(node));
+            if is_exit {
+                queue.push(distance, (*link, first_hop, true));
+            }
+        }
+
+        while let Some((distance, (link, first_hop, in_own_cell))) = queue.pop() {
+            if best.map_or(false, |(best_distance, _)| distance >= best_distance) {
+                break;
+            }
+            if !settled.insert(link) {
+                continue;
+            }
+
+            if let (Some(&(rest, _)), Some(first_hop)) =
+                (to_destination_local.get(&link), first_hop)
+            {
+                if best.map_or(true, |(best_distance, _)| distance + rest < best_distance) {
+                    best = Some((distance + rest, first_hop));
+                }
+            }
+
+            if let Some(node) = self.nodes.get(link) {
+                for &(target, cost) in node.edges.iter() {
+                    let leaves_cell = self
+                        .nodes
+                        .get(target)
+                        .map_or(false, |target_node| target_node.cell != node.cell);
+                    if leaves_cell {
+                        queue.push(distance + cost, (target, first_hop.or(Some(target)), false));
+                    }
+                }
+
+                // inside the own cell, the local search already found everything
+                if !in_own_cell {
+                    for &(exit, cost) in node.shortcuts.iter() {
+                        queue.push(distance + cost, (exit, first_hop, false));
+                    }
+                }
+            }
+        }
+
+        best.map(|(distance, first_hop)| (distance + destination_length, first_hop))
+    }
+
+    pub fn route(&mut self, from: LaneID, destination: LinkID, trip: TripID, world: &mut World) {
+        if self.rebuild_dirty_cells() {
+            self.forget_route_tables(world);
+        }
+
+        let from_link: LinkID = from.into();
+        let maybe_cell = self.nodes.get(from_link).map(|node| node.cell);
+        let already_routed = maybe_cell
+            .and_then(|cell| self.cells.get(cell))
+            .map_or(false, |cell_info| {
+                cell_info.routed_destinations.contains(&destination)
+            });
+
+        let maybe_first_hop = match maybe_cell {
+            Some(cell) if !already_routed => {
+                let mut maybe_first_hop = None;
+                for (link, hop) in self.route_table(cell, destination) {
+                    if link == from_link {
+                        maybe_first_hop = Some(hop);
+                    } else {
+                        // TODO: ugly: untyped RawID shenanigans
+                        let lane = LaneID::from_raw(link.as_raw());
+                        lane.learn_hierarchical_route(destination, hop, world);
+                    }
+                }
+                if let Some(cell_info) = self.cells.get_mut(cell) {
+                    cell_info.routed_destinations.push(destination);
+                }
+                maybe_first_hop
+            }
+            _ => self
+                .find_route(from_link, destination)
+                .map(|(_, first_hop)| first_hop),
+        };
+
+        from.on_hierarchical_route(trip, destination, maybe_first_hop, world);
+    }
+
+    pub fn check_consistency(&mut self, world: &mut World) {
+        self.report = ConsistencyReport::default();
+        LaneID::global_broadcast(world).report_routes_for_check(self.id, world);
+        self.time
+            .wake_up_in(Ticks(CONSISTENCY_CHECK_TICKS), self.id.into(), world);
+    }
+
+    pub fn compare_routes(
+        &mut self,
+        from: LaneID,
+        gossip_routes: &CVec<(LinkID, f32, LinkID)>,
+        world: &mut World,
+    ) {
+        for &(destination, gossip_distance, gossip_hop) in gossip_routes.iter() {
+            self.report.checked += 1;
+
+            if let Some((distance, first_hop)) = self.find_route(from.into(), destination) {
+                let relative_error = (distance - gossip_distance).abs() / distance.max(1.0);
+                self.report.max_relative_error = self.report.max_relative_error.max(relative_error);
+
+                if relative_error <= CONSISTENCY_TOLERANCE {
+                    self.report.distance_agreed += 1;
+                } else if relative_error > CONSISTENCY_WARNING_ERROR {
+                    warn(
+                        LOG_T,
+                        format!(
+                            "{:?} -> {:?}: router {} via {:?}, gossip {} via {:?}",
+                            from, destination, distance, first_hop, gossip_distance, gossip_hop
+                        ),
+                        self.id,
+                        world,
+                    );
+                }
+
+                if first_hop == gossip_hop {
+                    self.report.hop_agreed += 1;
+                }
+            } else {
+                self.report.unroutable += 1;
+            }
+        }
+    }
+}
+
+impl Sleeper for HierarchicalRouter {
+    fn wake(&mut self, _: Instant, world: &mut World) {
+        let report = self.report;
+        info(
+            LOG_T,
+            format!(
+                "Checked {} gossiped routes: {} same distance, {} same next hop, \
+                 {} not routable, max relative error {:.3}",
+                report.checked,
+                report.distance_agreed,
+                report.hop_agreed,
+                report.unroutable,
+                report.max_relative_error
+            ),
+            self.id,
+            world,
+        );
+    }
+}
+
+impl Lane {
+    pub fn set_hierarchical_routing(&mut self, enabled: bool, _: &mut World) {
+        let router = &mut self.pathfinding.router;
+        router.outdated = enabled && !router.enabled;
+        router.enabled = enabled;
+        self.pathfinding.router_hops = CHashMap::new();
+    }
+
+    pub fn learn_hierarchical_route(&mut self, destination: LinkID, hop: LinkID, _: &mut World) {
+        self.pathfinding.router_hops.insert(destination, hop);
+    }
+
+    pub fn forget_hierarchical_routes(&mut self, _: &mut World) {
+        self.pathfinding.router_hops = CHashMap::new();
+    }
+
+    pub fn on_hierarchical_route(
+        &mut self,
+        trip: TripID,
+        destination: LinkID,
+        maybe_first_hop: Option<LinkID>,
+        world: &mut World,
+    ) {
+        if let Some(first_hop) = maybe_first_hop {
+            self.pathfinding.router_hops.insert(destination, first_hop);
+        }
+
+        let maybe_hop_idx =
+            maybe_first_hop.and_then(|first_hop| self.map_connected_link_to_idx(first_hop));
+
+        if let Some(car_idx) = self
+            .microtraffic
+            .cars
+            .iter()
+            .position(|car| car.trip == trip)
+        {
+            if let Some(hop_idx) = maybe_hop_idx {
+                self.microtraffic.cars[car_idx].next_hop_interaction = Some(hop_idx as u8);
+            } else if self.microtraffic.cars[car_idx]
+                .next_hop_interaction
+                .is_none()
+            {
+                let car = self.microtraffic.cars.remove(car_idx);
+                car.trip.finish(
+                    TripResult {
+                        location_now: Some(self.id_as()),
+                        fate: TripFate::NoRoute,
+                        parked_at: None,
+                    },
+                    world,
+                );
+            }
+        }
+    }
+
+    pub fn report_routes_for_check(&mut self, router: HierarchicalRouterID, world: &mut World) {
+        let interactions = &self.connectivity.interactions;
+        let gossip_routes: CVec<_> = self
+            .pathfinding
+            .routes
+            .pairs()
+            .map(|(destination, entry)| {
+                let hop: LinkID = interactions[entry.outgoing_idx as usize]
+                    .indirect_lane_partner()
+                    .into();
+                (destination.link, entry.distance, hop)
+            })
+            .collect();
+
+        if !gossip_routes.is_empty() {
+            router.compare_routes(self.id, gossip_routes, world);
+        }
+    }
+}
+
+pub fn update_router(lane: &mut Lane, world: &mut World) {
+    if lane.pathfinding.router.enabled && lane.pathfinding.router.outdated {
+        let edges = lane
+            .successors()
+            .into_iter()
+            .map(|connection| (connection.link, connection.connection_cost))
+            .collect();
+
+        HierarchicalRouterID::global_first(world).update_link(
+            lane.id.into(),
+            lane.construction.path.start(),
+            lane.construction.length,
+            edges,
+            world,
+        );
+
+        lane.pathfinding.router.outdated = false;
+        lane.pathfinding.router.reported_cost_factor = lane.pathfinding.travel_cost_factor;
+    }
+}
+
+pub fn request_route(lane: &Lane, trip: TripID, destination: Location, world: &mut World) {
+    HierarchicalRouterID::global_first(world).route(lane.id, destination.link, trip, world);
+}
+
+pub fn on_unbuild(lane: &Lane, world: &mut World) {
+    if lane.pathfinding.router.enabled {
+        HierarchicalRouterID::global_first(world).remove_link(lane.id.into(), world);
+    }
+}
+
+// kay IDs aren't ordered, so the heap only holds indices into the queued labels
+struct SearchQueue<L: Copy> {
+    heap: BinaryHeap<(Reverse<OrderedFloat<f32>>, usize)>,
+    labels: Vec<L>,
+}
+
+impl<L: Copy> SearchQueue<L> {
+    fn new() -> Self {
+        SearchQueue {
+            heap: BinaryHeap::new(),
+            labels: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, distance: f32, label: L) {
+        self.heap
+            .push((Reverse(OrderedFloat(distance)), self.labels.len()));
+        self.labels.push(label);
+    }
+
+    fn pop(&mut self) -> Option<(f32, L)> {
+        self.heap
+            .pop()
+            .map(|(Reverse(OrderedFloat(distance)), idx)| (distance, self.labels[idx]))
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<HierarchicalRouter>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World, time: TimeID) {
+    HierarchicalRouterID::spawn(time, world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
+
+const ROUTER_CELL_SIZE: f32 = 500.0;
+const CONSISTENCY_CHECK_TICKS: u32 = 30;
+const CONSISTENCY_TOLERANCE: f32 = 0.01;
+const CONSISTENCY_WARNING_ERROR: f32 = 0.5;
+// rebuilding a cell is expensive, so the router only hears about big changes in travel cost
+pub const ROUTER_COST_FACTOR_CHANGE: f32 = 2.0;
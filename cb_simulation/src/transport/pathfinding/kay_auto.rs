@@ -290,14 +290,29 @@ impl RoughLocationID {
         world.send(self.as_raw(), MSG_RoughLocation_resolve_as_location(requester, rough_location, instant));
     }
     
+    pub fn resolve_as_bike_location(self, requester: LocationRequesterID, rough_location: RoughLocationID, instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_RoughLocation_resolve_as_bike_location(requester, rough_location, instant));
+    }
+    
     pub fn resolve_as_position(self, requester: PositionRequesterID, rough_location: RoughLocationID, world: &mut World) {
         world.send(self.as_raw(), MSG_RoughLocation_resolve_as_position(requester, rough_location));
     }
+    
+    pub fn find_parking(self, trip: TripID, instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_RoughLocation_find_parking(trip, instant));
+    }
+    
+    pub fn leave_parking(self, on_street: Option<LaneID>, world: &mut World) {
+        world.send(self.as_raw(), MSG_RoughLocation_leave_parking(on_street));
+    }
 
     pub fn register_trait(system: &mut ActorSystem) {
         system.register_trait::<RoughLocationRepresentative>();
         system.register_trait_message::<MSG_RoughLocation_resolve_as_location>();
+        system.register_trait_message::<MSG_RoughLocation_resolve_as_bike_location>();
         system.register_trait_message::<MSG_RoughLocation_resolve_as_position>();
+        system.register_trait_message::<MSG_RoughLocation_find_parking>();
+        system.register_trait_message::<MSG_RoughLocation_leave_parking>();
     }
 
     pub fn register_implementor<Act: Actor + RoughLocation>(system: &mut ActorSystem) {
@@ -308,18 +323,42 @@ impl RoughLocationID {
             }, false
         );
         
+        system.add_handler::<Act, _, _>(
+            |&MSG_RoughLocation_resolve_as_bike_location(requester, rough_location, instant), instance, world| {
+                instance.resolve_as_bike_location(requester, rough_location, instant, world); Fate::Live
+            }, false
+        );
+        
         system.add_handler::<Act, _, _>(
             |&MSG_RoughLocation_resolve_as_position(requester, rough_location), instance, world| {
                 instance.resolve_as_position(requester, rough_location, world); Fate::Live
             }, false
         );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_RoughLocation_find_parking(trip, instant), instance, world| {
+                instance.find_parking(trip, instant, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_RoughLocation_leave_parking(on_street), instance, world| {
+                instance.leave_parking(on_street, world); Fate::Live
+            }, false
+        );
     }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_RoughLocation_resolve_as_location(pub LocationRequesterID, pub RoughLocationID, pub Instant);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_RoughLocation_resolve_as_bike_location(pub LocationRequesterID, pub RoughLocationID, pub Instant);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_RoughLocation_resolve_as_position(pub PositionRequesterID, pub RoughLocationID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_RoughLocation_find_parking(pub TripID, pub Instant);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_RoughLocation_leave_parking(pub Option<LaneID>);
 #[derive(Serialize, Deserialize)] #[serde(transparent)]
 pub struct LocationRequesterID {
     _raw_id: RawID
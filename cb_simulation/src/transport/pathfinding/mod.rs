@@ -5,6 +5,10 @@ use cb_time::units::Instant;
 
 pub mod trip;
 pub mod road_pathfinding;
+pub mod hierarchical_routing;
+pub mod route_explanation;
+use self::trip::TripID;
+use super::lane::LaneID;
 
 const LOG_T: &str = "Pathfinding";
 
@@ -23,12 +27,15 @@ pub trait Link: Actor {
     fn predecessors(&self) -> Vec<LinkConnection>;
 
     fn after_route_forgotten(&mut self, forgotten_route: Location, world: &mut World);
+    fn after_route_changed(&mut self, changed_route: Location);
 
     fn on_connect(&mut self) {
         self.core_mut().routing_timeout = ROUTING_TIMEOUT_AFTER_CHANGE;
+        self.core_mut().router.outdated = true;
     }
 
     fn on_disconnect(&mut self) {
+        self.core_mut().router.outdated = true;
         self.core_mut().routes = CHashMap::new();
         self.core_mut().routes_changed = true;
         self.core_mut().query_routes_next_tick = true;
@@ -60,7 +67,10 @@ pub trait Link: Actor {
                 query_routes_next_tick: false,
                 tell_to_forget_next_tick: CVec::new(),
                 routing_timeout: ROUTING_TIMEOUT_AFTER_CHANGE,
+                travel_cost_factor: self.core().travel_cost_factor,
                 attachees: self.core().attachees.clone(),
+                router: self.core().router,
+                router_hops: self.core().router_hops.clone(),
             }
         }
 
@@ -140,23 +150,69 @@ pub trait Link: Actor {
                         .map(|self_dest| self_dest.landmark == destination.landmark)
                         .unwrap_or(false)
                 {
-                    let insert = self
-                        .core()
-                        .routes
-                        .get(destination)
-                        .map(|&StoredRoutingEntry { distance, .. }| new_distance < distance)
-                        .unwrap_or(true);
+                    // updates from the neighbour we learned a route from are always
+                    // accepted (costs can rise with congestion), but only propagated
+                    // further if they changed significantly since they were last
+                    // propagated, so that slow drift still adds up but nothing oscillates
+                    let (insert, propagate, rerouted, propagated_distance) =
+                        match self.core().routes.get(destination) {
+                            Some(&StoredRoutingEntry {
+                                distance,
+                                propagated_distance,
+                                learned_from,
+                                ..
+                            }) => {
+                                if learned_from == from {
+                                    let significant =
+                                        changed_significantly(propagated_distance, new_distance);
+                                    if significant && new_distance > propagated_distance {
+                                        // the current route got much slower, ask all other
+                                        // neighbours right away if they know a faster one
+                                        for successor in self.successors() {
+                                            if successor.link != from {
+                                                successor.link.query_routes(
+                                                    self.id_as(),
+                                                    successor.connection_cost,
+                                                    world,
+                                                );
+                                            }
+                                        }
+                                    }
+                                    (
+                                        (new_distance - distance).abs() > ::std::f32::EPSILON,
+                                        significant,
+                                        false,
+                                        if significant {
+                                            new_distance
+                                        } else {
+                                            propagated_distance
+                                        },
+                                    )
+                                } else if new_distance < distance {
+                                    (true, true, true, new_distance)
+                                } else {
+                                    (false, false, false, propagated_distance)
+                                }
+                            }
+                            None => (true, true, false, new_distance),
+                        };
                     if insert {
                         self.core_mut().routes.insert(
                             destination,
                             StoredRoutingEntry {
                                 distance: new_distance,
+                                propagated_distance,
                                 distance_hops: new_distance_hops,
                                 outgoing_idx: from_connection_idx as u8,
                                 learned_from: from,
                             },
                         );
-                        self.core_mut().routes_changed = true;
+                        if propagate {
+                            self.core_mut().routes_changed = true;
+                        }
+                        if rerouted {
+                            self.after_route_changed(destination);
+                        }
                     }
                 }
             }
@@ -235,7 +291,10 @@ pub trait Link: Actor {
                 query_routes_next_tick: true,
                 tell_to_forget_next_tick,
                 routing_timeout: ROUTING_TIMEOUT_AFTER_CHANGE,
+                travel_cost_factor: self.core().travel_cost_factor,
                 attachees: self.core().attachees.clone(),
+                router: self.core().router,
+                router_hops: self.core().router_hops.clone(),
             };
         }
     }
@@ -270,7 +329,7 @@ pub struct LinkConnection {
     connection_cost: f32,
 }
 
-#[derive(Compact, Clone, Default)]
+#[derive(Compact, Clone)]
 pub struct PathfindingCore {
     pub location: Option<Location>,
     pub hops_from_landmark: u8,
@@ -280,7 +339,37 @@ pub struct PathfindingCore {
     pub tell_to_forget_next_tick: CVec<Location>,
     pub query_routes_next_tick: bool,
     pub routing_timeout: u16,
+    pub travel_cost_factor: f32,
     attachees: CVec<AttacheeID>,
+    pub router: self::hierarchical_routing::RouterLinkState,
+    // next hops the router told this link, by destination link
+    pub router_hops: CHashMap<LinkID, LinkID>,
+}
+
+impl Default for PathfindingCore {
+    fn default() -> Self {
+        PathfindingCore {
+            location: None,
+            hops_from_landmark: 0,
+            learned_landmark_from: None,
+            routes: CHashMap::new(),
+            routes_changed: false,
+            tell_to_forget_next_tick: CVec::new(),
+            query_routes_next_tick: false,
+            routing_timeout: 0,
+            // free flow, raised by congestion and incidents
+            travel_cost_factor: 1.0,
+            attachees: CVec::new(),
+            router: Default::default(),
+            router_hops: CHashMap::new(),
+        }
+    }
+}
+
+impl PathfindingCore {
+    pub fn has_attachees(&self) -> bool {
+        !self.attachees.is_empty()
+    }
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
@@ -337,6 +426,8 @@ pub trait Attachee {
 pub struct StoredRoutingEntry {
     pub outgoing_idx: u8,
     pub distance: f32,
+    // as last told to the predecessors
+    propagated_distance: f32,
     distance_hops: u8,
     learned_from: LinkID,
 }
@@ -350,6 +441,36 @@ pub struct CommunicatedRoutingEntry {
 const IDEAL_LANDMARK_RADIUS: u8 = 3;
 const MIN_LANDMARK_INCOMING: usize = 3;
 const ROUTING_TIMEOUT_AFTER_CHANGE: u16 = 15;
+const ROUTE_COST_CHANGE_THRESHOLD: f32 = 0.1;
+
+fn changed_significantly(propagated_distance: f32, new_distance: f32) -> bool {
+    (new_distance - propagated_distance).abs() > ROUTE_COST_CHANGE_THRESHOLD * propagated_distance
+}
+
+#[cfg(test)]
+mod tests {
+    use super::changed_significantly;
+
+    #[test]
+    fn small_changes_are_not_propagated() {
+        assert!(!changed_significantly(100.0, 105.0));
+        assert!(!changed_significantly(100.0, 95.0));
+        assert!(changed_significantly(100.0, 120.0));
+        assert!(changed_significantly(100.0, 80.0));
+    }
+
+    #[test]
+    fn slow_drift_adds_up() {
+        // each update is small compared to the previous one,
+        // but not compared to what was last propagated
+        let propagated_distance = 100.0;
+        let updates = [104.0, 108.0, 112.0];
+        let first_significant = updates
+            .iter()
+            .position(|&distance| changed_significantly(propagated_distance, distance));
+        assert_eq!(first_significant, Some(2));
+    }
+}
 
 pub enum RoughLocationResolve {
     Done(Option<PreciseLocation>, P2),
@@ -359,6 +480,11 @@ pub enum RoughLocationResolve {
 pub trait RoughLocation {
     fn resolve(&self) -> RoughLocationResolve;
 
+    // cyclists might start and end their trips somewhere else, like on a bike lane
+    fn resolve_for_cycling(&self) -> RoughLocationResolve {
+        self.resolve()
+    }
+
     fn resolve_as_location(
         &mut self,
         requester: LocationRequesterID,
@@ -376,6 +502,28 @@ pub trait RoughLocation {
         }
     }
 
+    fn resolve_as_bike_location(
+        &mut self,
+        requester: LocationRequesterID,
+        rough_location: RoughLocationID,
+        instant: Instant,
+        world: &mut World,
+    ) {
+        match self.resolve_for_cycling() {
+            RoughLocationResolve::Done(maybe_location, _) => {
+                requester.location_resolved(rough_location, maybe_location, instant, world);
+            }
+            RoughLocationResolve::SameAs(other_rough_location) => {
+                other_rough_location.resolve_as_bike_location(
+                    requester,
+                    rough_location,
+                    instant,
+                    world,
+                );
+            }
+        }
+    }
+
     fn resolve_as_position(
         &mut self,
         requester: PositionRequesterID,
@@ -391,6 +539,21 @@ pub trait RoughLocation {
             }
         }
     }
+
+    fn find_parking(&mut self, trip: TripID, instant: Instant, world: &mut World) {
+        match self.resolve() {
+            RoughLocationResolve::Done(..) => trip.parked(None, instant, world),
+            RoughLocationResolve::SameAs(other_rough_location) => {
+                other_rough_location.find_parking(trip, instant, world);
+            }
+        }
+    }
+
+    fn leave_parking(&mut self, on_street: Option<LaneID>, world: &mut World) {
+        if let RoughLocationResolve::SameAs(other_rough_location) = self.resolve() {
+            other_rough_location.leave_parking(on_street, world);
+        }
+    }
 }
 
 pub trait LocationRequester {
@@ -421,11 +584,14 @@ use cb_time::actors::TimeID;
 pub fn setup(system: &mut ActorSystem) {
     trip::setup(system);
     road_pathfinding::auto_setup(system);
+    hierarchical_routing::setup(system);
+    route_explanation::setup(system);
     auto_setup(system);
 }
 
 pub fn spawn(world: &mut World, time: TimeID) {
     trip::spawn(world, time);
+    hierarchical_routing::spawn(world, time);
 }
 
 mod kay_auto;
@@ -5,6 +5,8 @@ use transport::lane::connectivity::Interaction;
 use super::{PathfindingCore, Link, LinkID, Location, LinkConnection,
 CommunicatedRoutingEntry, RoughLocation, RoughLocationResolve, PreciseLocation, RoughLocationID};
 use super::trip::{TripResult, TripFate};
+use super::hierarchical_routing::ROUTER_COST_FACTOR_CHANGE;
+use cb_time::units::Instant;
 
 impl Link for Lane {
     fn core(&self) -> &PathfindingCore {
@@ -60,7 +62,7 @@ impl Link for Lane {
                 }),
                 Interaction::Next { next, .. } => Some(LinkConnection {
                     link: next.into(),
-                    connection_cost: self.construction.length,
+                    connection_cost: self.travel_cost(),
                 }),
                 _ => None,
             })
@@ -82,7 +84,7 @@ impl Link for Lane {
                 }),
                 Interaction::Previous { previous, .. } => Some(LinkConnection {
                     link: previous.into(),
-                    connection_cost: self.construction.length,
+                    connection_cost: self.travel_cost(),
                 }),
                 _ => None,
             })
@@ -91,6 +93,8 @@ impl Link for Lane {
 
     fn after_route_forgotten(&mut self, forgotten_route: Location, world: &mut World) {
         let self_as_rough_location = self.id_as();
+        let use_router = self.pathfinding.router.enabled;
+        let mut trips_to_reroute = Vec::new();
 
         self.microtraffic.cars.retain(|car| {
             let car_was_going_there = if forgotten_route.is_landmark() {
@@ -99,11 +103,15 @@ impl Link for Lane {
                 car.destination.location == forgotten_route
             };
 
-            if car_was_going_there {
+            if car_was_going_there && use_router {
+                trips_to_reroute.push((car.trip, car.destination.location));
+                true
+            } else if car_was_going_there {
                 car.trip.finish(
                     TripResult {
                         location_now: Some(self_as_rough_location),
                         fate: TripFate::RouteForgotten,
+                        parked_at: None,
                     },
                     world,
                 );
@@ -112,6 +120,71 @@ impl Link for Lane {
                 true
             }
         });
+
+        for (trip, destination) in trips_to_reroute {
+            super::hierarchical_routing::request_route(self, trip, destination, world);
+        }
+    }
+
+    fn after_route_changed(&mut self, changed_route: Location) {
+        let routes = &self.pathfinding.routes;
+        let interactions = &self.connectivity.interactions;
+
+        for car in self.microtraffic.cars.iter_mut() {
+            let car_is_going_there = if changed_route.is_landmark() {
+                car.destination.landmark == changed_route.landmark
+            } else {
+                car.destination.location == changed_route
+            };
+
+            if car_is_going_there {
+                let maybe_new_hop = routes
+                    .get(car.destination.location)
+                    .or_else(|| routes.get(car.destination.landmark_destination()))
+                    .map(|entry| entry.outgoing_idx);
+
+                if let Some(new_hop) = maybe_new_hop {
+                    // don't reroute onto a lane change that the car already passed
+                    let still_reachable = match interactions[new_hop as usize] {
+                        Interaction::Switch { end, .. } => *car.position < end,
+                        _ => true,
+                    };
+                    if still_reachable && car.next_hop_interaction.is_some() {
+                        car.next_hop_interaction = Some(new_hop);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Lane {
+    pub fn travel_cost(&self) -> f32 {
+        self.construction.length * self.pathfinding.travel_cost_factor
+    }
+
+    pub fn update_travel_cost_factor(&mut self, instant: Instant) {
+        let relative_speed = self
+            .microtraffic
+            .relative_speed
+            .max(MIN_RELATIVE_SPEED_FOR_COST);
+        // known incidents make a lane expensive right away, before congestion builds up
+        let observed_factor = (1.0 / relative_speed).max(self.incident_travel_cost_factor(instant));
+        let advertised_factor = self.pathfinding.travel_cost_factor;
+
+        if (observed_factor - advertised_factor).abs()
+            > TRAVEL_COST_FACTOR_CHANGE_THRESHOLD * advertised_factor
+        {
+            self.pathfinding.travel_cost_factor = observed_factor;
+            self.pathfinding.routes_changed = true;
+
+            let reported_factor = self.pathfinding.router.reported_cost_factor;
+            if observed_factor > ROUTER_COST_FACTOR_CHANGE * reported_factor
+                || ROUTER_COST_FACTOR_CHANGE * observed_factor < reported_factor
+            {
+                self.pathfinding.router.outdated = true;
+            }
+        }
     }
 }
 
@@ -119,6 +192,8 @@ pub fn on_unbuild(lane: &Lane, world: &mut World) {
     for attachee in &lane.pathfinding.attachees {
         attachee.location_changed(lane.pathfinding.location, None, world);
     }
+
+    super::hierarchical_routing::on_unbuild(lane, world);
 }
 
 impl RoughLocation for Lane {
@@ -136,5 +211,9 @@ impl RoughLocation for Lane {
 const LANE_CHANGE_COST_LEFT: f32 = 5.0;
 const LANE_CHANGE_COST_RIGHT: f32 = 3.0;
 
+// caps the travel cost of a jammed lane at 10x its length
+const MIN_RELATIVE_SPEED_FOR_COST: f32 = 0.1;
+const TRAVEL_COST_FACTOR_CHANGE_THRESHOLD: f32 = 0.25;
+
 mod kay_auto;
 pub use self::kay_auto::*;
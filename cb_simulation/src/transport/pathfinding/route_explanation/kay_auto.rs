@@ -0,0 +1,188 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct RouteExplanationRequesterID {
+    _raw_id: RawID
+}
+
+impl Copy for RouteExplanationRequesterID {}
+impl Clone for RouteExplanationRequesterID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for RouteExplanationRequesterID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "RouteExplanationRequesterID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for RouteExplanationRequesterID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for RouteExplanationRequesterID {
+    fn eq(&self, other: &RouteExplanationRequesterID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for RouteExplanationRequesterID {}
+
+pub struct RouteExplanationRequesterRepresentative;
+
+impl ActorOrActorTrait for RouteExplanationRequesterRepresentative {
+    type ID = RouteExplanationRequesterID;
+}
+
+impl TypedID for RouteExplanationRequesterID {
+    type Target = RouteExplanationRequesterRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        RouteExplanationRequesterID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + RouteExplanationRequester> TraitIDFrom<Act> for RouteExplanationRequesterID {}
+
+impl RouteExplanationRequesterID {
+    pub fn on_route_explanation(self, explanation: RouteExplanation, world: &mut World) {
+        world.send(self.as_raw(), MSG_RouteExplanationRequester_on_route_explanation(explanation));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<RouteExplanationRequesterRepresentative>();
+        system.register_trait_message::<MSG_RouteExplanationRequester_on_route_explanation>();
+    }
+
+    pub fn register_implementor<Act: Actor + RouteExplanationRequester>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, RouteExplanationRequesterRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_RouteExplanationRequester_on_route_explanation(ref explanation), instance, world| {
+                instance.on_route_explanation(explanation, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_RouteExplanationRequester_on_route_explanation(pub RouteExplanation);
+impl Actor for RouteExplainer {
+    type ID = RouteExplainerID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct RouteExplainerID {
+    _raw_id: RawID
+}
+
+impl Copy for RouteExplainerID {}
+impl Clone for RouteExplainerID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for RouteExplainerID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "RouteExplainerID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for RouteExplainerID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for RouteExplainerID {
+    fn eq(&self, other: &RouteExplainerID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for RouteExplainerID {}
+
+impl TypedID for RouteExplainerID {
+    type Target = RouteExplainer;
+
+    fn from_raw(id: RawID) -> Self {
+        RouteExplainerID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl RouteExplainerID {
+    pub fn spawn(rough_source: RoughLocationID, rough_destination: RoughLocationID, requester: RouteExplanationRequesterID, instant: Instant, world: &mut World) -> Self {
+        let id = RouteExplainerID::from_raw(world.allocate_instance_id::<RouteExplainer>());
+        let swarm = world.local_broadcast::<RouteExplainer>();
+        world.send(swarm, MSG_RouteExplainer_spawn(id, rough_source, rough_destination, requester, instant));
+        id
+    }
+    
+    pub fn on_route_step(self, step: RouteStep, path: LinePath, world: &mut World) {
+        world.send(self.as_raw(), MSG_RouteExplainer_on_route_step(step, path));
+    }
+    
+    pub fn finish(self, outcome: RouteExplanationOutcome, world: &mut World) {
+        world.send(self.as_raw(), MSG_RouteExplainer_finish(outcome));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_RouteExplainer_spawn(pub RouteExplainerID, pub RoughLocationID, pub RoughLocationID, pub RouteExplanationRequesterID, pub Instant);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_RouteExplainer_on_route_step(pub RouteStep, pub LinePath);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_RouteExplainer_finish(pub RouteExplanationOutcome);
+
+impl Into<LocationRequesterID> for RouteExplainerID {
+    fn into(self) -> LocationRequesterID {
+        LocationRequesterID::from_raw(self.as_raw())
+    }
+}
+
+impl LaneID {
+    pub fn explain_route_step(self, explainer: RouteExplainerID, destination: Location, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_explain_route_step(explainer, destination));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_explain_route_step(pub RouteExplainerID, pub Location);
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    RouteExplanationRequesterID::register_trait(system);
+    LocationRequesterID::register_implementor::<RouteExplainer>(system);
+    system.add_spawner::<RouteExplainer, _, _>(
+        |&MSG_RouteExplainer_spawn(id, rough_source, rough_destination, requester, instant), world| {
+            RouteExplainer::spawn(id, rough_source, rough_destination, requester, instant, world)
+        }, false
+    );
+    
+    system.add_handler::<RouteExplainer, _, _>(
+        |&MSG_RouteExplainer_on_route_step(ref step, ref path), instance, world| {
+            instance.on_route_step(step, path, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<RouteExplainer, _, _>(
+        |&MSG_RouteExplainer_finish(outcome), instance, world| {
+            instance.finish(outcome, world)
+        }, false
+    );
+    
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_explain_route_step(explainer, destination), instance, world| {
+            instance.explain_route_step(explainer, destination, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
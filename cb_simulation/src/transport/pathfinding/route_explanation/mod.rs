@@ -0,0 +1,224 @@
+use kay::{ActorSystem, World, Fate, TypedID, Actor};
+use compact::CVec;
+use descartes::LinePath;
+use cb_time::units::Instant;
+
+use transport::lane::{Lane, LaneID, SwitchLaneID};
+use transport::lane::connectivity::Interaction;
+use super::{Link, LinkID, Location, PreciseLocation, RoughLocationID, LocationRequester,
+LocationRequesterID};
+
+// One lane of an explained route, as seen from that lane's routing table
+#[derive(Copy, Clone, Serialize, Deserialize)]
+pub struct RouteStep {
+    pub lane: LaneID,
+    // the landmark the lane itself belongs to
+    pub landmark: Option<LinkID>,
+    // only the destination's landmark was known here, not the destination itself
+    pub via_landmark: bool,
+    // remaining cost to the destination, according to this lane
+    pub distance: Option<f32>,
+    pub next: Option<LaneID>,
+    pub via_switch: Option<SwitchLaneID>,
+    // cost of getting from this lane to the next one
+    pub step_cost: Option<f32>,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum RouteExplanationOutcome {
+    Arrived,
+    SourceOrDestinationNotResolvable,
+    NoRoute,
+    Loop,
+    TooLong,
+}
+
+#[derive(Compact, Clone)]
+pub struct RouteExplanation {
+    pub source: Option<PreciseLocation>,
+    pub destination: Option<PreciseLocation>,
+    pub outcome: RouteExplanationOutcome,
+    pub steps: CVec<RouteStep>,
+    // lane paths of the steps, to highlight the route
+    pub step_paths: CVec<LinePath>,
+}
+
+pub trait RouteExplanationRequester {
+    fn on_route_explanation(&mut self, explanation: &RouteExplanation, world: &mut World);
+}
+
+// Follows the next hops a car would take, lane by lane, and reports the whole route
+#[derive(Compact, Clone)]
+pub struct RouteExplainer {
+    id: RouteExplainerID,
+    rough_source: RoughLocationID,
+    rough_destination: RoughLocationID,
+    requester: RouteExplanationRequesterID,
+    source: Option<PreciseLocation>,
+    destination: Option<PreciseLocation>,
+    steps: CVec<RouteStep>,
+    step_paths: CVec<LinePath>,
+}
+
+impl RouteExplainer {
+    pub fn spawn(
+        id: RouteExplainerID,
+        rough_source: RoughLocationID,
+        rough_destination: RoughLocationID,
+        requester: RouteExplanationRequesterID,
+        instant: Instant,
+        world: &mut World,
+    ) -> RouteExplainer {
+        rough_source.resolve_as_location(id.into(), rough_source, instant, world);
+
+        RouteExplainer {
+            id,
+            rough_source,
+            rough_destination,
+            requester,
+            source: None,
+            destination: None,
+            steps: CVec::new(),
+            step_paths: CVec::new(),
+        }
+    }
+
+    pub fn on_route_step(&mut self, step: &RouteStep, path: &LinePath, world: &mut World) {
+        let destination = self
+            .destination
+            .expect("Should only explain steps once the destination is known");
+        let arrived = destination.link == step.lane.into();
+
+        self.steps.push(*step);
+        self.step_paths.push(path.clone());
+
+        if arrived {
+            self.id.finish(RouteExplanationOutcome::Arrived, world);
+        } else if let Some(next) = step.next {
+            if self.steps.iter().any(|earlier| earlier.lane == next) {
+                self.id.finish(RouteExplanationOutcome::Loop, world);
+            } else if self.steps.len() >= MAX_EXPLAINED_STEPS {
+                self.id.finish(RouteExplanationOutcome::TooLong, world);
+            } else {
+                next.explain_route_step(self.id, destination.location, world);
+            }
+        } else {
+            self.id.finish(RouteExplanationOutcome::NoRoute, world);
+        }
+    }
+
+    pub fn finish(&mut self, outcome: RouteExplanationOutcome, world: &mut World) -> Fate {
+        self.requester.on_route_explanation(
+            RouteExplanation {
+                source: self.source,
+                destination: self.destination,
+                outcome,
+                steps: self.steps.clone(),
+                step_paths: self.step_paths.clone(),
+            },
+            world,
+        );
+        Fate::Die
+    }
+}
+
+impl LocationRequester for RouteExplainer {
+    fn location_resolved(
+        &mut self,
+        rough_location: RoughLocationID,
+        location: Option<PreciseLocation>,
+        instant: Instant,
+        world: &mut World,
+    ) {
+        if location.is_none() {
+            self.id.finish(
+                RouteExplanationOutcome::SourceOrDestinationNotResolvable,
+                world,
+            );
+            return;
+        }
+
+        if self.source.is_none() && rough_location == self.rough_source {
+            self.source = location;
+
+            if self.rough_source == self.rough_destination {
+                self.destination = location;
+            } else {
+                self.rough_destination.resolve_as_location(
+                    self.id_as(),
+                    self.rough_destination,
+                    instant,
+                    world,
+                );
+            }
+        } else {
+            self.destination = location;
+        }
+
+        if let (Some(source), Some(destination)) = (self.source, self.destination) {
+            // TODO: ugly: untyped RawID shenanigans
+            let source_as_lane = LaneID::from_raw(source.link.as_raw());
+            source_as_lane.explain_route_step(self.id, destination.location, world);
+        }
+    }
+}
+
+impl Lane {
+    pub fn explain_route_step(
+        &mut self,
+        explainer: RouteExplainerID,
+        destination: Location,
+        world: &mut World,
+    ) {
+        let maybe_entry = self
+            .pathfinding
+            .routes
+            .get(destination)
+            .map(|entry| (*entry, false))
+            .or_else(|| {
+                self.pathfinding
+                    .routes
+                    .get(destination.landmark_destination())
+                    .map(|entry| (*entry, true))
+            });
+
+        let maybe_interaction = maybe_entry
+            .map(|(entry, _)| self.connectivity.interactions[entry.outgoing_idx as usize]);
+        let next = maybe_interaction.map(|interaction| interaction.indirect_lane_partner());
+        let via_switch = match maybe_interaction {
+            Some(Interaction::Switch { via, .. }) => Some(via),
+            _ => None,
+        };
+        let step_cost = next.and_then(|next| {
+            let next_as_link: LinkID = next.into();
+            self.successors()
+                .into_iter()
+                .find(|connection| connection.link == next_as_link)
+                .map(|connection| connection.connection_cost)
+        });
+
+        explainer.on_route_step(
+            RouteStep {
+                lane: self.id,
+                landmark: self.pathfinding.location.map(|location| location.landmark),
+                via_landmark: maybe_entry.map_or(false, |(_, via_landmark)| via_landmark),
+                distance: maybe_entry.map(|(entry, _)| entry.distance),
+                next,
+                via_switch,
+                step_cost,
+            },
+            self.construction.path.clone(),
+            world,
+        );
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<RouteExplainer>();
+    auto_setup(system);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
+
+const MAX_EXPLAINED_STEPS: usize = 500;
@@ -85,6 +85,72 @@ struct MSG_TripListener_trip_created(pub TripID);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_TripListener_trip_result(pub TripID, pub TripResult, pub RoughLocationID, pub RoughLocationID);
 
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct TripStatisticsRequesterID {
+    _raw_id: RawID
+}
+
+impl Copy for TripStatisticsRequesterID {}
+impl Clone for TripStatisticsRequesterID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for TripStatisticsRequesterID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "TripStatisticsRequesterID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for TripStatisticsRequesterID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for TripStatisticsRequesterID {
+    fn eq(&self, other: &TripStatisticsRequesterID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for TripStatisticsRequesterID {}
+
+pub struct TripStatisticsRequesterRepresentative;
+
+impl ActorOrActorTrait for TripStatisticsRequesterRepresentative {
+    type ID = TripStatisticsRequesterID;
+}
+
+impl TypedID for TripStatisticsRequesterID {
+    type Target = TripStatisticsRequesterRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        TripStatisticsRequesterID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + TripStatisticsRequester> TraitIDFrom<Act> for TripStatisticsRequesterID {}
+
+impl TripStatisticsRequesterID {
+    pub fn on_trip_statistics(self, modes: CVec < ModeStatistics >, world: &mut World) {
+        world.send(self.as_raw(), MSG_TripStatisticsRequester_on_trip_statistics(modes));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<TripStatisticsRequesterRepresentative>();
+        system.register_trait_message::<MSG_TripStatisticsRequester_on_trip_statistics>();
+    }
+
+    pub fn register_implementor<Act: Actor + TripStatisticsRequester>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, TripStatisticsRequesterRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_TripStatisticsRequester_on_trip_statistics(ref modes), instance, world| {
+                instance.on_trip_statistics(modes, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TripStatisticsRequester_on_trip_statistics(pub CVec < ModeStatistics >);
 impl Actor for Trip {
     type ID = TripID;
 
@@ -133,22 +199,40 @@ impl TypedID for TripID {
 }
 
 impl TripID {
-    pub fn spawn(rough_source: RoughLocationID, rough_destination: RoughLocationID, listener: Option < TripListenerID >, instant: Instant, world: &mut World) -> Self {
+    pub fn spawn(rough_source: RoughLocationID, rough_destination: RoughLocationID, listener: Option < TripListenerID >, vehicle_type: VehicleType, instant: Instant, world: &mut World) -> Self {
         let id = TripID::from_raw(world.allocate_instance_id::<Trip>());
         let swarm = world.local_broadcast::<Trip>();
-        world.send(swarm, MSG_Trip_spawn(id, rough_source, rough_destination, listener, instant));
+        world.send(swarm, MSG_Trip_spawn(id, rough_source, rough_destination, listener, vehicle_type, instant));
         id
     }
     
     pub fn finish(self, result: TripResult, world: &mut World) {
         world.send(self.as_raw(), MSG_Trip_finish(result));
     }
+    
+    pub fn arrive(self, instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_Trip_arrive(instant));
+    }
+    
+    pub fn parked(self, maybe_ticket: Option<ParkingTicket>, instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_Trip_parked(maybe_ticket, instant));
+    }
+    
+    pub fn explain_route(self, requester: RouteExplanationRequesterID, instant: Instant, world: &mut World) {
+        world.send(self.as_raw(), MSG_Trip_explain_route(requester, instant));
+    }
 }
 
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
-struct MSG_Trip_spawn(pub TripID, pub RoughLocationID, pub RoughLocationID, pub Option < TripListenerID >, pub Instant);
+struct MSG_Trip_spawn(pub TripID, pub RoughLocationID, pub RoughLocationID, pub Option < TripListenerID >, pub VehicleType, pub Instant);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_Trip_finish(pub TripResult);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Trip_arrive(pub Instant);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Trip_parked(pub Option<ParkingTicket>, pub Instant);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Trip_explain_route(pub RouteExplanationRequesterID, pub Instant);
 
 impl Into<LocationRequesterID> for TripID {
     fn into(self) -> LocationRequesterID {
@@ -307,15 +391,98 @@ impl Into<PositionRequesterID> for FailedTripDebuggerID {
         PositionRequesterID::from_raw(self.as_raw())
     }
 }
+impl Actor for TripStatistics {
+    type ID = TripStatisticsID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct TripStatisticsID {
+    _raw_id: RawID
+}
+
+impl Copy for TripStatisticsID {}
+impl Clone for TripStatisticsID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for TripStatisticsID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "TripStatisticsID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for TripStatisticsID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for TripStatisticsID {
+    fn eq(&self, other: &TripStatisticsID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for TripStatisticsID {}
+
+impl TypedID for TripStatisticsID {
+    type Target = TripStatistics;
+
+    fn from_raw(id: RawID) -> Self {
+        TripStatisticsID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl TripStatisticsID {
+    pub fn spawn(world: &mut World) -> Self {
+        let id = TripStatisticsID::from_raw(world.allocate_instance_id::<TripStatistics>());
+        let swarm = world.local_broadcast::<TripStatistics>();
+        world.send(swarm, MSG_TripStatistics_spawn(id, ));
+        id
+    }
+    
+    pub fn on_trip_started(self, vehicle_type: VehicleType, world: &mut World) {
+        world.send(self.as_raw(), MSG_TripStatistics_on_trip_started(vehicle_type));
+    }
+    
+    pub fn on_trip_mode_changed(self, from: VehicleType, to: VehicleType, world: &mut World) {
+        world.send(self.as_raw(), MSG_TripStatistics_on_trip_mode_changed(from, to));
+    }
+    
+    pub fn on_trip_finished(self, vehicle_type: VehicleType, succeeded: bool, world: &mut World) {
+        world.send(self.as_raw(), MSG_TripStatistics_on_trip_finished(vehicle_type, succeeded));
+    }
+    
+    pub fn get_trip_statistics(self, requester: TripStatisticsRequesterID, world: &mut World) {
+        world.send(self.as_raw(), MSG_TripStatistics_get_trip_statistics(requester));
+    }
+}
+
+#[derive(Copy, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TripStatistics_spawn(pub TripStatisticsID, );
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TripStatistics_on_trip_started(pub VehicleType);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TripStatistics_on_trip_mode_changed(pub VehicleType, pub VehicleType);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TripStatistics_on_trip_finished(pub VehicleType, pub bool);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TripStatistics_get_trip_statistics(pub TripStatisticsRequesterID);
 
 #[allow(unused_variables)]
 #[allow(unused_mut)]
 pub fn auto_setup(system: &mut ActorSystem) {
     TripListenerID::register_trait(system);
+    TripStatisticsRequesterID::register_trait(system);
     LocationRequesterID::register_implementor::<Trip>(system);
     system.add_spawner::<Trip, _, _>(
-        |&MSG_Trip_spawn(id, rough_source, rough_destination, listener, instant), world| {
-            Trip::spawn(id, rough_source, rough_destination, listener, instant, world)
+        |&MSG_Trip_spawn(id, rough_source, rough_destination, listener, vehicle_type, instant), world| {
+            Trip::spawn(id, rough_source, rough_destination, listener, vehicle_type, instant, world)
         }, false
     );
     
@@ -324,6 +491,24 @@ pub fn auto_setup(system: &mut ActorSystem) {
             instance.finish(result, world)
         }, false
     );
+    
+    system.add_handler::<Trip, _, _>(
+        |&MSG_Trip_arrive(instant), instance, world| {
+            instance.arrive(instant, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Trip, _, _>(
+        |&MSG_Trip_parked(maybe_ticket, instant), instance, world| {
+            instance.parked(maybe_ticket, instant, world)
+        }, false
+    );
+    
+    system.add_handler::<Trip, _, _>(
+        |&MSG_Trip_explain_route(requester, instant), instance, world| {
+            instance.explain_route(requester, instant, world); Fate::Live
+        }, false
+    );
     SleeperID::register_implementor::<TripCreator>(system);
     system.add_spawner::<TripCreator, _, _>(
         |&MSG_TripCreator_spawn(id, time), world| {
@@ -354,4 +539,33 @@ pub fn auto_setup(system: &mut ActorSystem) {
             instance.done(world)
         }, false
     );
+    system.add_spawner::<TripStatistics, _, _>(
+        |&MSG_TripStatistics_spawn(id, ), world| {
+            TripStatistics::spawn(id, world)
+        }, false
+    );
+    
+    system.add_handler::<TripStatistics, _, _>(
+        |&MSG_TripStatistics_on_trip_started(vehicle_type), instance, world| {
+            instance.on_trip_started(vehicle_type, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TripStatistics, _, _>(
+        |&MSG_TripStatistics_on_trip_mode_changed(from, to), instance, world| {
+            instance.on_trip_mode_changed(from, to, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TripStatistics, _, _>(
+        |&MSG_TripStatistics_on_trip_finished(vehicle_type, succeeded), instance, world| {
+            instance.on_trip_finished(vehicle_type, succeeded, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<TripStatistics, _, _>(
+        |&MSG_TripStatistics_get_trip_statistics(requester), instance, world| {
+            instance.get_trip_statistics(requester, world); Fate::Live
+        }, false
+    );
 }
\ No newline at end of file
@@ -3,8 +3,10 @@ use compact::CVec;
 use ordered_float::OrderedFloat;
 use cb_time::units::Instant;
 
-use transport::lane::LaneID;
+use transport::lane::{LaneID, LaneKind};
 use super::{PreciseLocation, RoughLocationID, LocationRequester, LocationRequesterID};
+use super::route_explanation::{RouteExplainerID, RouteExplanationRequesterID};
+use transport::parking::ParkingTicket;
 
 use itertools::Itertools;
 use super::super::lane::Lane;
@@ -20,6 +22,7 @@ pub struct Trip {
     source: Option<PreciseLocation>,
     destination: Option<PreciseLocation>,
     listener: Option<TripListenerID>,
+    vehicle_type: VehicleType,
 }
 
 #[derive(Copy, Clone)]
@@ -27,6 +30,7 @@ pub struct TripResult {
     pub location_now: Option<RoughLocationID>,
     //pub instant: Instant,
     pub fate: TripFate,
+    pub parked_at: Option<ParkingTicket>,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -48,26 +52,40 @@ impl Trip {
         rough_source: RoughLocationID,
         rough_destination: RoughLocationID,
         listener: Option<TripListenerID>,
+        vehicle_type: VehicleType,
         instant: Instant,
         world: &mut World,
     ) -> Self {
-        rough_source.resolve_as_location(id.into(), rough_source, instant, world);
+        if vehicle_type == VehicleType::Bicycle {
+            rough_source.resolve_as_bike_location(id.into(), rough_source, instant, world);
+        } else {
+            rough_source.resolve_as_location(id.into(), rough_source, instant, world);
+        }
 
         if let Some(listener) = listener {
             listener.trip_created(id, world);
         }
 
+        TripStatisticsID::local_first(world).on_trip_started(vehicle_type, world);
+
         Trip {
             id,
             rough_source,
             rough_destination,
             listener,
+            vehicle_type,
             source: None,
             destination: None,
         }
     }
 
     pub fn finish(&mut self, result: TripResult, world: &mut World) -> Fate {
+        let succeeded = match result.fate {
+            TripFate::Success(_) => true,
+            _ => false,
+        };
+        TripStatisticsID::local_first(world).on_trip_finished(self.vehicle_type, succeeded, world);
+
         match result.fate {
             TripFate::Success(_) | TripFate::ForceStopped => {}
             reason => {
@@ -99,10 +117,50 @@ impl Trip {
                 self.rough_destination,
                 world,
             );
+        } else if let Some(ticket) = result.parked_at {
+            // nobody will drive this car again
+            ticket.give_back(world);
         }
 
         Fate::Die
     }
+
+    pub fn arrive(&mut self, instant: Instant, world: &mut World) {
+        if self.vehicle_type == VehicleType::Bicycle {
+            // bikes are left right at the destination
+            self.id.parked(None, instant, world);
+        } else {
+            self.rough_destination.find_parking(self.id, instant, world);
+        }
+    }
+
+    pub fn parked(
+        &mut self,
+        maybe_ticket: Option<ParkingTicket>,
+        instant: Instant,
+        world: &mut World,
+    ) -> Fate {
+        if maybe_ticket.is_none() && self.vehicle_type != VehicleType::Bicycle {
+            debug(
+                LOG_T,
+                format!(
+                    "Trip {:?} found no parking near {:?}",
+                    self.id, self.rough_destination
+                ),
+                self.id(),
+                world,
+            );
+        }
+
+        self.finish(
+            TripResult {
+                location_now: None,
+                fate: TripFate::Success(instant),
+                parked_at: maybe_ticket,
+            },
+            world,
+        )
+    }
 }
 
 impl LocationRequester for Trip {
@@ -119,6 +177,13 @@ impl LocationRequester for Trip {
 
                 if self.rough_source == self.rough_destination {
                     self.destination = Some(precise);
+                } else if self.vehicle_type == VehicleType::Bicycle {
+                    self.rough_destination.resolve_as_bike_location(
+                        self.id_as(),
+                        self.rough_destination,
+                        instant,
+                        world,
+                    );
                 } else {
                     self.rough_destination.resolve_as_location(
                         self.id_as(),
@@ -139,10 +204,11 @@ impl LocationRequester for Trip {
                 source_as_lane.add_car(
                     LaneCar {
                         trip: self.id,
+                        vehicle_type: self.vehicle_type,
                         as_obstacle: Obstacle {
                             position: OrderedFloat(source.offset),
                             velocity: 0.0,
-                            max_velocity: 8.0,
+                            max_velocity: self.vehicle_type.parameters().max_velocity,
                         },
                         acceleration: 0.0,
                         destination,
@@ -153,6 +219,27 @@ impl LocationRequester for Trip {
                     world,
                 );
             }
+        } else if self.vehicle_type == VehicleType::Bicycle {
+            // only cycle if both ends are on the bike lane network, drive otherwise
+            debug(
+                LOG_T,
+                format!(
+                    "{:?} has no bike lane, driving instead",
+                    rough_location.as_raw()
+                ),
+                self.id(),
+                world,
+            );
+            TripStatisticsID::local_first(world).on_trip_mode_changed(
+                self.vehicle_type,
+                VehicleType::Car,
+                world,
+            );
+            self.vehicle_type = VehicleType::Car;
+            self.source = None;
+            self.destination = None;
+            self.rough_source
+                .resolve_as_location(self.id_as(), self.rough_source, instant, world);
         } else {
             debug(
                 LOG_T,
@@ -167,6 +254,7 @@ impl LocationRequester for Trip {
                 TripResult {
                     location_now: Some(self.rough_source),
                     fate: TripFate::SourceOrDestinationNotResolvable,
+                    parked_at: None,
                 },
                 world,
             );
@@ -174,9 +262,26 @@ impl LocationRequester for Trip {
     }
 }
 
+impl Trip {
+    pub fn explain_route(
+        &mut self,
+        requester: RouteExplanationRequesterID,
+        instant: Instant,
+        world: &mut World,
+    ) {
+        RouteExplainerID::spawn(
+            self.rough_source,
+            self.rough_destination,
+            requester,
+            instant,
+            world,
+        );
+    }
+}
+
 use cb_time::actors::{TimeID, Sleeper, SleeperID};
 use cb_time::units::Ticks;
-use super::super::microtraffic::{LaneLikeID, LaneCar, Obstacle};
+use super::super::microtraffic::{LaneLikeID, LaneCar, Obstacle, VehicleType};
 
 pub trait TripListener {
     fn trip_created(&mut self, trip: TripID, world: &mut World);
@@ -227,6 +332,7 @@ impl Sleeper for TripCreator {
                     (*source).into(),
                     (*dest).into(),
                     None,
+                    VehicleType::Car,
                     current_instant,
                     world,
                 );
@@ -241,7 +347,7 @@ pub const DEBUG_MANUALLY_SPAWN_CARS: bool = false;
 
 impl Lane {
     pub fn manually_spawn_car_add_lane(&self, world: &mut World) {
-        if !self.connectivity.on_intersection {
+        if !self.connectivity.on_intersection && self.connectivity.kind == LaneKind::Mixed {
             // TODO: ugly/wrong
             TripCreatorID::local_first(world).add_lane_for_trip(self.id, world);
         }
@@ -305,15 +411,133 @@ impl PositionRequester for FailedTripDebugger {
     }
 }
 
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct ModeStatistics {
+    pub vehicle_type: VehicleType,
+    pub started: u32,
+    pub succeeded: u32,
+    pub failed: u32,
+}
+
+pub trait TripStatisticsRequester {
+    fn on_trip_statistics(&mut self, modes: &CVec<ModeStatistics>, world: &mut World);
+}
+
+// Counts trips by vehicle type, which gives the mode share
+#[derive(Compact, Clone)]
+pub struct TripStatistics {
+    id: TripStatisticsID,
+    modes: CVec<ModeStatistics>,
+}
+
+impl TripStatistics {
+    pub fn spawn(id: TripStatisticsID, _: &mut World) -> TripStatistics {
+        TripStatistics {
+            id,
+            modes: CVec::new(),
+        }
+    }
+
+    pub fn on_trip_started(&mut self, vehicle_type: VehicleType, _: &mut World) {
+        count_started(&mut self.modes, vehicle_type);
+    }
+
+    // a trip that was started as a bike trip, but is driven after all
+    pub fn on_trip_mode_changed(&mut self, from: VehicleType, to: VehicleType, _: &mut World) {
+        count_mode_changed(&mut self.modes, from, to);
+    }
+
+    pub fn on_trip_finished(&mut self, vehicle_type: VehicleType, succeeded: bool, _: &mut World) {
+        count_finished(&mut self.modes, vehicle_type, succeeded);
+    }
+
+    pub fn get_trip_statistics(&mut self, requester: TripStatisticsRequesterID, world: &mut World) {
+        requester.on_trip_statistics(self.modes.clone(), world);
+    }
+}
+
+fn mode_mut(modes: &mut CVec<ModeStatistics>, vehicle_type: VehicleType) -> &mut ModeStatistics {
+    if let Some(idx) = modes
+        .iter()
+        .position(|mode| mode.vehicle_type == vehicle_type)
+    {
+        &mut modes[idx]
+    } else {
+        modes.push(ModeStatistics {
+            vehicle_type,
+            started: 0,
+            succeeded: 0,
+            failed: 0,
+        });
+        modes.last_mut().unwrap()
+    }
+}
+
+fn count_started(modes: &mut CVec<ModeStatistics>, vehicle_type: VehicleType) {
+    mode_mut(modes, vehicle_type).started += 1;
+}
+
+fn count_mode_changed(modes: &mut CVec<ModeStatistics>, from: VehicleType, to: VehicleType) {
+    mode_mut(modes, from).started -= 1;
+    mode_mut(modes, to).started += 1;
+}
+
+fn count_finished(modes: &mut CVec<ModeStatistics>, vehicle_type: VehicleType, succeeded: bool) {
+    let mode = mode_mut(modes, vehicle_type);
+    if succeeded {
+        mode.succeeded += 1;
+    } else {
+        mode.failed += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all_trips_accounted_for(modes: &CVec<ModeStatistics>) -> bool {
+        modes
+            .iter()
+            .all(|mode| mode.started == mode.succeeded + mode.failed)
+    }
+
+    #[test]
+    fn trips_finish_in_the_mode_they_started() {
+        let mut modes = CVec::new();
+        count_started(&mut modes, VehicleType::Car);
+        count_started(&mut modes, VehicleType::Bicycle);
+        count_finished(&mut modes, VehicleType::Car, true);
+        count_finished(&mut modes, VehicleType::Bicycle, false);
+        assert!(all_trips_accounted_for(&modes));
+    }
+
+    #[test]
+    fn bike_trips_driven_after_all_count_as_car_trips() {
+        let mut modes = CVec::new();
+        count_started(&mut modes, VehicleType::Bicycle);
+        count_mode_changed(&mut modes, VehicleType::Bicycle, VehicleType::Car);
+        count_finished(&mut modes, VehicleType::Car, true);
+        assert!(all_trips_accounted_for(&modes));
+
+        let bicycle = modes
+            .iter()
+            .find(|mode| mode.vehicle_type == VehicleType::Bicycle)
+            .unwrap();
+        assert_eq!(bicycle.started, 0);
+    }
+}
+
 pub fn setup(system: &mut ActorSystem) {
     system.register::<Trip>();
     system.register::<TripCreator>();
     system.register::<FailedTripDebugger>();
+    system.register::<TripStatistics>();
     auto_setup(system);
 }
 
 pub fn spawn(world: &mut World, time: TimeID) {
     TripCreatorID::spawn(time, world);
+    TripStatisticsID::spawn(world);
 }
 
 mod kay_auto;
@@ -3,6 +3,7 @@ use descartes::{ArcLinePath, Intersect, WithUniqueOrthogonal,
 RoughEq};
 use itertools::Itertools;
 use ordered_float::OrderedFloat;
+use transport::lane::LaneKind;
 
 use super::{IntersectionPrototype, IntersectionConnector, ConnectionRole, LANE_DISTANCE,
 LanePrototype, GestureSideID};
@@ -80,18 +81,24 @@ pub fn create_connecting_lanes(intersection: &mut IntersectionPrototype) {
 
     // assign roles to connectors
     {
-        for incoming_group in intersection.incoming.values_mut() {
+        for (incoming_id, incoming_group) in intersection.incoming.pairs_mut() {
             let n_lanes = incoming_group.len();
+            // bike lanes only ever connect to bike lanes
+            let outgoing = &intersection.outgoing;
+            let outgoing_groups = || {
+                outgoing
+                    .pairs()
+                    .filter(move |(outgoing_id, _)| outgoing_id.is_bike() == incoming_id.is_bike())
+                    .map(|(_, group)| group)
+            };
 
-            let has_inner_turn = intersection.outgoing.values().any(|outgoing_group| {
+            let has_inner_turn = outgoing_groups().any(|outgoing_group| {
                 let role = role_between_groups(incoming_group, outgoing_group);
                 role.inner_turn || role.u_turn
             });
-            let has_straight = intersection
-                .outgoing
-                .values()
+            let has_straight = outgoing_groups()
                 .any(|outgoing_group| role_between_groups(incoming_group, outgoing_group).straight);
-            let has_outer_turn = intersection.outgoing.values().any(|outgoing_group| {
+            let has_outer_turn = outgoing_groups().any(|outgoing_group| {
                 role_between_groups(incoming_group, outgoing_group).outer_turn
             });
 
@@ -106,6 +113,10 @@ pub fn create_connecting_lanes(intersection: &mut IntersectionPrototype) {
                     (true, false, true) => ((n_lanes / 2).max(1), (n_lanes / 2).max(1)),
                 };
 
+            let available_roles = outgoing_groups()
+                .map(|outgoing_group| role_between_groups(incoming_group, outgoing_group))
+                .collect::<Vec<_>>();
+
             for (l, incoming_lane) in incoming_group.iter_mut().enumerate() {
                 if l == 0 && has_inner_turn {
                     incoming_lane.role.u_turn = true;
@@ -119,21 +130,40 @@ pub fn create_connecting_lanes(intersection: &mut IntersectionPrototype) {
                 if l >= n_lanes - n_outer_turn_lanes {
                     incoming_lane.role.outer_turn = true;
                 }
+                // lane-use markings planned by the user override the automatic assignment,
+                // unless nothing they allow exists here, which would make the lane a dead end
+                if let Some(fixed_role) = incoming_lane.fixed_role {
+                    let leads_somewhere = available_roles.iter().any(|role| {
+                        (role.straight && fixed_role.straight)
+                            || (role.u_turn && fixed_role.u_turn)
+                            || (role.inner_turn && fixed_role.inner_turn)
+                            || (role.outer_turn && fixed_role.outer_turn)
+                    });
+                    if leads_somewhere {
+                        incoming_lane.role = fixed_role;
+                    }
+                }
             }
         }
 
-        for outgoing_group in intersection.outgoing.values_mut() {
+        for (outgoing_id, outgoing_group) in intersection.outgoing.pairs_mut() {
             let n_lanes = outgoing_group.len();
+            // bike lanes only ever connect to bike lanes
+            let incoming = &intersection.incoming;
+            let incoming_groups = || {
+                incoming
+                    .pairs()
+                    .filter(move |(incoming_id, _)| incoming_id.is_bike() == outgoing_id.is_bike())
+                    .map(|(_, group)| group)
+            };
 
-            let has_inner_turn = intersection.incoming.values().any(|incoming_group| {
+            let has_inner_turn = incoming_groups().any(|incoming_group| {
                 let role = role_between_groups(incoming_group, outgoing_group);
                 role.inner_turn || role.u_turn
             });
-            let has_straight = intersection
-                .incoming
-                .values()
+            let has_straight = incoming_groups()
                 .any(|incoming_group| role_between_groups(incoming_group, outgoing_group).straight);
-            let has_outer_turn = intersection.incoming.values().any(|incoming_group| {
+            let has_outer_turn = incoming_groups().any(|incoming_group| {
                 role_between_groups(incoming_group, outgoing_group).outer_turn
             });
 
@@ -171,6 +201,9 @@ pub fn create_connecting_lanes(intersection: &mut IntersectionPrototype) {
                 intersection
                     .outgoing
                     .pairs()
+                    .filter(move |(outgoing_gesture_side_id, _)| {
+                        outgoing_gesture_side_id.is_bike() == incoming_gesture_side_id.is_bike()
+                    })
                     .map(|(outgoing_gesture_side_id, outgoing_group)| {
                         let role = role_between_groups(incoming_group, outgoing_group);
 
@@ -211,7 +244,13 @@ pub fn create_connecting_lanes(intersection: &mut IntersectionPrototype) {
                                     )?
                                     .to_line_path_with_max_angle(0.6);
 
-                                    Some(LanePrototype(path, CVec::new()))
+                                    let kind = if incoming_gesture_side_id.is_bike() {
+                                        LaneKind::BikeOnly
+                                    } else {
+                                        LaneKind::Mixed
+                                    };
+
+                                    Some(LanePrototype(path, CVec::new(), kind))
                                 })
                                 .collect::<Vec<_>>()
                         } else {
@@ -241,7 +280,7 @@ pub fn create_connecting_lanes(intersection: &mut IntersectionPrototype) {
 
         fn compatible(lanes_a: &[LanePrototype], lanes_b: &[LanePrototype]) -> bool {
             lanes_a.iter().cartesian_product(lanes_b).all(
-                |(&LanePrototype(ref path_a, _), &LanePrototype(ref path_b, _))| {
+                |(&LanePrototype(ref path_a, ..), &LanePrototype(ref path_b, ..))| {
                     path_a.start().rough_eq_by(path_b.start(), 0.1)
                         || (!path_a.end().rough_eq_by(path_b.end(), 0.1)
                             && (path_a, path_b).intersect().is_empty())
@@ -364,7 +403,7 @@ pub fn create_connecting_lanes(intersection: &mut IntersectionPrototype) {
                 })
                 .collect();
 
-            for &mut LanePrototype(_, ref mut lane_timings) in lanes.iter_mut() {
+            for &mut LanePrototype(_, ref mut lane_timings, _) in lanes.iter_mut() {
                 *lane_timings = timings.clone()
             }
         }
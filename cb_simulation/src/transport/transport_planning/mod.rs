@@ -1,3 +1,4 @@
+use std::collections::{BTreeMap, HashMap};
 use compact::{CHashMap, CVec};
 use descartes::{
     N, P2, V2, EditArcLinePath, Band, LinePath, ClosedLinePath, Area, Intersect,
@@ -10,11 +11,12 @@ use cb_planning::{
     VersionedGesture, StepID, PrototypeID, PlanHistory, PlanResult, Prototype, GestureID,
 };
 use planning::{CBPrototypeKind, CBGestureIntent};
+use transport::lane::LaneKind;
 
 mod intersection_connections;
 pub mod smooth_path;
 use dimensions::{
-    LANE_DISTANCE, CENTER_LANE_DISTANCE, MIN_SWITCHING_LANE_LENGTH,
+    LANE_DISTANCE, CENTER_LANE_DISTANCE, BIKE_LANE_DISTANCE, MIN_SWITCHING_LANE_LENGTH,
     SWITCHING_LANE_OVERLAP_TOLERANCE,
 };
 
@@ -22,17 +24,205 @@ use dimensions::{
 pub struct RoadLaneConfig {
     pub n_lanes_forward: u8,
     pub n_lanes_backward: u8,
+    // an outermost bike lane on each side that has lanes
+    #[serde(default)]
+    pub bike_lanes: bool,
+}
+
+impl RoadLaneConfig {
+    pub fn new(n_lanes_forward: u8, n_lanes_backward: u8) -> Option<Self> {
+        if n_lanes_forward + n_lanes_backward > 0 {
+            Some(RoadLaneConfig {
+                n_lanes_forward,
+                n_lanes_backward,
+                bike_lanes: false,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn is_one_way(&self) -> bool {
+        self.n_lanes_forward == 0 || self.n_lanes_backward == 0
+    }
+
+    pub fn with_bike_lanes(self, bike_lanes: bool) -> Self {
+        RoadLaneConfig { bike_lanes, ..self }
+    }
+
+    fn bike_lane_width(&self, n_lanes: u8) -> N {
+        if self.bike_lanes && n_lanes > 0 {
+            BIKE_LANE_DISTANCE
+        } else {
+            0.0
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct TurnPocket {
+    pub length: N,
+    pub lane_use: ConnectionRole,
+}
+
+impl TurnPocket {
+    // pockets for turning across oncoming traffic go next to the center line
+    pub fn is_inner(&self) -> bool {
+        (self.lane_use.inner_turn || self.lane_use.u_turn) && !self.lane_use.outer_turn
+    }
+}
+
+#[derive(Compact, Clone, Default, Debug, Serialize, Deserialize)]
+pub struct LaneMarkings {
+    // lanes are identified like their offset from the center line:
+    // 1, 2, ... for forward lanes and -1, -2, ... for backward lanes,
+    // lanes without a fixed lane use get one assigned automatically
+    pub lane_uses: CVec<(i8, ConnectionRole)>,
+    pub turn_pocket_forward: Option<TurnPocket>,
+    pub turn_pocket_backward: Option<TurnPocket>,
+}
+
+impl LaneMarkings {
+    pub fn lane_use(&self, lane: i8) -> Option<ConnectionRole> {
+        self.lane_uses
+            .iter()
+            .find(|&&(marked_lane, _)| marked_lane == lane)
+            .map(|&(_, lane_use)| lane_use)
+    }
+
+    pub fn set_lane_use(&mut self, lane: i8, maybe_lane_use: Option<ConnectionRole>) {
+        self.lane_uses
+            .retain(|&(marked_lane, _)| marked_lane != lane);
+        if let Some(lane_use) = maybe_lane_use {
+            self.lane_uses.push((lane, lane_use));
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum RoadEnd {
+    // connects to the roads on the given level through an intersection
+    Intersection(i8),
+    // runs alongside the lanes of a road on the given level and joins them through switch lanes
+    Merge(i8),
+}
+
+impl RoadEnd {
+    pub fn intersection_level(self) -> Option<i8> {
+        match self {
+            RoadEnd::Intersection(level) => Some(level),
+            RoadEnd::Merge(_) => None,
+        }
+    }
+
+    pub fn merge_level(self) -> Option<i8> {
+        match self {
+            RoadEnd::Intersection(_) => None,
+            RoadEnd::Merge(level) => Some(level),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct RoadElevation {
+    // level 0 is at grade, positive levels are bridges and negative levels are tunnels,
+    // roads only form intersections with roads on the same level
+    pub level: i8,
+    pub start: RoadEnd,
+    pub end: RoadEnd,
+}
+
+impl Default for RoadElevation {
+    fn default() -> Self {
+        RoadElevation::at_level(0)
+    }
+}
+
+impl RoadElevation {
+    pub fn at_level(level: i8) -> Self {
+        RoadElevation {
+            level,
+            start: RoadEnd::Intersection(level),
+            end: RoadEnd::Intersection(level),
+        }
+    }
+
+    pub fn grade_separated(level: i8) -> Self {
+        RoadElevation {
+            level,
+            start: RoadEnd::Intersection(0),
+            end: RoadEnd::Intersection(0),
+        }
+    }
+
+    pub fn ramp(start: RoadEnd, end: RoadEnd) -> Self {
+        RoadElevation {
+            level: 0,
+            start,
+            end,
+        }
+    }
+
+    pub fn is_ramp(&self) -> bool {
+        match (self.start, self.end) {
+            (RoadEnd::Merge(_), _) | (_, RoadEnd::Merge(_)) => true,
+            _ => false,
+        }
+    }
+
+    // ramps change their level along the way, so they only connect at their ends
+    pub fn body_level(&self) -> Option<i8> {
+        if self.is_ramp() {
+            None
+        } else {
+            Some(self.level)
+        }
+    }
 }
 
 #[derive(Compact, Clone, Debug, Serialize, Deserialize)]
 pub struct RoadIntent {
     pub path: EditArcLinePath,
     pub lane_config: RoadLaneConfig,
+    #[serde(default)]
+    pub lane_markings: LaneMarkings,
+    #[serde(default)]
+    pub elevation: RoadElevation,
 }
 
 impl RoadIntent {
     pub fn new<V: Into<VecLike<Corner>>>(corners: V, lane_config: RoadLaneConfig) -> Self {
-        RoadIntent { path: EditArcLinePath::new(corners, ResolutionStrategy::AssumeSmooth, Closedness::NeverClosed), lane_config }
+        RoadIntent {
+            path: EditArcLinePath::new(
+                corners,
+                ResolutionStrategy::AssumeSmooth,
+                Closedness::NeverClosed,
+            ),
+            lane_config,
+            lane_markings: LaneMarkings::default(),
+            elevation: RoadElevation::default(),
+        }
+    }
+
+    pub fn with_lane_config(&self, lane_config: RoadLaneConfig) -> Self {
+        RoadIntent {
+            lane_config,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_lane_markings(&self, lane_markings: LaneMarkings) -> Self {
+        RoadIntent {
+            lane_markings,
+            ..self.clone()
+        }
+    }
+
+    pub fn with_elevation(&self, elevation: RoadElevation) -> Self {
+        RoadIntent {
+            elevation,
+            ..self.clone()
+        }
     }
 }
 
@@ -63,15 +253,19 @@ impl RoadPrototype {
 }
 
 #[derive(Compact, Clone, Serialize, Deserialize, Debug)]
-pub struct LanePrototype(pub LinePath, pub CVec<bool>);
+pub struct LanePrototype(pub LinePath, pub CVec<bool>, pub LaneKind);
 
 impl LanePrototype {
     pub fn morphable_from(&self, other: &LanePrototype) -> bool {
         match (self, other) {
             (
-                &LanePrototype(ref path_1, ref timings_1),
-                &LanePrototype(ref path_2, ref timings_2),
-            ) => path_1.rough_eq_by(path_2, 0.05) && timings_1[..] == timings_2[..],
+                &LanePrototype(ref path_1, ref timings_1, kind_1),
+                &LanePrototype(ref path_2, ref timings_2, kind_2),
+            ) => {
+                path_1.rough_eq_by(path_2, 0.05)
+                    && timings_1[..] == timings_2[..]
+                    && kind_1 == kind_2
+            }
         }
     }
 }
@@ -91,10 +285,10 @@ impl SwitchLanePrototype {
 
 #[derive(Copy, Clone, Serialize, Deserialize, Debug)]
 pub struct ConnectionRole {
-    straight: bool,
-    u_turn: bool,
-    inner_turn: bool,
-    outer_turn: bool,
+    pub straight: bool,
+    pub u_turn: bool,
+    pub inner_turn: bool,
+    pub outer_turn: bool,
 }
 
 #[derive(Compact, Clone, Serialize, Deserialize, Debug)]
@@ -102,13 +296,15 @@ pub struct IntersectionConnector {
     position: P2,
     direction: V2,
     role: ConnectionRole,
+    fixed_role: Option<ConnectionRole>,
 }
 
 impl IntersectionConnector {
-    fn new(position: P2, direction: V2) -> Self {
+    fn new(position: P2, direction: V2, fixed_role: Option<ConnectionRole>) -> Self {
         IntersectionConnector {
             position,
             direction,
+            fixed_role,
             role: ConnectionRole {
                 straight: false,
                 u_turn: false,
@@ -120,21 +316,31 @@ impl IntersectionConnector {
 }
 
 #[derive(Copy, Clone, Hash, PartialEq, Eq, Serialize, Deserialize, Debug)]
-pub struct GestureSideID(i16);
+pub struct GestureSideID(i16, bool);
 
 impl GestureSideID {
     pub fn new_forward(gesture_idx: usize) -> Self {
-        GestureSideID((gesture_idx + 1) as i16)
+        GestureSideID((gesture_idx + 1) as i16, false)
     }
 
     pub fn new_backward(gesture_idx: usize) -> Self {
-        GestureSideID(-((gesture_idx + 1) as i16))
+        GestureSideID(-((gesture_idx + 1) as i16), false)
+    }
+
+    // bike lanes of a side are kept apart from its other lanes
+    pub fn bike_lanes_of(self) -> Self {
+        GestureSideID(self.0, true)
+    }
+
+    pub fn is_bike(self) -> bool {
+        self.1
     }
 }
 
 #[derive(Compact, Clone, Serialize, Deserialize, Debug)]
 pub struct IntersectionPrototype {
     area: Area,
+    level: i8,
     incoming: CHashMap<GestureSideID, CVec<IntersectionConnector>>,
     outgoing: CHashMap<GestureSideID, CVec<IntersectionConnector>>,
     pub connecting_lanes: CHashMap<(GestureSideID, GestureSideID), CVec<LanePrototype>>,
@@ -142,8 +348,30 @@ pub struct IntersectionPrototype {
 
 impl IntersectionPrototype {
     pub fn morphable_from(&self, other: &IntersectionPrototype) -> bool {
-        // TODO: make this better!!
-        (&self.area).rough_eq_by(&other.area, 0.1)
+        // an intersection only consists of its connecting lanes, so it can be kept
+        // as long as they all stay the same, even if its outline changed because
+        // the lane config of a connected road was edited
+        let own_lanes = self
+            .connecting_lanes
+            .values()
+            .flat_map(|group| group.iter())
+            .collect::<Vec<_>>();
+        let other_lanes = other
+            .connecting_lanes
+            .values()
+            .flat_map(|group| group.iter())
+            .collect::<Vec<_>>();
+
+        if own_lanes.is_empty() && other_lanes.is_empty() {
+            (&self.area).rough_eq_by(&other.area, 0.1)
+        } else {
+            own_lanes.len() == other_lanes.len()
+                && own_lanes.iter().all(|own_lane| {
+                    other_lanes
+                        .iter()
+                        .any(|other_lane| own_lane.morphable_from(other_lane))
+                })
+        }
     }
 }
 
@@ -185,6 +413,20 @@ pub fn gesture_intent_smooth_paths(
         .collect::<Vec<_>>()
 }
 
+fn road_intent_for(
+    history: &PlanHistory<CBGestureIntent>,
+    gesture_id: GestureID,
+) -> Option<&RoadIntent> {
+    match history
+        .gestures
+        .get(gesture_id)
+        .map(|VersionedGesture(gesture, _)| &gesture.intent)
+    {
+        Some(CBGestureIntent::Road(road_intent)) => Some(road_intent),
+        _ => None,
+    }
+}
+
 #[allow(clippy::cognitive_complexity)]
 pub fn calculate_prototypes(
     history: &PlanHistory<CBGestureIntent>,
@@ -192,6 +434,18 @@ pub fn calculate_prototypes(
 ) -> Result<Vec<Prototype<CBPrototypeKind>>, AreaError> {
     let gesture_intent_smooth_paths = gesture_intent_smooth_paths(history);
 
+    let elevations = gesture_intent_smooth_paths
+        .iter()
+        .map(|&(gesture_id, ..)| {
+            (
+                gesture_id,
+                road_intent_for(history, gesture_id)
+                    .map(|road_intent| road_intent.elevation)
+                    .unwrap_or_default(),
+            )
+        })
+        .collect::<HashMap<_, _>>();
+
     let gesture_areas_for_intersection = gesture_intent_smooth_paths
         .iter()
         .map(|&(gesture_id, step_id, road_intent, ref path)| {
@@ -199,12 +453,14 @@ pub fn calculate_prototypes(
                 Band::new_asymmetric(
                     path.clone(),
                     f32::from(road_intent.n_lanes_backward) * LANE_DISTANCE
+                        + road_intent.bike_lane_width(road_intent.n_lanes_backward)
                         + if road_intent.n_lanes_backward > 0 {
                             1.2 * LANE_DISTANCE
                         } else {
                             0.4 * LANE_DISTANCE
                         },
                     f32::from(road_intent.n_lanes_forward) * LANE_DISTANCE
+                        + road_intent.bike_lane_width(road_intent.n_lanes_forward)
                         + if road_intent.n_lanes_forward > 0 {
                             1.2 * LANE_DISTANCE
                         } else {
@@ -218,7 +474,9 @@ pub fn calculate_prototypes(
         })
         .collect::<Vec<_>>();
 
-    let mut road_intersection_embedding = AreaEmbedding::new(15.0);
+    // grade separated roads don't intersect what they cross,
+    // so intersections are only found among the roads on each level
+    let mut road_intersection_embeddings = BTreeMap::new();
 
     #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
     enum RoadPart {
@@ -228,10 +486,15 @@ pub fn calculate_prototypes(
     }
 
     for (gesture_area, gesture_id, step_id) in &gesture_areas_for_intersection {
-        road_intersection_embedding.insert(
-            gesture_area.clone(),
-            (*gesture_id, *step_id, RoadPart::Road),
-        );
+        if let Some(level) = elevations[gesture_id].body_level() {
+            road_intersection_embeddings
+                .entry(level)
+                .or_insert_with(|| AreaEmbedding::new(15.0))
+                .insert(
+                    gesture_area.clone(),
+                    (*gesture_id, *step_id, RoadPart::Road),
+                );
+        }
     }
 
     // add intersections at the starts and ends of gestures
@@ -249,9 +512,11 @@ pub fn calculate_prototypes(
                 let half_depth = direction * ROAD_CAP_DEPTH / 2.0;
                 let width_backward = orthogonal
                     * (f32::from(road_intent.n_lanes_backward) * LANE_DISTANCE
+                        + road_intent.bike_lane_width(road_intent.n_lanes_backward)
                         + 0.4 * LANE_DISTANCE);
                 let width_forward = orthogonal
                     * (f32::from(road_intent.n_lanes_forward) * LANE_DISTANCE
+                        + road_intent.bike_lane_width(road_intent.n_lanes_forward)
                         + 0.4 * LANE_DISTANCE);
                 (
                     Area::new_simple(
@@ -278,36 +543,56 @@ pub fn calculate_prototypes(
     );
 
     for (road_cap_area, road_cap_label) in road_caps {
-        road_intersection_embedding.insert(road_cap_area, road_cap_label);
+        let (gesture_id, _, role) = road_cap_label;
+        let maybe_level = match role {
+            RoadPart::StartCap => elevations[&gesture_id].start.intersection_level(),
+            RoadPart::EndCap => elevations[&gesture_id].end.intersection_level(),
+            RoadPart::Road => unreachable!(),
+        };
+
+        if let Some(level) = maybe_level {
+            road_intersection_embeddings
+                .entry(level)
+                .or_insert_with(|| AreaEmbedding::new(15.0))
+                .insert(road_cap_area, road_cap_label);
+        }
     }
 
-    let mut intersection_prototypes: Vec<_> = road_intersection_embedding
-        .view(AreaFilter::Function(Box::new(|labels| labels.len() >= 2)))
-        .get_areas_with_pieces()?
-        .into_iter()
-        .map(|(area, pieces)| {
-            let mut influenced_id = PrototypeID::from_influences(
-                pieces
-                    .iter()
-                    .map(|(_piece, label)| label.own_right_label)
-                    .collect::<Vec<_>>(),
-            );
-            influenced_id = influenced_id.add_influences(vec![
-                pieces[0].0.start().x.to_bits(),
-                pieces[0].0.start().y.to_bits(),
-            ]);
-            Prototype {
-                representative_position: area.primitives[0].boundary.path().points[0],
-                kind: CBPrototypeKind::Road(RoadPrototype::Intersection(IntersectionPrototype {
-                    area,
-                    incoming: CHashMap::new(),
-                    outgoing: CHashMap::new(),
-                    connecting_lanes: CHashMap::new(),
-                })),
-                id: influenced_id,
-            }
-        })
-        .collect();
+    let mut intersection_prototypes = Vec::new();
+
+    for (level, road_intersection_embedding) in road_intersection_embeddings {
+        intersection_prototypes.extend(
+            road_intersection_embedding
+                .view(AreaFilter::Function(Box::new(|labels| labels.len() >= 2)))
+                .get_areas_with_pieces()?
+                .into_iter()
+                .map(|(area, pieces)| {
+                    let mut influenced_id = PrototypeID::from_influences(
+                        pieces
+                            .iter()
+                            .map(|(_piece, label)| label.own_right_label)
+                            .collect::<Vec<_>>(),
+                    );
+                    influenced_id = influenced_id.add_influences(vec![
+                        pieces[0].0.start().x.to_bits(),
+                        pieces[0].0.start().y.to_bits(),
+                    ]);
+                    Prototype {
+                        representative_position: area.primitives[0].boundary.path().points[0],
+                        kind: CBPrototypeKind::Road(RoadPrototype::Intersection(
+                            IntersectionPrototype {
+                                area,
+                                level,
+                                incoming: CHashMap::new(),
+                                outgoing: CHashMap::new(),
+                                connecting_lanes: CHashMap::new(),
+                            },
+                        )),
+                        id: influenced_id,
+                    }
+                }),
+        );
+    }
 
     let intersected_lane_paths = {
         let raw_lane_paths = gesture_intent_smooth_paths
@@ -315,29 +600,127 @@ pub fn calculate_prototypes(
             .enumerate()
             .flat_map(
                 |(gesture_i, &(gesture_id, step_id, road_intent, ref path))| {
+                    let lane_markings = road_intent_for(history, gesture_id)
+                        .map(|road_intent| road_intent.lane_markings.clone())
+                        .unwrap_or_default();
+                    let elevation = elevations[&gesture_id];
+
+                    // turn pockets are additional lanes in front of intersections,
+                    // inner ones move the regular lanes of their side one lane outwards
+                    let pocket_forward = lane_markings
+                        .turn_pocket_forward
+                        .filter(|_| road_intent.n_lanes_forward > 0);
+                    let pocket_backward = lane_markings
+                        .turn_pocket_backward
+                        .filter(|_| road_intent.n_lanes_backward > 0);
+                    let lane_offset = |lane_i: u8, maybe_pocket: Option<TurnPocket>| {
+                        let shift = match maybe_pocket {
+                            Some(pocket) if pocket.is_inner() => 1.0,
+                            _ => 0.0,
+                        };
+                        CENTER_LANE_DISTANCE / 2.0 + (f32::from(lane_i) + shift) * LANE_DISTANCE
+                    };
+                    let pocket_offset = |n_lanes: u8, pocket: TurnPocket| {
+                        if pocket.is_inner() {
+                            CENTER_LANE_DISTANCE / 2.0
+                        } else {
+                            CENTER_LANE_DISTANCE / 2.0 + f32::from(n_lanes) * LANE_DISTANCE
+                        }
+                    };
+
+                    let turn_pockets = pocket_forward
+                        .map(|pocket| {
+                            (
+                                pocket_offset(road_intent.n_lanes_forward, pocket),
+                                road_intent.n_lanes_forward as i8 + 1,
+                                Some(pocket),
+                                LaneKind::Mixed,
+                            )
+                        })
+                        .into_iter()
+                        .chain(pocket_backward.map(|pocket| {
+                            (
+                                -pocket_offset(road_intent.n_lanes_backward, pocket),
+                                -(road_intent.n_lanes_backward as i8) - 1,
+                                Some(pocket),
+                                LaneKind::Mixed,
+                            )
+                        }));
+
+                    // bike lanes are outside of everything else, including turn pockets
+                    let bike_lane_offset = |n_lanes: u8, has_pocket: bool| {
+                        CENTER_LANE_DISTANCE / 2.0
+                            + (f32::from(n_lanes) - 0.5 + if has_pocket { 1.0 } else { 0.0 })
+                                * LANE_DISTANCE
+                            + BIKE_LANE_DISTANCE / 2.0
+                    };
+                    let bike_lanes = Some((
+                        bike_lane_offset(road_intent.n_lanes_forward, pocket_forward.is_some()),
+                        BIKE_LANE_OFFSET_I,
+                        None,
+                        LaneKind::BikeOnly,
+                    ))
+                    .filter(|_| road_intent.bike_lanes && road_intent.n_lanes_forward > 0)
+                    .into_iter()
+                    .chain(
+                        Some((
+                            -bike_lane_offset(
+                                road_intent.n_lanes_backward,
+                                pocket_backward.is_some(),
+                            ),
+                            -BIKE_LANE_OFFSET_I,
+                            None,
+                            LaneKind::BikeOnly,
+                        ))
+                        .filter(|_| road_intent.bike_lanes && road_intent.n_lanes_backward > 0),
+                    );
+
                     (0..road_intent.n_lanes_forward)
                         .map(|lane_i| {
                             (
-                                CENTER_LANE_DISTANCE / 2.0 + f32::from(lane_i) * LANE_DISTANCE,
+                                lane_offset(lane_i, pocket_forward),
                                 lane_i as i8 + 1,
+                                None,
+                                LaneKind::Mixed,
                             )
                         })
                         .chain((0..road_intent.n_lanes_backward).map(|lane_i| {
                             (
-                                -(CENTER_LANE_DISTANCE / 2.0 + f32::from(lane_i) * LANE_DISTANCE),
+                                -lane_offset(lane_i, pocket_backward),
                                 -(lane_i as i8) - 1,
+                                None,
+                                LaneKind::Mixed,
                             )
                         }))
-                        .filter_map(|(offset, offset_i)| {
+                        .chain(turn_pockets)
+                        .chain(bike_lanes)
+                        .filter_map(|(offset, offset_i, maybe_pocket, kind)| {
                             path.shift_orthogonally(offset).map(|path| {
+                                let side_id = if offset < 0.0 {
+                                    GestureSideID::new_backward(gesture_i)
+                                } else {
+                                    GestureSideID::new_forward(gesture_i)
+                                };
                                 (
-                                    if offset < 0.0 {
-                                        GestureSideID::new_backward(gesture_i)
+                                    if kind == LaneKind::BikeOnly {
+                                        side_id.bike_lanes_of()
                                     } else {
-                                        GestureSideID::new_forward(gesture_i)
+                                        side_id
                                     },
                                     PrototypeID::from_influences((gesture_id, step_id, offset_i)),
                                     if offset < 0.0 { path.reverse() } else { path },
+                                    (
+                                        maybe_pocket
+                                            .map(|pocket: TurnPocket| pocket.lane_use)
+                                            .or_else(|| lane_markings.lane_use(offset_i)),
+                                        maybe_pocket.map(|pocket| pocket.length),
+                                    ),
+                                    if offset < 0.0 {
+                                        (elevation.end, elevation.body_level(), elevation.start)
+                                    } else {
+                                        (elevation.start, elevation.body_level(), elevation.end)
+                                    },
+                                    kind,
                                 )
                             })
                         })
@@ -348,7 +731,11 @@ pub fn calculate_prototypes(
 
         raw_lane_paths
             .into_iter()
-            .flat_map(|(gesture_side_id, lane_influence_id, raw_lane_path)| {
+            .flat_map(|raw_lane| {
+                let (gesture_side_id, lane_influence_id, raw_lane_path, marking, levels, kind) =
+                    raw_lane;
+                let (fixed_role, pocket_length) = marking;
+                let (lane_start, body_level, lane_end) = levels;
                 let mut start_trim = 0.0f32;
                 let mut start_influence = lane_influence_id;
                 let mut end_trim = raw_lane_path.length();
@@ -370,6 +757,37 @@ pub fn calculate_prototypes(
                         )
                             .intersect();
 
+                        // grade separated lanes only enter intersections on their own level,
+                        // which they might do only close to their ends
+                        let lane_length = raw_lane_path.length();
+                        let touched_from = if intersection.area.contains(raw_lane_path.start()) {
+                            0.0
+                        } else {
+                            points
+                                .iter()
+                                .map(|p| OrderedFloat(p.along_a))
+                                .min()
+                                .map_or(lane_length, |distance| *distance)
+                        };
+                        let touched_to = if intersection.area.contains(raw_lane_path.end()) {
+                            lane_length
+                        } else {
+                            points
+                                .iter()
+                                .map(|p| OrderedFloat(p.along_a))
+                                .max()
+                                .map_or(0.0, |distance| *distance)
+                        };
+                        let on_lane_level = Some(intersection.level) == body_level
+                            || (Some(intersection.level) == lane_start.intersection_level()
+                                && touched_from < ROAD_CAP_DEPTH)
+                            || (Some(intersection.level) == lane_end.intersection_level()
+                                && touched_to > lane_length - ROAD_CAP_DEPTH);
+
+                        if !on_lane_level {
+                            continue;
+                        }
+
                         if points.len() >= 2 {
                             let entry_distance = points
                                 .iter()
@@ -386,26 +804,33 @@ pub fn calculate_prototypes(
                                 IntersectionConnector::new(
                                     raw_lane_path.along(*entry_distance),
                                     raw_lane_path.direction_along(*entry_distance),
+                                    fixed_role,
                                 ),
                             );
-                            intersection.outgoing.push_at(
-                                gesture_side_id,
-                                IntersectionConnector::new(
-                                    raw_lane_path.along(*exit_distance),
-                                    raw_lane_path.direction_along(*exit_distance),
-                                ),
-                            );
-                            cuts.push((*entry_distance, *exit_distance, *intersection_id));
-                        } else if points.len() == 1 {
-                            if intersection.area.contains(raw_lane_path.start()) {
-                                let exit_distance = points[0].along_a;
+                            if pocket_length.is_none() {
                                 intersection.outgoing.push_at(
                                     gesture_side_id,
                                     IntersectionConnector::new(
-                                        raw_lane_path.along(exit_distance),
-                                        raw_lane_path.direction_along(exit_distance),
+                                        raw_lane_path.along(*exit_distance),
+                                        raw_lane_path.direction_along(*exit_distance),
+                                        None,
                                     ),
                                 );
+                            }
+                            cuts.push((*entry_distance, *exit_distance, *intersection_id));
+                        } else if points.len() == 1 {
+                            if intersection.area.contains(raw_lane_path.start()) {
+                                let exit_distance = points[0].along_a;
+                                if pocket_length.is_none() {
+                                    intersection.outgoing.push_at(
+                                        gesture_side_id,
+                                        IntersectionConnector::new(
+                                            raw_lane_path.along(exit_distance),
+                                            raw_lane_path.direction_along(exit_distance),
+                                            None,
+                                        ),
+                                    );
+                                }
                                 if exit_distance > start_trim {
                                     start_trim = exit_distance;
                                     start_influence = *intersection_id;
@@ -417,6 +842,7 @@ pub fn calculate_prototypes(
                                     IntersectionConnector::new(
                                         raw_lane_path.along(entry_distance),
                                         raw_lane_path.direction_along(entry_distance),
+                                        fixed_role,
                                     ),
                                 );
                                 if entry_distance < end_trim {
@@ -441,11 +867,27 @@ pub fn calculate_prototypes(
                             (_, exit_distance, exit_influence),
                             (entry_distance, _, entry_influence),
                         ) = (two_cuts[0], two_cuts[1]);
+                        let subsection_start = match pocket_length {
+                            Some(length) if entry_distance < raw_lane_path.length() => {
+                                (entry_distance - length).max(exit_distance)
+                            }
+                            // turn pockets only exist in front of intersections
+                            Some(_) => return None,
+                            None => exit_distance,
+                        };
                         let subsection_id =
                             lane_influence_id.add_influences((exit_influence, entry_influence));
+                        // ramps only reach the level they merge with at their very ends
+                        let switch_levels = (
+                            lane_start.merge_level().filter(|_| subsection_start <= 0.0),
+                            body_level,
+                            lane_end
+                                .merge_level()
+                                .filter(|_| entry_distance >= raw_lane_path.length()),
+                        );
                         raw_lane_path
-                            .subsection(exit_distance, entry_distance)
-                            .map(|subsection| (subsection, subsection_id))
+                            .subsection(subsection_start, entry_distance)
+                            .map(|subsection| (subsection, subsection_id, switch_levels, kind))
                     })
                     .collect::<Vec<_>>()
             })
@@ -455,48 +897,94 @@ pub fn calculate_prototypes(
     let switch_lane_paths = {
         #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
         enum SwitchLaneLabel {
-            Left(PrototypeID),
-            Right(PrototypeID),
+            Left(PrototypeID, i8),
+            Right(PrototypeID, i8),
         };
 
+        // lanes of ramps have no level of their own,
+        // they can only switch close to where they merge with the lanes of that level
+        fn switching_stretches(
+            path: &LinePath,
+            switch_levels: (Option<i8>, Option<i8>, Option<i8>),
+        ) -> Vec<(LinePath, i8)> {
+            let (start_merge_level, body_level, end_merge_level) = switch_levels;
+            if let Some(level) = body_level {
+                vec![(path.clone(), level)]
+            } else {
+                let length = path.length();
+                start_merge_level
+                    .and_then(|level| {
+                        path.subsection(0.0, RAMP_MERGE_LENGTH.min(length))
+                            .map(|stretch| (stretch, level))
+                    })
+                    .into_iter()
+                    .chain(end_merge_level.and_then(|level| {
+                        path.subsection((length - RAMP_MERGE_LENGTH).max(0.0), length)
+                            .map(|stretch| (stretch, level))
+                    }))
+                    .collect()
+            }
+        }
+
         let mut switch_lane_embedding = AreaEmbedding::new(30.0);
 
-        let right_lane_bands = intersected_lane_paths.iter().filter_map(|(path, id)| {
-            path.shift_orthogonally(0.5 * LANE_DISTANCE + 0.5 * SWITCHING_LANE_OVERLAP_TOLERANCE)
+        // bikes can't switch to other lanes
+        let right_lane_bands = intersected_lane_paths
+            .iter()
+            .filter(|(.., kind)| *kind == LaneKind::Mixed)
+            .flat_map(|(path, id, switch_levels, _)| {
+                switching_stretches(path, *switch_levels)
+                    .into_iter()
+                    .map(move |(stretch, level)| (stretch, *id, level))
+            })
+            .filter_map(|(path, id, level)| {
+                path.shift_orthogonally(
+                    0.5 * LANE_DISTANCE + 0.5 * SWITCHING_LANE_OVERLAP_TOLERANCE,
+                )
                 .map(|right_path| {
                     let band =
                         Band::new(right_path.clone(), SWITCHING_LANE_OVERLAP_TOLERANCE * 2.0);
-                    (band.as_area(), *id)
+                    (band.as_area(), id, level)
                 })
-        });
+            });
 
-        for (band_area, id) in right_lane_bands {
-            switch_lane_embedding.insert(band_area, SwitchLaneLabel::Right(id))
+        for (band_area, id, level) in right_lane_bands {
+            switch_lane_embedding.insert(band_area, SwitchLaneLabel::Right(id, level))
         }
 
-        let left_lane_bands = intersected_lane_paths.iter().filter_map(|(path, id)| {
-            path.shift_orthogonally(-0.5 * LANE_DISTANCE - 0.5 * SWITCHING_LANE_OVERLAP_TOLERANCE)
+        let left_lane_bands = intersected_lane_paths
+            .iter()
+            .filter(|(.., kind)| *kind == LaneKind::Mixed)
+            .flat_map(|(path, id, switch_levels, _)| {
+                switching_stretches(path, *switch_levels)
+                    .into_iter()
+                    .map(move |(stretch, level)| (stretch, *id, level))
+            })
+            .filter_map(|(path, id, level)| {
+                path.shift_orthogonally(
+                    -0.5 * LANE_DISTANCE - 0.5 * SWITCHING_LANE_OVERLAP_TOLERANCE,
+                )
                 .map(|left_path| {
                     let band = Band::new(left_path.clone(), SWITCHING_LANE_OVERLAP_TOLERANCE * 2.0);
-                    (band.as_area(), *id)
+                    (band.as_area(), id, level)
                 })
-        });
+            });
 
-        for (band_area, id) in left_lane_bands {
-            switch_lane_embedding.insert(band_area, SwitchLaneLabel::Left(id))
+        for (band_area, id, level) in left_lane_bands {
+            switch_lane_embedding.insert(band_area, SwitchLaneLabel::Left(id, level))
         }
 
         switch_lane_embedding
             .view(AreaFilter::Function(Box::new(|labels| {
                 labels.iter().any(|label| {
-                    if let SwitchLaneLabel::Left(_) = label {
-                        true
-                    } else {
-                        false
-                    }
-                }) && labels.iter().any(|label| {
-                    if let SwitchLaneLabel::Right(_) = label {
-                        true
+                    if let SwitchLaneLabel::Left(_, left_level) = label {
+                        labels.iter().any(|other_label| {
+                            if let SwitchLaneLabel::Right(_, right_level) = other_label {
+                                left_level == right_level
+                            } else {
+                                false
+                            }
+                        })
                     } else {
                         false
                     }
@@ -505,7 +993,7 @@ pub fn calculate_prototypes(
             .get_unique_pieces()
             .into_iter()
             .filter_map(|(piece, piece_area_label)| {
-                if let SwitchLaneLabel::Right(own_id) = piece_area_label.own_right_label {
+                if let SwitchLaneLabel::Right(own_id, _) = piece_area_label.own_right_label {
                     if piece.length() > MIN_SWITCHING_LANE_LENGTH {
                         let mut influenced_id = PrototypeID::from_influences(own_id);
                         influenced_id = influenced_id.add_influences(
@@ -541,11 +1029,12 @@ pub fn calculate_prototypes(
         .chain(
             intersected_lane_paths
                 .into_iter()
-                .map(|(path, id)| Prototype {
+                .map(|(path, id, _, kind)| Prototype {
                     representative_position: path.points[0],
                     kind: CBPrototypeKind::Road(RoadPrototype::Lane(LanePrototype(
                         path,
                         CVec::new(),
+                        kind,
                     ))),
                     id,
                 }),
@@ -566,3 +1055,8 @@ pub fn calculate_prototypes(
         )
         .collect())
 }
+
+// identifies bike lanes among the numbered lanes of a gesture
+const BIKE_LANE_OFFSET_I: i8 = 100;
+// how far from its merging end a ramp can switch onto the lanes of that level
+const RAMP_MERGE_LENGTH: N = 50.0;
@@ -61,12 +61,22 @@ impl TransportUIID {
     pub fn on_car_info(self, from_lane: RawID, infos: CVec < CarRenderInfo >, world: &mut World) {
         world.send(self.as_raw(), MSG_TransportUI_on_car_info(from_lane, infos));
     }
+    
+    pub fn on_incidents_changed(self, lane: RawID, incidents: CVec < IncidentRenderInfo >, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransportUI_on_incidents_changed(lane, incidents));
+    }
+    
+    pub fn on_detectors_changed(self, lane: RawID, detectors: CVec < DetectorInfo >, world: &mut World) {
+        world.send(self.as_raw(), MSG_TransportUI_on_detectors_changed(lane, detectors));
+    }
 
     pub fn register_trait(system: &mut ActorSystem) {
         system.register_trait::<TransportUIRepresentative>();
         system.register_trait_message::<MSG_TransportUI_on_lane_constructed>();
         system.register_trait_message::<MSG_TransportUI_on_lane_destructed>();
         system.register_trait_message::<MSG_TransportUI_on_car_info>();
+        system.register_trait_message::<MSG_TransportUI_on_incidents_changed>();
+        system.register_trait_message::<MSG_TransportUI_on_detectors_changed>();
     }
 
     pub fn register_implementor<Act: Actor + TransportUI>(system: &mut ActorSystem) {
@@ -88,6 +98,18 @@ impl TransportUIID {
                 instance.on_car_info(from_lane, infos, world); Fate::Live
             }, false
         );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_TransportUI_on_incidents_changed(lane, ref incidents), instance, world| {
+                instance.on_incidents_changed(lane, incidents, world); Fate::Live
+            }, false
+        );
+        
+        system.add_handler::<Act, _, _>(
+            |&MSG_TransportUI_on_detectors_changed(lane, ref detectors), instance, world| {
+                instance.on_detectors_changed(lane, detectors, world); Fate::Live
+            }, false
+        );
     }
 }
 
@@ -97,6 +119,10 @@ struct MSG_TransportUI_on_lane_constructed(pub RawID, pub LinePath, pub bool, pu
 struct MSG_TransportUI_on_lane_destructed(pub RawID, pub bool, pub bool);
 #[derive(Compact, Clone)] #[allow(non_camel_case_types)]
 struct MSG_TransportUI_on_car_info(pub RawID, pub CVec < CarRenderInfo >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransportUI_on_incidents_changed(pub RawID, pub CVec < IncidentRenderInfo >);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_TransportUI_on_detectors_changed(pub RawID, pub CVec < DetectorInfo >);
 
 
 
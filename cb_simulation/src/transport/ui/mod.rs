@@ -4,6 +4,9 @@ use kay::{ActorSystem, World, TypedID, RawID};
 use michelangelo::Mesh;
 use super::lane::{Lane, LaneID, SwitchLane, SwitchLaneID};
 use transport::pathfinding::trip::TripID;
+use transport::microtraffic::VehicleType;
+use transport::incidents::IncidentRenderInfo;
+use transport::detectors::DetectorInfo;
 
 use dimensions::{LANE_DISTANCE, LANE_WIDTH, LANE_MARKER_WIDTH, LANE_MARKER_DASH_GAP,
 LANE_MARKER_DASH_LENGTH};
@@ -15,6 +18,7 @@ pub struct CarRenderInfo {
     pub position: [f32; 2],
     pub direction: [f32; 2],
     pub trip: TripID,
+    pub vehicle_type: VehicleType,
 }
 
 pub trait TransportUI {
@@ -35,6 +39,13 @@ pub trait TransportUI {
         _world: &mut World,
     );
     fn on_car_info(&mut self, from_lane: RawID, infos: &CVec<CarRenderInfo>, _: &mut World);
+    fn on_incidents_changed(
+        &mut self,
+        lane: RawID,
+        incidents: &CVec<IncidentRenderInfo>,
+        _: &mut World,
+    );
+    fn on_detectors_changed(&mut self, lane: RawID, detectors: &CVec<DetectorInfo>, _: &mut World);
 }
 
 impl Lane {
@@ -51,6 +62,7 @@ impl Lane {
                     position: [position2d.x, position2d.y],
                     direction: [direction.x, direction.y],
                     trip: car.trip,
+                    vehicle_type: car.vehicle_type,
                 })
             }
         }
@@ -102,6 +114,14 @@ impl Lane {
             self.connectivity.on_intersection,
             world,
         );
+
+        if !self.microtraffic.incidents.is_empty() {
+            ui.on_incidents_changed(self.id.as_raw(), self.incident_render_infos(), world);
+        }
+
+        if !self.microtraffic.detectors.is_empty() {
+            ui.on_detectors_changed(self.id.as_raw(), self.detector_infos(), world);
+        }
     }
 }
 
@@ -136,6 +156,7 @@ impl SwitchLane {
                     position: [shifted_position2d.x, shifted_position2d.y],
                     direction: [rotated_direction.x, rotated_direction.y],
                     trip: car.trip,
+                    vehicle_type: car.vehicle_type,
                 })
             }
         }
@@ -168,6 +189,22 @@ pub fn on_build_switch(lane: &SwitchLane, world: &mut World) {
     );
 }
 
+pub fn on_incidents_changed(lane: &Lane, world: &mut World) {
+    TransportUIID::global_broadcast(world).on_incidents_changed(
+        lane.id.as_raw(),
+        lane.incident_render_infos(),
+        world,
+    );
+}
+
+pub fn on_detectors_changed(lane: &Lane, world: &mut World) {
+    TransportUIID::global_broadcast(world).on_detectors_changed(
+        lane.id.as_raw(),
+        lane.detector_infos(),
+        world,
+    );
+}
+
 pub fn on_unbuild(lane: &Lane, world: &mut World) {
     TransportUIID::global_broadcast(world).on_lane_destructed(
         lane.id.as_raw(),
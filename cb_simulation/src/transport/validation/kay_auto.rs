@@ -0,0 +1,200 @@
+//! This is all auto-generated. Do not touch.
+#![rustfmt::skip]
+#[allow(unused_imports)]
+use kay::{ActorSystem, TypedID, RawID, Fate, Actor, TraitIDFrom, ActorOrActorTrait};
+#[allow(unused_imports)]
+use super::*;
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct NetworkValidationListenerID {
+    _raw_id: RawID
+}
+
+impl Copy for NetworkValidationListenerID {}
+impl Clone for NetworkValidationListenerID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for NetworkValidationListenerID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "NetworkValidationListenerID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for NetworkValidationListenerID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for NetworkValidationListenerID {
+    fn eq(&self, other: &NetworkValidationListenerID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for NetworkValidationListenerID {}
+
+pub struct NetworkValidationListenerRepresentative;
+
+impl ActorOrActorTrait for NetworkValidationListenerRepresentative {
+    type ID = NetworkValidationListenerID;
+}
+
+impl TypedID for NetworkValidationListenerID {
+    type Target = NetworkValidationListenerRepresentative;
+
+    fn from_raw(id: RawID) -> Self {
+        NetworkValidationListenerID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl<Act: Actor + NetworkValidationListener> TraitIDFrom<Act> for NetworkValidationListenerID {}
+
+impl NetworkValidationListenerID {
+    pub fn on_network_validated(self, projects: CVec < ProjectID >, issues: CVec < NetworkIssue >, world: &mut World) {
+        world.send(self.as_raw(), MSG_NetworkValidationListener_on_network_validated(projects, issues));
+    }
+
+    pub fn register_trait(system: &mut ActorSystem) {
+        system.register_trait::<NetworkValidationListenerRepresentative>();
+        system.register_trait_message::<MSG_NetworkValidationListener_on_network_validated>();
+    }
+
+    pub fn register_implementor<Act: Actor + NetworkValidationListener>(system: &mut ActorSystem) {
+        system.register_implementor::<Act, NetworkValidationListenerRepresentative>();
+        system.add_handler::<Act, _, _>(
+            |&MSG_NetworkValidationListener_on_network_validated(ref projects, ref issues), instance, world| {
+                instance.on_network_validated(projects, issues, world); Fate::Live
+            }, false
+        );
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_NetworkValidationListener_on_network_validated(pub CVec < ProjectID >, pub CVec < NetworkIssue >);
+impl Actor for NetworkValidator {
+    type ID = NetworkValidatorID;
+
+    fn id(&self) -> Self::ID {
+        self.id
+    }
+    unsafe fn set_id(&mut self, id: RawID) {
+        self.id = Self::ID::from_raw(id);
+    }
+}
+
+#[derive(Serialize, Deserialize)] #[serde(transparent)]
+pub struct NetworkValidatorID {
+    _raw_id: RawID
+}
+
+impl Copy for NetworkValidatorID {}
+impl Clone for NetworkValidatorID { fn clone(&self) -> Self { *self } }
+impl ::std::fmt::Debug for NetworkValidatorID {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        write!(f, "NetworkValidatorID({:?})", self._raw_id)
+    }
+}
+impl ::std::hash::Hash for NetworkValidatorID {
+    fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+        self._raw_id.hash(state);
+    }
+}
+impl PartialEq for NetworkValidatorID {
+    fn eq(&self, other: &NetworkValidatorID) -> bool {
+        self._raw_id == other._raw_id
+    }
+}
+impl Eq for NetworkValidatorID {}
+
+impl TypedID for NetworkValidatorID {
+    type Target = NetworkValidator;
+
+    fn from_raw(id: RawID) -> Self {
+        NetworkValidatorID { _raw_id: id }
+    }
+
+    fn as_raw(&self) -> RawID {
+        self._raw_id
+    }
+}
+
+impl NetworkValidatorID {
+    pub fn spawn(time: TimeID, world: &mut World) -> Self {
+        let id = NetworkValidatorID::from_raw(world.allocate_instance_id::<NetworkValidator>());
+        let swarm = world.local_broadcast::<NetworkValidator>();
+        world.send(swarm, MSG_NetworkValidator_spawn(id, time));
+        id
+    }
+    
+    pub fn validate_after_construction(self, project_id: ProjectID, world: &mut World) {
+        world.send(self.as_raw(), MSG_NetworkValidator_validate_after_construction(project_id));
+    }
+    
+    pub fn on_lane_report(self, info: LaneValidationInfo, world: &mut World) {
+        world.send(self.as_raw(), MSG_NetworkValidator_on_lane_report(info));
+    }
+    
+    pub fn on_unattached_building(self, position: P2, world: &mut World) {
+        world.send(self.as_raw(), MSG_NetworkValidator_on_unattached_building(position));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_NetworkValidator_spawn(pub NetworkValidatorID, pub TimeID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_NetworkValidator_validate_after_construction(pub ProjectID);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_NetworkValidator_on_lane_report(pub LaneValidationInfo);
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_NetworkValidator_on_unattached_building(pub P2);
+
+impl Into<SleeperID> for NetworkValidatorID {
+    fn into(self) -> SleeperID {
+        SleeperID::from_raw(self.as_raw())
+    }
+}
+
+impl LaneID {
+    pub fn report_for_validation(self, validator: NetworkValidatorID, world: &mut World) {
+        world.send(self.as_raw(), MSG_Lane_report_for_validation(validator));
+    }
+}
+
+#[derive(Compact, Clone)] #[allow(non_camel_case_types)]
+struct MSG_Lane_report_for_validation(pub NetworkValidatorID);
+
+#[allow(unused_variables)]
+#[allow(unused_mut)]
+pub fn auto_setup(system: &mut ActorSystem) {
+    NetworkValidationListenerID::register_trait(system);
+    SleeperID::register_implementor::<NetworkValidator>(system);
+    system.add_spawner::<NetworkValidator, _, _>(
+        |&MSG_NetworkValidator_spawn(id, time), world| {
+            NetworkValidator::spawn(id, time, world)
+        }, false
+    );
+    
+    system.add_handler::<NetworkValidator, _, _>(
+        |&MSG_NetworkValidator_validate_after_construction(project_id), instance, world| {
+            instance.validate_after_construction(project_id, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<NetworkValidator, _, _>(
+        |&MSG_NetworkValidator_on_lane_report(ref info), instance, world| {
+            instance.on_lane_report(info, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<NetworkValidator, _, _>(
+        |&MSG_NetworkValidator_on_unattached_building(position), instance, world| {
+            instance.on_unattached_building(position, world); Fate::Live
+        }, false
+    );
+    
+    system.add_handler::<Lane, _, _>(
+        |&MSG_Lane_report_for_validation(validator), instance, world| {
+            instance.report_for_validation(validator, world); Fate::Live
+        }, false
+    );
+}
\ No newline at end of file
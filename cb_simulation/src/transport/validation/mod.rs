@@ -0,0 +1,320 @@
+use kay::{ActorSystem, World, TypedID, Actor};
+use compact::CVec;
+use descartes::P2;
+use std::collections::HashMap;
+use cb_time::actors::{Sleeper, SleeperID, TimeID};
+use cb_time::units::{Instant, Ticks};
+use cb_planning::plan_manager::ProjectID;
+
+use super::lane::{Lane, LaneID, LaneKind};
+use super::lane::connectivity::Interaction;
+use land_use::buildings::BuildingID;
+
+use cb_util::log::info;
+const LOG_T: &str = "Network Validation";
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub enum NetworkIssueKind {
+    DeadEnd,
+    DisconnectedComponent { n_lanes: u32 },
+    UnattachedBuilding,
+    UnreachableIntersection,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct NetworkIssue {
+    pub kind: NetworkIssueKind,
+    pub position: P2,
+}
+
+pub trait NetworkValidationListener {
+    fn on_network_validated(
+        &mut self,
+        projects: &CVec<ProjectID>,
+        issues: &CVec<NetworkIssue>,
+        world: &mut World,
+    );
+}
+
+#[derive(Compact, Clone)]
+pub struct LaneValidationInfo {
+    lane: LaneID,
+    on_intersection: bool,
+    kind: LaneKind,
+    start: P2,
+    end: P2,
+    successors: CVec<LaneID>,
+    conflicting: CVec<LaneID>,
+    has_predecessors: bool,
+}
+
+// Checks the whole network once construction after implementing projects settled down
+#[derive(Compact, Clone)]
+pub struct NetworkValidator {
+    id: NetworkValidatorID,
+    time: TimeID,
+    projects: CVec<ProjectID>,
+    scheduled_wake_ups: u32,
+    collecting: bool,
+    lanes: CVec<LaneValidationInfo>,
+    unattached_buildings: CVec<P2>,
+}
+
+impl NetworkValidator {
+    pub fn spawn(id: NetworkValidatorID, time: TimeID, _: &mut World) -> NetworkValidator {
+        NetworkValidator {
+            id,
+            time,
+            projects: CVec::new(),
+            scheduled_wake_ups: 0,
+            collecting: false,
+            lanes: CVec::new(),
+            unattached_buildings: CVec::new(),
+        }
+    }
+
+    pub fn validate_after_construction(&mut self, project_id: ProjectID, world: &mut World) {
+        self.projects.push(project_id);
+        // a collection that is already running would see a half-finished network
+        self.collecting = false;
+        self.wake_up_in(VALIDATION_DELAY_TICKS, world);
+    }
+
+    fn wake_up_in(&mut self, ticks: u32, world: &mut World) {
+        self.scheduled_wake_ups += 1;
+        self.time.wake_up_in(Ticks(ticks), self.id_as(), world);
+    }
+
+    pub fn on_lane_report(&mut self, info: &LaneValidationInfo, _: &mut World) {
+        if self.collecting {
+            self.lanes.push(info.clone());
+        }
+    }
+
+    pub fn on_unattached_building(&mut self, position: P2, _: &mut World) {
+        if self.collecting {
+            self.unattached_buildings.push(position);
+        }
+    }
+
+    fn issues(&self) -> CVec<NetworkIssue> {
+        let mut issues = CVec::new();
+
+        let lane_indices = self
+            .lanes
+            .iter()
+            .enumerate()
+            .map(|(i, info)| (info.lane, i))
+            .collect::<HashMap<_, _>>();
+
+        for info in self.lanes.iter() {
+            if info.successors.is_empty() {
+                issues.push(NetworkIssue {
+                    kind: NetworkIssueKind::DeadEnd,
+                    position: info.end,
+                });
+            }
+        }
+
+        let mut components = DisjointSets::new(self.lanes.len());
+        let mut intersections = DisjointSets::new(self.lanes.len());
+
+        for (i, info) in self.lanes.iter().enumerate() {
+            for successor in info.successors.iter() {
+                if let Some(&j) = lane_indices.get(successor) {
+                    components.join(i, j);
+                }
+            }
+
+            if info.on_intersection {
+                for conflicting in info.conflicting.iter() {
+                    if let Some(&j) = lane_indices.get(conflicting) {
+                        if self.lanes[j].on_intersection {
+                            intersections.join(i, j);
+                        }
+                    }
+                }
+            }
+        }
+
+        // the bike network is separate, so it has a main component of its own
+        let component_lanes = components.groups();
+        let largest_component = |kind: LaneKind| {
+            component_lanes
+                .values()
+                .filter(|lanes| self.lanes[lanes[0]].kind == kind)
+                .map(|lanes| lanes.len())
+                .max()
+                .unwrap_or(0)
+        };
+        let mut largest_seen = Vec::new();
+
+        for lanes in component_lanes.values() {
+            let kind = self.lanes[lanes[0]].kind;
+            if lanes.len() == largest_component(kind) && !largest_seen.contains(&kind) {
+                largest_seen.push(kind);
+            } else {
+                issues.push(NetworkIssue {
+                    kind: NetworkIssueKind::DisconnectedComponent {
+                        n_lanes: lanes.len() as u32,
+                    },
+                    position: self.lanes[lanes[0]].start,
+                });
+            }
+        }
+
+        for lanes in intersections.groups().values() {
+            let is_intersection = self.lanes[lanes[0]].on_intersection;
+            if is_intersection && lanes.iter().all(|&i| !self.lanes[i].has_predecessors) {
+                issues.push(NetworkIssue {
+                    kind: NetworkIssueKind::UnreachableIntersection,
+                    position: self.lanes[lanes[0]].start,
+                });
+            }
+        }
+
+        for position in self.unattached_buildings.iter() {
+            issues.push(NetworkIssue {
+                kind: NetworkIssueKind::UnattachedBuilding,
+                position: *position,
+            });
+        }
+
+        issues
+    }
+}
+
+impl Sleeper for NetworkValidator {
+    fn wake(&mut self, _: Instant, world: &mut World) {
+        self.scheduled_wake_ups -= 1;
+        if self.scheduled_wake_ups > 0 {
+            // only the latest implementation decides when to validate
+            return;
+        }
+
+        if self.collecting {
+            let issues = self.issues();
+
+            info(
+                LOG_T,
+                format!(
+                    "Validated {} lanes after implementing {} project(s): {} issues",
+                    self.lanes.len(),
+                    self.projects.len(),
+                    issues.len()
+                ),
+                self.id,
+                world,
+            );
+
+            NetworkValidationListenerID::global_broadcast(world).on_network_validated(
+                self.projects.clone(),
+                issues,
+                world,
+            );
+
+            self.collecting = false;
+            self.projects = CVec::new();
+            self.lanes = CVec::new();
+            self.unattached_buildings = CVec::new();
+        } else if !self.projects.is_empty() {
+            self.collecting = true;
+            self.lanes = CVec::new();
+            self.unattached_buildings = CVec::new();
+
+            LaneID::global_broadcast(world).report_for_validation(self.id, world);
+            BuildingID::global_broadcast(world).report_attachment_for_validation(self.id, world);
+
+            self.wake_up_in(VALIDATION_COLLECTION_TICKS, world);
+        }
+    }
+}
+
+impl Lane {
+    pub fn report_for_validation(&mut self, validator: NetworkValidatorID, world: &mut World) {
+        let mut successors = CVec::new();
+        let mut conflicting = CVec::new();
+        let mut has_predecessors = false;
+
+        for interaction in self.connectivity.interactions.iter() {
+            match *interaction {
+                Interaction::Next { next, .. } => successors.push(next),
+                Interaction::Switch { to, .. } => {
+                    // switching works both ways
+                    successors.push(to);
+                    has_predecessors = true;
+                }
+                Interaction::Previous { .. } => has_predecessors = true,
+                Interaction::Conflicting {
+                    conflicting: other, ..
+                } => conflicting.push(other),
+            }
+        }
+
+        validator.on_lane_report(
+            LaneValidationInfo {
+                lane: self.id,
+                on_intersection: self.connectivity.on_intersection,
+                kind: self.connectivity.kind,
+                start: self.construction.path.start(),
+                end: self.construction.path.end(),
+                successors,
+                conflicting,
+                has_predecessors,
+            },
+            world,
+        );
+    }
+}
+
+// union-find over indices, to group lanes into components and intersections
+struct DisjointSets {
+    parents: Vec<usize>,
+}
+
+impl DisjointSets {
+    fn new(n: usize) -> Self {
+        DisjointSets {
+            parents: (0..n).collect(),
+        }
+    }
+
+    fn root(&mut self, i: usize) -> usize {
+        let mut root = i;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+        self.parents[i] = root;
+        root
+    }
+
+    fn join(&mut self, a: usize, b: usize) {
+        let (root_a, root_b) = (self.root(a), self.root(b));
+        self.parents[root_a] = root_b;
+    }
+
+    fn groups(&mut self) -> HashMap<usize, Vec<usize>> {
+        let mut groups = HashMap::<usize, Vec<usize>>::new();
+        for i in 0..self.parents.len() {
+            let root = self.root(i);
+            groups.entry(root).or_insert_with(Vec::new).push(i);
+        }
+        groups
+    }
+}
+
+pub fn setup(system: &mut ActorSystem) {
+    system.register::<NetworkValidator>();
+    auto_setup(system);
+}
+
+pub fn spawn(world: &mut World, time: TimeID) {
+    NetworkValidatorID::spawn(time, world);
+}
+
+mod kay_auto;
+pub use self::kay_auto::*;
+
+// enough for construction to connect all lanes of typical projects
+const VALIDATION_DELAY_TICKS: u32 = 30;
+const VALIDATION_COLLECTION_TICKS: u32 = 5;
@@ -4,7 +4,10 @@ use arrayvec::ArrayString;
 use serde::de::DeserializeOwned;
 
 pub type Name = ArrayString<[u8; 16]>;
-pub trait Config: Compact + 'static {}
+pub trait Config: Compact + 'static {
+    // fixes up entries that don't make sense, right after they were loaded
+    fn sanitize(&mut self, _name: Name) {}
+}
 
 #[derive(Compact, Clone)]
 pub struct ConfigManager<C: Config> {
@@ -105,8 +108,11 @@ impl<CD: Config + DeserializeOwned> ConfigFileWatcher<CD> {
             let file = File::open(&*(self.file))
                 .expect(&format!("Couldn't find config file {:?}", &*self.file));
             let reader = BufReader::new(file);
-            let new_entries: HashMap<Name, CD> =
+            let mut new_entries: HashMap<Name, CD> =
                 serde_yaml::from_reader(reader).expect("parsing failed");
+            for (name, entry) in &mut new_entries {
+                entry.sanitize(*name);
+            }
             self.target
                 .update_all_entries(new_entries.into_iter().collect(), world);
         }